@@ -0,0 +1,29 @@
+//! Embeds the git commit as `GIT_SHA` for `GET /v1/version`.
+//!
+//! Uses the `GIT_SHA` environment variable when set (Docker builds have no `.git`; pass it as
+//! a build arg), otherwise asks git, otherwise `unknown`.
+
+use std::process::Command;
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    output.status.success().then(|| stdout.trim().to_string())
+}
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    // Rebuild when a commit or checkout moves HEAD
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs/heads", git_dir);
+        println!("cargo:rerun-if-changed={}/packed-refs", git_dir);
+    }
+
+    let sha = std::env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| git(&["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_SHA={}", sha);
+}
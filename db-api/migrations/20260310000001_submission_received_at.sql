@@ -0,0 +1,9 @@
+-- Record the database-clock time a submission was received, independent of submitted_at.
+-- submitted_at stays the ordering key; received_at is never client-supplied, so it remains
+-- trustworthy even if client-claimed submission times are accepted in the future.
+-- Existing rows were written with submitted_at = NOW(), so backfill from it.
+
+ALTER TABLE submissions ADD COLUMN received_at TIMESTAMPTZ;
+UPDATE submissions SET received_at = submitted_at;
+ALTER TABLE submissions ALTER COLUMN received_at SET DEFAULT now();
+ALTER TABLE submissions ALTER COLUMN received_at SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Per-form opt-in: when true, the WASI module rejects submissions whose decrypted answers
+-- contain keys that are not question IDs. Default false keeps existing forms permissive.
+
+ALTER TABLE forms ADD COLUMN strict_answers BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,22 @@
+-- Replay protection: reject an identical ciphertext submitted again (e.g. under another
+-- submitter_id). Legitimate submissions never collide — every EC01 blob uses a fresh random
+-- ephemeral key and nonce, so two encryptions of the same answers still differ.
+--
+-- blob_hash = hex(SHA-256(encrypted_blob)) over the normalized lowercase hex text, set by
+-- db-api on insert. Nullable so rows inserted by older code paths are not rejected.
+
+ALTER TABLE submissions ADD COLUMN blob_hash TEXT;
+
+-- Backfill existing rows; if historical duplicates exist, only the earliest copy gets a hash
+-- so the unique index below can still be built.
+UPDATE submissions s
+SET blob_hash = x.hash
+FROM (
+    SELECT id,
+           encode(sha256(convert_to(encrypted_blob, 'UTF8')), 'hex') AS hash,
+           row_number() OVER (PARTITION BY encrypted_blob ORDER BY submitted_at, id) AS rn
+    FROM submissions
+) x
+WHERE s.id = x.id AND x.rn = 1;
+
+CREATE UNIQUE INDEX idx_submissions_blob_hash ON submissions(blob_hash);
@@ -0,0 +1,8 @@
+-- Free-form tags so creators with many forms can group them on a dashboard.
+-- GIN index serves `tags @> ARRAY[$1]` containment filters; the creator index serves
+-- the per-creator listing that the tag filter narrows.
+
+ALTER TABLE forms ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_forms_tags ON forms USING GIN (tags);
+CREATE INDEX idx_forms_creator ON forms(creator_id, created_at DESC);
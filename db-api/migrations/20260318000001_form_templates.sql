@@ -0,0 +1,9 @@
+-- Forms flagged as templates can be listed in `extends` when creating a form; their questions
+-- are copied into the new form at creation time (see create_form).
+--
+-- forms.extends records that lineage: the template IDs a form was created from. It only
+-- records where the questions came from, so create_form can walk the chain and refuse a
+-- cycle (possible only in rows written outside create_form, such as manual edits).
+
+ALTER TABLE forms ADD COLUMN is_template BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE forms ADD COLUMN extends UUID[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,17 @@
+-- Per-form submission policy: 'one_per_account' (the original behaviour) or 'unlimited'.
+--
+-- The table-wide UNIQUE(form_id, submitter_id) becomes a partial unique index over rows
+-- flagged one_per_account. create_submission sets the flag from the form's policy in the
+-- same INSERT, so enforcement stays in the database and concurrent inserts cannot race.
+
+ALTER TABLE forms ADD COLUMN submission_policy TEXT NOT NULL DEFAULT 'one_per_account'
+    CHECK (submission_policy IN ('one_per_account', 'unlimited'));
+
+ALTER TABLE submissions ADD COLUMN one_per_account BOOLEAN NOT NULL DEFAULT true;
+
+ALTER TABLE submissions DROP CONSTRAINT submissions_form_id_submitter_id_key;
+CREATE UNIQUE INDEX idx_submissions_one_per_account
+    ON submissions(form_id, submitter_id) WHERE one_per_account;
+
+-- Lookups by submitter on 'unlimited' forms are not covered by the partial index
+CREATE INDEX idx_submissions_form_submitter ON submissions(form_id, submitter_id);
@@ -0,0 +1,5 @@
+-- Cross-form lookup of one account's submissions (subject-access and erasure requests).
+-- idx_submissions_form_submitter leads with form_id, so it cannot serve a submitter-only
+-- filter; this index also matches the endpoint's newest-first keyset order.
+
+CREATE INDEX idx_submissions_submitter ON submissions(submitter_id, submitted_at DESC, id DESC);
@@ -0,0 +1,7 @@
+-- Per-form required ciphertext format: 'any' (the original behaviour: a single EC01 blob
+-- or a JSON object of per-question EC01 blobs), 'ec01' (single blob only) or
+-- 'ec01_per_question' (per-question only). Enforced in the same INSERT as the submission,
+-- and again by the WASI module before it forwards a blob.
+
+ALTER TABLE forms ADD COLUMN encryption_scheme TEXT NOT NULL DEFAULT 'any'
+    CHECK (encryption_scheme IN ('any', 'ec01', 'ec01_per_question'));
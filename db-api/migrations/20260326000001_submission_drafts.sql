@@ -0,0 +1,10 @@
+-- Draft submissions: a respondent's saved progress, stored alongside final submissions.
+--
+-- Drafts never count toward one-per-account (create_submission stores them with
+-- one_per_account = false), and each account has at most one draft per form, which
+-- create_submission upserts. A final submission deletes the account's draft in the same
+-- transaction. Reads exclude drafts unless asked for them.
+
+ALTER TABLE submissions ADD COLUMN is_draft BOOLEAN NOT NULL DEFAULT false;
+
+CREATE UNIQUE INDEX idx_submissions_draft ON submissions(form_id, submitter_id) WHERE is_draft;
@@ -0,0 +1,24 @@
+-- Live submission events: NOTIFY `submission_inserted` for every new final submission,
+-- relayed by db-api to `GET /v1/forms/:form_id/submissions/stream` clients.
+--
+-- A NOTIFY inside a transaction is delivered only when it commits, so listeners never see
+-- a rolled-back insert. The trigger covers every insert path (single, batch, import).
+-- Drafts are skipped. The payload is metadata only (NOTIFY payloads are capped at 8000
+-- bytes, and blobs are not needed to know something arrived).
+
+CREATE FUNCTION notify_submission_inserted() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('submission_inserted', json_build_object(
+        'id', NEW.id,
+        'form_id', NEW.form_id,
+        'submitter_id', NEW.submitter_id,
+        'submitted_at', NEW.submitted_at
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_notify_inserted
+    AFTER INSERT ON submissions
+    FOR EACH ROW WHEN (NOT NEW.is_draft)
+    EXECUTE FUNCTION notify_submission_inserted();
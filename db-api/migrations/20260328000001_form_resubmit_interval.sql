@@ -0,0 +1,6 @@
+-- Per-form throttle on repeat submissions: with submission_policy = 'unlimited', an account
+-- must wait min_resubmit_interval_secs after its last final submission before the next one
+-- (create_submission answers 429). NULL means no minimum. Drafts are not throttled.
+
+ALTER TABLE forms ADD COLUMN min_resubmit_interval_secs INTEGER
+    CHECK (min_resubmit_interval_secs > 0);
@@ -0,0 +1,5 @@
+-- Per-form denylist: accounts create_submission refuses with 403 (drafts included).
+-- Set on create or replaced with PUT /v1/forms/:id/denied-submitters; existing
+-- submissions from a newly denied account are kept.
+
+ALTER TABLE forms ADD COLUMN denied_submitters TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,5 @@
+-- Pause a form without closing it: while paused, create_submission refuses final
+-- submissions with 423 (drafts can still be saved). Reads are unaffected.
+-- Toggled with PATCH /v1/forms/:id/pause and /resume.
+
+ALTER TABLE forms ADD COLUMN paused BOOLEAN NOT NULL DEFAULT false;
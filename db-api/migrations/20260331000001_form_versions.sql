@@ -0,0 +1,77 @@
+-- Form versioning: every revision of a form's questions is kept in form_versions, and each
+-- submission records the version it answered, so editing questions.json after responses
+-- exist does not reinterpret old answers against the new questions.
+--
+-- forms.version is the current revision. Triggers keep both sides up to date on every
+-- write path (seeding, POST /v1/forms, archive import, submission inserts):
+-- - changing forms.questions bumps forms.version and records the new revision
+-- - a new submission is stamped with its form's current version; re-saving a draft
+--   restamps it (see create_submission), re-encrypting a blob does not
+-- - archive import sets near_forms.importing for its transaction and writes the archived
+--   history and submission versions itself, so neither of the above applies to it
+--
+-- Existing forms start at version 1 with their current questions, and existing
+-- submissions are assumed to have answered it.
+
+ALTER TABLE forms ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE form_versions (
+    form_id    UUID NOT NULL REFERENCES forms(id) ON DELETE CASCADE,
+    version    INTEGER NOT NULL,
+    questions  JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (form_id, version)
+);
+
+INSERT INTO form_versions (form_id, version, questions, created_at)
+SELECT id, version, questions, COALESCE(created_at, NOW()) FROM forms;
+
+ALTER TABLE submissions ADD COLUMN form_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE FUNCTION bump_form_version() RETURNS trigger AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER forms_bump_version
+    BEFORE UPDATE OF questions ON forms
+    FOR EACH ROW WHEN (OLD.questions IS DISTINCT FROM NEW.questions)
+    EXECUTE FUNCTION bump_form_version();
+
+CREATE FUNCTION record_form_version() RETURNS trigger AS $$
+BEGIN
+    IF current_setting('near_forms.importing', true) = 'on' THEN
+        RETURN NULL;
+    END IF;
+    INSERT INTO form_versions (form_id, version, questions)
+    VALUES (NEW.id, NEW.version, NEW.questions);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER forms_record_version_on_insert
+    AFTER INSERT ON forms
+    FOR EACH ROW
+    EXECUTE FUNCTION record_form_version();
+
+CREATE TRIGGER forms_record_version_on_update
+    AFTER UPDATE OF questions ON forms
+    FOR EACH ROW WHEN (OLD.questions IS DISTINCT FROM NEW.questions)
+    EXECUTE FUNCTION record_form_version();
+
+CREATE FUNCTION stamp_submission_form_version() RETURNS trigger AS $$
+BEGIN
+    IF current_setting('near_forms.importing', true) = 'on' THEN
+        RETURN NEW;
+    END IF;
+    NEW.form_version := COALESCE((SELECT version FROM forms WHERE id = NEW.form_id), NEW.form_version);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_stamp_form_version
+    BEFORE INSERT ON submissions
+    FOR EACH ROW
+    EXECUTE FUNCTION stamp_submission_form_version();
@@ -0,0 +1,38 @@
+-- Form co-owners: every account in form_owners may read and manage a form's responses
+-- through the WASI module, not just forms.creator_id.
+--
+-- The creator is always an owner. Triggers keep that true on every write path (seeding,
+-- POST /v1/forms, archive import): a new form gets its creator as first owner, and a
+-- creator change (PATCH /v1/forms/:id/creator or FORM_CREATOR_ID) swaps the old creator's
+-- row for the new one. Other owners are managed with PUT/DELETE /v1/forms/:id/owners/:account.
+
+CREATE TABLE form_owners (
+    form_id    UUID NOT NULL REFERENCES forms(id) ON DELETE CASCADE,
+    account_id TEXT NOT NULL,
+    added_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (form_id, account_id)
+);
+
+INSERT INTO form_owners (form_id, account_id, added_at)
+SELECT id, creator_id, COALESCE(created_at, NOW()) FROM forms;
+
+CREATE FUNCTION sync_creator_owner() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        DELETE FROM form_owners WHERE form_id = OLD.id AND account_id = OLD.creator_id;
+    END IF;
+    INSERT INTO form_owners (form_id, account_id) VALUES (NEW.id, NEW.creator_id)
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER forms_creator_owner_on_insert
+    AFTER INSERT ON forms
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_creator_owner();
+
+CREATE TRIGGER forms_creator_owner_on_update
+    AFTER UPDATE OF creator_id ON forms
+    FOR EACH ROW WHEN (OLD.creator_id IS DISTINCT FROM NEW.creator_id)
+    EXECUTE FUNCTION sync_creator_owner();
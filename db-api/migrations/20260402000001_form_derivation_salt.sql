@@ -0,0 +1,14 @@
+-- Secret per-form salt mixed into the WASI module's form key derivation, so a form's key
+-- cannot be derived from public values. It is never returned by public endpoints: only
+-- API-secret holders read it (the WASI module via GET /v1/forms/:id/derivation-salt, and
+-- archive export). Browsers encrypt to the finished form public key from the WASI module.
+--
+-- Existing forms keep NULL (the original derivation), since changing their key would leave
+-- their submissions undecryptable. The default is set after adding the column so it only
+-- applies to forms inserted from now on: 64 hex characters from two random UUIDs.
+-- Archive import keeps the archived value, NULL included.
+
+ALTER TABLE forms ADD COLUMN derivation_salt TEXT
+    CHECK (derivation_salt ~ '^[0-9a-f]{64}$');
+ALTER TABLE forms ALTER COLUMN derivation_salt
+    SET DEFAULT encode(sha256((gen_random_uuid()::text || gen_random_uuid()::text)::bytea), 'hex');
@@ -0,0 +1,8 @@
+-- Cached form encryption public key (compressed secp256k1, lowercase hex), served by
+-- GET /v1/forms/:id/public-key so a frontend needs one HTTP call to get the point to
+-- encrypt against. db-api cannot derive it (that needs the master key's public half and
+-- the WASI module's derivation), so the WASI module registers it with
+-- PUT /v1/forms/:id/public-key. NULL until registered.
+
+ALTER TABLE forms ADD COLUMN public_key TEXT
+    CHECK (public_key ~ '^0[23][0-9a-f]{64}$');
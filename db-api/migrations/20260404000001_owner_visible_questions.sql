@@ -0,0 +1,5 @@
+-- Question-level access for co-owners: the WASI module's ReadResponses returns an owner
+-- only the answers to the questions listed here. NULL (the default) means every question.
+-- The creator is never restricted. Set with PUT /v1/forms/:id/owners/:account/visible-questions.
+
+ALTER TABLE form_owners ADD COLUMN visible_questions TEXT[];
@@ -0,0 +1,6 @@
+-- Per-form retention for ephemeral surveys: a background task in db-api deletes submissions
+-- (drafts included) once submitted_at is more than retention_days old. NULL keeps them
+-- forever. Served by idx_submissions_form_time.
+
+ALTER TABLE forms ADD COLUMN retention_days INTEGER
+    CHECK (retention_days BETWEEN 1 AND 36500);
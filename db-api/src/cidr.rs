@@ -0,0 +1,130 @@
+//! IP networks in CIDR notation, for the `TRUSTED_PROXIES` allowlist.
+//!
+//! `extract_client_ip` only believes `X-Forwarded-For` / `X-Real-IP` when the connecting
+//! peer is inside one of these networks, so clients cannot pick their own rate-limit key.
+
+use std::{fmt, net::IpAddr, str::FromStr};
+
+/// An IPv4 or IPv6 network, e.g. `10.0.0.0/8` or `fd00::/8`. A bare address is a
+/// single-host network (`/32` or `/128`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Whether `ip` is inside this network. IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`)
+    /// match IPv4 networks, since dual-stack listeners report IPv4 peers that way.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix_len)).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix_len)).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// Networks covering every address; what `RATE_LIMIT_TRUST_PROXY=true` means.
+    pub fn any() -> [IpCidr; 2] {
+        [
+            IpCidr { network: IpAddr::from([0u8; 4]), prefix_len: 0 },
+            IpCidr { network: IpAddr::from([0u8; 16]), prefix_len: 0 },
+        ]
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let network: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("'{}' is not an IP address or CIDR", s))?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix {
+            None => max_len,
+            Some(p) => p
+                .parse::<u8>()
+                .ok()
+                .filter(|len| *len <= max_len)
+                .ok_or_else(|| format!("'{}' has an invalid prefix length (max /{})", s, max_len))?,
+        };
+        Ok(IpCidr { network, prefix_len })
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// Parse a comma-separated list of networks (whitespace around entries is ignored).
+pub fn parse_list(raw: &str) -> Result<Vec<IpCidr>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(IpCidr::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ipv4_prefix_matching() {
+        let net: IpCidr = "10.1.0.0/16".parse().unwrap();
+        assert!(net.contains(ip("10.1.255.3")));
+        assert!(!net.contains(ip("10.2.0.1")));
+        assert!(net.contains(ip("::ffff:10.1.0.9")));
+        assert!(!net.contains(ip("fd00::1")));
+    }
+
+    #[test]
+    fn ipv6_prefix_matching() {
+        let net: IpCidr = "fd00::/8".parse().unwrap();
+        assert!(net.contains(ip("fd12:3456::1")));
+        assert!(!net.contains(ip("fe80::1")));
+        assert!(!net.contains(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn bare_address_is_single_host() {
+        let net: IpCidr = "192.168.1.5".parse().unwrap();
+        assert_eq!(net.to_string(), "192.168.1.5/32");
+        assert!(net.contains(ip("192.168.1.5")));
+        assert!(!net.contains(ip("192.168.1.6")));
+    }
+
+    #[test]
+    fn any_matches_everything() {
+        let any = IpCidr::any();
+        for addr in ["1.2.3.4", "255.255.255.255", "::1", "2001:db8::7"] {
+            assert!(any.iter().any(|net| net.contains(ip(addr))), "{}", addr);
+        }
+    }
+
+    #[test]
+    fn parse_list_and_errors() {
+        let nets = parse_list(" 10.0.0.0/8, ::1 ,").unwrap();
+        assert_eq!(nets.len(), 2);
+        assert!(parse_list("10.0.0.0/33").unwrap_err().contains("prefix length"));
+        assert!(parse_list("fd00::/129").is_err());
+        assert!(parse_list("10.0.0.0/8, proxy.internal").unwrap_err().contains("proxy.internal"));
+    }
+}
@@ -7,42 +7,154 @@
 //!
 //! - **Types** — request/response structs (`Form`, `Submission`, etc.)
 //! - **Rate Limiting** — per-IP token-bucket `RateLimiter`
-//! - **App State** — shared `AppState` (pool, secret, limiter)
-//! - **Middleware** — `require_api_secret`, `rate_limit`, `extract_client_ip`
-//! - **Validation** — `validate_near_account_id`
-//! - **Handlers** — `health`, `get_form`, `get_submissions`, `create_submission`
+//! - **Form Cache** — TTL `FormCache` in front of `get_form`
+//! - **App State** — shared `AppState` (primary/read pools, secret hash, limiter)
+//! - **Middleware** — `require_api_secret`, `require_api_secret_or_token`, `rate_limit`,
+//!   `json_charset`, `no_store`, `retry_after`, the `ApiJson` body extractor,
+//!   `extract_client_ip`, submission token minting/verification
+//! - **Validation** — `validate_near_account_id`, `validate_questions`, `question_errors`, `validate_tags`,
+//!   `validate_resubmit_interval`, `validate_retention_days`, `validate_denied_submitters`, `is_compressed_public_key`
+//! - **Transactions** — `retry_transaction` (retries on serialization failure / deadlock)
+//! - **Handlers** — `health`, `health_ready`, `version`, `get_form`, `get_form_schema`, `get_form_fields`, `validate_form_questions`,
+//!   `get_form_public_key`, `set_form_public_key`, `get_form_derivation_salt`,
+//!   `get_form_versions`, `get_form_version`, `get_submissions`,
+//!   `get_submission`,
+//!   `submission_exists`,
+//!   `create_submission`, `update_submission`, `batch_create_submission`, `create_form`, `list_creator_forms`,
+//!   `update_form_creator`, `get_form_owners`, `add_form_owner`, `remove_form_owner`, `set_owner_visible_questions`,
+//!   `update_denied_submitters`, `update_form_retention`, `pause_form`, `resume_form`,
+//!   `create_submission_token`, `export_form_archive`, `import_form_archive`, `export_submissions_csv`
+//! - **Submission Events** — `SubmissionEvents`, `relay_submission_events`, `stream_submissions` (SSE)
 //! - **App Builder** — `build_app` assembles the axum `Router`
+//! - **Maintenance** — `delete_orphaned_submissions`, `delete_expired_submissions` (periodic background cleanup)
+//!
+//! Answer JSON Schema generation (and the `/fields` projection) lives in [`schema`]; startup configuration in [`config`];
+//! `TRUSTED_PROXIES` networks in [`cidr`].
+
+pub mod cidr;
+pub mod config;
+pub mod schema;
 
 use axum::{
-    extract::{Path, Query, Request, State},
-    http::StatusCode,
+    error_handling::HandleErrorLayer,
+    extract::{rejection::JsonRejection, DefaultBodyLimit, FromRequest, Path, Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
     middleware::{self, Next},
-    response::Response,
-    routing::{get, post},
-    Json, Router,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, patch, post, put},
+    Extension, Json, Router,
 };
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::{FromRow, PgPool};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
-use axum::http::{HeaderValue, Method};
+use std::{collections::{BTreeMap, HashMap}, net::SocketAddr, sync::Arc};
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower::{limit::GlobalConcurrencyLimitLayer, load_shed::LoadShedLayer, BoxError, ServiceBuilder};
 use tower_http::cors::CorsLayer;
+use tokio::sync::{broadcast, watch};
 use tower_http::limit::RequestBodyLimitLayer;
-use tracing::error;
+use tracing::{error, warn};
 use uuid::Uuid;
 
+/// Migrations embedded at compile time; applied by `main` at startup and compared
+/// against `_sqlx_migrations` by `GET /v1/health/ready`.
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
 // ==================== Types ====================
 
+/// How many submissions an account may make to a form (`forms.submission_policy`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SubmissionPolicy {
+    /// A second submission from the same account is rejected with 409
+    #[default]
+    OnePerAccount,
+    /// Any number of submissions per account (replayed ciphertexts are still rejected)
+    Unlimited,
+}
+
+/// Ciphertext format a form requires for `encrypted_blob` (`forms.encryption_scheme`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum EncryptionScheme {
+    /// Either layout below
+    #[default]
+    Any,
+    /// One hex EC01 ciphertext over the whole answers object
+    Ec01,
+    /// A JSON object of question ID to hex EC01 ciphertext (question IDs are visible)
+    Ec01PerQuestion,
+}
+
+impl EncryptionScheme {
+    /// The concrete scheme of a validated `encrypted_blob` (never `Any`).
+    pub fn of_blob(blob: &str) -> Self {
+        if blob.starts_with('{') {
+            EncryptionScheme::Ec01PerQuestion
+        } else {
+            EncryptionScheme::Ec01
+        }
+    }
+
+    /// Whether a form with this scheme accepts `blob`.
+    pub fn accepts(self, blob: &str) -> bool {
+        self == EncryptionScheme::Any || self == Self::of_blob(blob)
+    }
+
+    /// Value stored in `forms.encryption_scheme`, for error messages.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EncryptionScheme::Any => "any",
+            EncryptionScheme::Ec01 => "ec01",
+            EncryptionScheme::Ec01PerQuestion => "ec01_per_question",
+        }
+    }
+}
+
 /// Database row for a form (maps to `forms` table).
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Form {
     pub id: Uuid,
     pub creator_id: String,
     pub title: String,
     pub questions: serde_json::Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Reject answers with keys that are not question IDs (enforced by the WASI module)
+    pub strict_answers: bool,
+    /// Creator-defined labels for grouping forms (see `validate_tags`)
+    pub tags: Vec<String>,
+    /// Can be listed in another form's `extends` (see `create_form`)
+    pub is_template: bool,
+    /// Enforced by `create_submission` through `submissions.one_per_account`
+    pub submission_policy: SubmissionPolicy,
+    /// Checked against each submission's blob layout (see `EncryptionScheme::accepts`)
+    pub encryption_scheme: EncryptionScheme,
+    /// Seconds an account must wait between final submissions (`unlimited` policy only)
+    pub min_resubmit_interval_secs: Option<i32>,
+    /// Days submissions are kept before `delete_expired_submissions` removes them; None
+    /// keeps them forever
+    pub retention_days: Option<i32>,
+    /// Accounts `create_submission` refuses with 403 (see `validate_denied_submitters`)
+    pub denied_submitters: Vec<String>,
+    /// Final submissions are refused with 423 until resumed (see `set_form_paused`)
+    pub paused: bool,
+    /// Current revision of `questions`; bumped by a trigger whenever they change, with
+    /// every revision kept in `form_versions`
+    pub version: i32,
+    /// Mixed into the WASI module's form key derivation; random for new forms, NULL for
+    /// forms created before the column existed (see `is_derivation_salt`)
+    pub derivation_salt: Option<String>,
+    /// Compressed form public key registered by the WASI module (see `set_form_public_key`)
+    pub public_key: Option<String>,
 }
 
-/// JSON response for `GET /v1/forms/:id` (timestamps as RFC 3339 strings).
+/// JSON response for `GET /v1/forms/:id` (timestamps as UTC RFC 3339 strings, see `format_timestamp`).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FormResponse {
     pub id: String,
@@ -50,6 +162,50 @@ pub struct FormResponse {
     pub title: String,
     pub questions: serde_json::Value,
     pub created_at: String,
+    pub strict_answers: bool,
+    pub tags: Vec<String>,
+    pub is_template: bool,
+    pub submission_policy: SubmissionPolicy,
+    pub encryption_scheme: EncryptionScheme,
+    /// Absent from archives written before the column existed
+    #[serde(default)]
+    pub min_resubmit_interval_secs: Option<i32>,
+    /// Omitted when submissions are kept forever, so existing forms' ETags are unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_days: Option<i32>,
+    /// Omitted when empty; `get_form` always empties it, since that endpoint is public
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_submitters: Vec<String>,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub version: i32,
+    /// Omitted until the WASI module registers it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+impl From<Form> for FormResponse {
+    fn from(form: Form) -> Self {
+        Self {
+            id: form.id.to_string(),
+            creator_id: form.creator_id,
+            title: form.title,
+            questions: form.questions,
+            created_at: format_timestamp(&form.created_at),
+            strict_answers: form.strict_answers,
+            tags: form.tags,
+            is_template: form.is_template,
+            submission_policy: form.submission_policy,
+            encryption_scheme: form.encryption_scheme,
+            min_resubmit_interval_secs: form.min_resubmit_interval_secs,
+            retention_days: form.retention_days,
+            denied_submitters: form.denied_submitters,
+            paused: form.paused,
+            version: form.version,
+            public_key: form.public_key,
+        }
+    }
 }
 
 /// Database row for a submission (maps to `submissions` table).
@@ -60,23 +216,365 @@ pub struct Submission {
     pub submitter_id: String,
     pub encrypted_blob: String,
     pub submitted_at: chrono::DateTime<chrono::Utc>,
+    /// Database-clock time the row was inserted (never client-supplied)
+    pub received_at: chrono::DateTime<chrono::Utc>,
+    /// Saved progress rather than a final submission
+    pub is_draft: bool,
+    /// `forms.version` when the answers were stored (see `form_versions`)
+    pub form_version: i32,
 }
 
-/// JSON response for a single submission (used inside `PaginatedSubmissions`).
+/// JSON response for a single submission (`GET /v1/forms/:id/submissions/:submitter_id`
+/// and inside `PaginatedSubmissions`).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SubmissionResponse {
     pub id: String,
     pub submitter_id: String,
     pub encrypted_blob: String,
     pub submitted_at: String,
+    pub received_at: String,
+    pub is_draft: bool,
+    /// Absent from archives written before the column existed
+    #[serde(default)]
+    pub form_version: i32,
+}
+
+impl From<Submission> for SubmissionResponse {
+    fn from(s: Submission) -> Self {
+        Self {
+            id: s.id.to_string(),
+            submitter_id: s.submitter_id,
+            encrypted_blob: s.encrypted_blob,
+            submitted_at: format_timestamp(&s.submitted_at),
+            received_at: format_timestamp(&s.received_at),
+            is_draft: s.is_draft,
+            form_version: s.form_version,
+        }
+    }
 }
 
-/// Request body for `POST /v1/submissions` (hex-encoded EC01 ciphertext).
+/// Request body for `POST /v1/submissions` (hex-encoded EC01 ciphertext, or a JSON object
+/// of question ID to hex EC01 ciphertext; see `validate_submission_request`).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateSubmissionRequest {
     pub form_id: String,
     pub submitter_id: String,
     pub encrypted_blob: String,
+    /// Save progress instead of submitting: replaces the account's previous draft for the
+    /// form and does not count toward one-per-account
+    #[serde(default)]
+    pub is_draft: bool,
+}
+
+/// Request body for `POST /v1/forms`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateFormRequest {
+    pub creator_id: String,
+    pub title: String,
+    pub questions: serde_json::Value,
+    #[serde(default)]
+    pub strict_answers: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Mark this form as a template other forms can extend
+    #[serde(default)]
+    pub is_template: bool,
+    /// Template form IDs whose questions are merged in before `questions` (see `merge_questions`)
+    #[serde(default)]
+    pub extends: Vec<String>,
+    /// Defaults to `one_per_account`
+    #[serde(default)]
+    pub submission_policy: SubmissionPolicy,
+    /// Defaults to `any`
+    #[serde(default)]
+    pub encryption_scheme: EncryptionScheme,
+    /// Minimum seconds between an account's final submissions; requires the `unlimited`
+    /// policy (see `validate_resubmit_interval`). Defaults to none.
+    pub min_resubmit_interval_secs: Option<i32>,
+    /// Delete submissions this many days after they are made (see
+    /// `validate_retention_days`). Defaults to keeping them forever.
+    pub retention_days: Option<i32>,
+    /// Accounts refused by `create_submission` (see `validate_denied_submitters`)
+    #[serde(default)]
+    pub denied_submitters: Vec<String>,
+}
+
+/// Query parameters for `GET /v1/creators/:creator_id/forms`.
+#[derive(Debug, Deserialize)]
+pub struct ListFormsParams {
+    /// Only return forms carrying this tag
+    pub tag: Option<String>,
+}
+
+/// Response for `GET /v1/creators/:creator_id/forms` (newest first).
+#[derive(Debug, Serialize)]
+pub struct FormListResponse {
+    pub forms: Vec<FormResponse>,
+}
+
+/// Database row for one revision of a form's questions (maps to `form_versions`).
+#[derive(Debug, FromRow)]
+pub struct FormVersion {
+    pub version: i32,
+    pub questions: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// JSON response for `GET /v1/forms/:id/versions/:version` (and inside `FormVersionsResponse`
+/// and form archives).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormVersionResponse {
+    pub version: i32,
+    pub questions: serde_json::Value,
+    pub created_at: String,
+}
+
+impl From<FormVersion> for FormVersionResponse {
+    fn from(v: FormVersion) -> Self {
+        Self {
+            version: v.version,
+            questions: v.questions,
+            created_at: format_timestamp(&v.created_at),
+        }
+    }
+}
+
+/// Response for `GET /v1/forms/:id/versions` (oldest first).
+#[derive(Debug, Serialize)]
+pub struct FormVersionsResponse {
+    pub versions: Vec<FormVersionResponse>,
+}
+
+/// Response for `GET /v1/forms/:id/fields`.
+#[derive(Debug, Serialize)]
+pub struct FormFieldsResponse {
+    pub fields: Vec<schema::FormField>,
+}
+
+/// Response for `GET /v1/forms/:id/submissions/:submitter_id/exists`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmissionExistsResponse {
+    pub submitted: bool,
+}
+
+/// Request body for `PUT /v1/submissions` (replace a stored blob, e.g. after key rotation).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateSubmissionRequest {
+    #[serde(flatten)]
+    pub submission: CreateSubmissionRequest,
+    /// Hex SHA-256 of the stored `encrypted_blob` being replaced (compare-and-swap guard)
+    pub previous_blob_hash: String,
+}
+
+/// Request body for `PATCH /v1/forms/:id/creator`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCreatorRequest {
+    pub new_creator_id: String,
+}
+
+/// Database row for a form owner (maps to `form_owners`, joined with `forms`).
+#[derive(Debug, FromRow)]
+pub struct FormOwner {
+    pub account_id: String,
+    pub added_at: chrono::DateTime<chrono::Utc>,
+    /// The form's `creator_id`, who cannot be removed (only replaced by a transfer)
+    pub is_creator: bool,
+    /// Questions whose answers ReadResponses returns to this owner; None means all
+    pub visible_questions: Option<Vec<String>>,
+}
+
+/// JSON form of `FormOwner` (inside `FormOwnersResponse`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormOwnerResponse {
+    pub account_id: String,
+    pub added_at: String,
+    pub is_creator: bool,
+    /// Omitted for an unrestricted owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible_questions: Option<Vec<String>>,
+}
+
+impl From<FormOwner> for FormOwnerResponse {
+    fn from(o: FormOwner) -> Self {
+        Self {
+            account_id: o.account_id,
+            added_at: format_timestamp(&o.added_at),
+            is_creator: o.is_creator,
+            visible_questions: o.visible_questions,
+        }
+    }
+}
+
+/// Response for `GET /v1/forms/:id/owners` and the owner add/remove endpoints
+/// (creator first, then by when they were added).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormOwnersResponse {
+    pub owners: Vec<FormOwnerResponse>,
+}
+
+/// Request body for `PUT /v1/forms/:id/owners/:account/visible-questions`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetVisibleQuestionsRequest {
+    /// Question IDs the owner may read; null lifts the restriction
+    pub visible_questions: Option<Vec<String>>,
+}
+
+/// Request body for `POST /v1/forms/validate`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateQuestionsRequest {
+    /// Candidate `questions` array, as it would be sent to `POST /v1/forms` or seeded
+    pub questions: serde_json::Value,
+}
+
+/// One rule a `questions` array breaks (see `question_errors`).
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestionError {
+    /// 0-based position of the offending question; absent for errors about the whole array
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+/// JSON response for `POST /v1/forms/validate`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateQuestionsResponse {
+    pub valid: bool,
+    /// Omitted when `valid`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<QuestionError>,
+}
+
+/// Request body for `PUT /v1/forms/:id/retention`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateRetentionRequest {
+    /// Days to keep submissions; null keeps them forever
+    pub retention_days: Option<i32>,
+}
+
+/// Request body for `PUT /v1/forms/:id/denied-submitters`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateDeniedSubmittersRequest {
+    /// Replaces the whole list; empty clears it
+    pub denied_submitters: Vec<String>,
+}
+
+/// Request body for `PUT /v1/forms/:id/public-key`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetFormPublicKeyRequest {
+    /// Compressed secp256k1 public key, hex (see `is_compressed_public_key`)
+    pub public_key: String,
+}
+
+/// JSON response for `GET /v1/forms/:id/derivation-salt`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormDerivationSaltResponse {
+    pub form_id: String,
+    /// None for forms created before the column existed (original derivation)
+    pub derivation_salt: Option<String>,
+}
+
+/// JSON response for `GET /v1/forms/:id/public-key`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormPublicKeyResponse {
+    pub form_id: String,
+    pub public_key: String,
+}
+
+/// Request body for `POST /v1/submission-tokens`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateSubmissionTokenRequest {
+    pub form_id: String,
+    /// Bind the token to one submitter; unbound tokens accept any `submitter_id`
+    #[serde(default)]
+    pub submitter_id: Option<String>,
+    /// Lifetime in seconds (default `DEFAULT_SUBMISSION_TOKEN_TTL_SECS`, max `MAX_SUBMISSION_TOKEN_TTL_SECS`)
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+/// JSON response for `POST /v1/submission-tokens`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmissionTokenResponse {
+    pub token: String,
+    pub expires_at: String,
+}
+
+/// What a verified submission token allows; added to request extensions by
+/// `require_api_secret_or_token` and enforced by `create_submission`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmissionGrant {
+    pub form_id: Uuid,
+    pub submitter_id: Option<String>,
+}
+
+/// Query parameters for `POST /v1/submissions/batch`.
+#[derive(Debug, Deserialize)]
+pub struct BatchSubmissionParams {
+    /// When true, any failed item rolls back the entire batch (default: false)
+    pub atomic: Option<bool>,
+}
+
+/// Per-item outcome in a `BatchSubmissionResponse` (`id` on success, `error` on failure).
+#[derive(Debug, Serialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the request array
+    pub index: usize,
+    /// HTTP status the item would have received from `POST /v1/submissions`
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for `POST /v1/submissions/batch`.
+#[derive(Debug, Serialize)]
+pub struct BatchSubmissionResponse {
+    pub inserted: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult>,
+}
+
+/// Query parameters for `POST /v1/forms/import`.
+#[derive(Debug, Deserialize)]
+pub struct ImportArchiveParams {
+    /// Import under a fresh form ID (and fresh submission IDs) instead of the archived ones
+    pub new_id: Option<bool>,
+}
+
+/// A form archive as read by `POST /v1/forms/import` (written by `GET /v1/forms/:id/archive`).
+/// `form` and `submissions` stay raw so the checksum covers exactly the bytes received.
+#[derive(Debug, Deserialize)]
+struct FormArchive<'a> {
+    format: String,
+    version: u32,
+    #[serde(borrow)]
+    form: &'a serde_json::value::RawValue,
+    #[serde(borrow)]
+    submissions: Vec<&'a serde_json::value::RawValue>,
+    checksum: String,
+}
+
+/// The `form` object of a form archive: the form as `GET /v1/forms/:id` returns it, plus
+/// its question history, so restored submissions keep the revision they answered, and its
+/// derivation salt, so the restored form keeps its key.
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchivedForm {
+    #[serde(flatten)]
+    form: FormResponse,
+    /// Every revision in `form_versions`, oldest first; the last is the current one
+    versions: Vec<FormVersionResponse>,
+    /// Kept out of `FormResponse` because it is secret (see `get_form_derivation_salt`).
+    /// Omitted for forms without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    derivation_salt: Option<String>,
+}
+
+/// Response for `POST /v1/forms/import`.
+#[derive(Debug, Serialize)]
+pub struct ImportArchiveResponse {
+    pub form: FormResponse,
+    pub imported_submissions: usize,
 }
 
 /// Standard error envelope returned by all endpoints on failure.
@@ -91,17 +589,178 @@ pub struct HealthResponse {
     pub status: String,
 }
 
+/// Response for `GET /v1/health/ready` — whether every embedded migration is applied.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// `ready` or `migrations_pending`
+    pub status: String,
+    /// Newest migration version embedded in this binary
+    pub expected_version: Option<i64>,
+    /// Newest successfully applied version in `_sqlx_migrations`
+    pub applied_version: Option<i64>,
+    /// Embedded versions not (successfully) applied yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_versions: Vec<i64>,
+}
+
+/// Response for `GET /v1/version` — which build is live and how far its schema got.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    /// `CARGO_PKG_VERSION`
+    pub version: String,
+    /// Commit the binary was built from (see `build.rs`; `unknown` if unavailable)
+    pub git_sha: String,
+    /// Newest migration version embedded in this binary
+    pub expected_migration_version: Option<i64>,
+    /// Newest successfully applied version in `_sqlx_migrations` (None if unreadable)
+    pub migration_version: Option<i64>,
+}
+
+/// Query parameters for `GET /v1/forms/:id/submissions`.
+/// Use either `offset` or `cursor` (from a previous page's `next_cursor`), not both.
 #[derive(Debug, Deserialize)]
 pub struct PaginationParams {
     pub offset: Option<i64>,
     pub limit: Option<i64>,
+    /// Opaque keyset cursor (`next_cursor` from the previous page)
+    pub cursor: Option<String>,
+    /// Include `total_count` (computed with `COUNT(*) OVER ()`; skipped on cursor pages)
+    pub with_total: Option<bool>,
+    /// `desc` (newest first, default) or `asc`; a cursor must be reused with the order it came from
+    pub order: Option<String>,
+    /// RFC 3339 timestamp; only submissions with `submitted_at` strictly after it are returned
+    pub since: Option<String>,
+    /// Also return drafts (default false: final submissions only)
+    pub include_drafts: Option<bool>,
 }
 
 /// Paginated response for `GET /v1/forms/:id/submissions` (auth required).
 #[derive(Debug, Serialize)]
 pub struct PaginatedSubmissions {
     pub submissions: Vec<SubmissionResponse>,
-    pub total_count: i64,
+    /// Total submissions for the form; present only with `?with_total=true` on a non-cursor page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+    /// Whether more submissions exist after this page
+    pub has_more: bool,
+    /// Cursor for the next page (present when `has_more` is true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Query parameters for `GET /v1/submitters/:submitter_id/submissions`.
+#[derive(Debug, Deserialize)]
+pub struct SubmitterSubmissionsParams {
+    /// Page size (default 200, clamped to 1..=200)
+    pub limit: Option<i64>,
+    /// Opaque keyset cursor (`next_cursor` from the previous page)
+    pub cursor: Option<String>,
+}
+
+/// Submission metadata row (no blob) for `get_submitter_submissions`.
+#[derive(Debug, FromRow)]
+struct SubmissionMetaRow {
+    id: Uuid,
+    form_id: Uuid,
+    submitted_at: chrono::DateTime<chrono::Utc>,
+    received_at: chrono::DateTime<chrono::Utc>,
+    is_draft: bool,
+}
+
+/// One submission in `SubmitterSubmissionsResponse`: metadata only, never the blob.
+#[derive(Debug, Serialize)]
+pub struct SubmitterSubmission {
+    pub id: String,
+    pub form_id: String,
+    pub submitted_at: String,
+    pub received_at: String,
+    pub is_draft: bool,
+}
+
+impl From<SubmissionMetaRow> for SubmitterSubmission {
+    fn from(row: SubmissionMetaRow) -> Self {
+        Self {
+            id: row.id.to_string(),
+            form_id: row.form_id.to_string(),
+            submitted_at: format_timestamp(&row.submitted_at),
+            received_at: format_timestamp(&row.received_at),
+            is_draft: row.is_draft,
+        }
+    }
+}
+
+/// Response for `GET /v1/submitters/:submitter_id/submissions` (newest first, all forms).
+#[derive(Debug, Serialize)]
+pub struct SubmitterSubmissionsResponse {
+    pub submissions: Vec<SubmitterSubmission>,
+    /// Whether more submissions exist after this page
+    pub has_more: bool,
+    /// Cursor for the next page (present when `has_more` is true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Submission row plus the optional windowed total used by `get_submissions`.
+#[derive(Debug, FromRow)]
+struct SubmissionPageRow {
+    #[sqlx(flatten)]
+    submission: Submission,
+    total_count: Option<i64>,
+}
+
+/// Default lifetime of a submission token (10 minutes).
+pub const DEFAULT_SUBMISSION_TOKEN_TTL_SECS: u64 = 600;
+
+/// Longest lifetime a submission token can be minted with (1 hour).
+pub const MAX_SUBMISSION_TOKEN_TTL_SECS: u64 = 3600;
+
+/// Header carrying a submission token on `POST /v1/submissions`.
+const SUBMISSION_TOKEN_HEADER: &str = "Submission-Token";
+
+/// Maximum number of items accepted by `POST /v1/submissions/batch`.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Request body limit for the batch route (the per-blob cap still applies to each item).
+const MAX_BATCH_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Request body limit for `POST /v1/forms/import` (a whole form archive)
+const MAX_ARCHIVE_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// `format` marker in a form archive
+const ARCHIVE_FORMAT: &str = "near-forms-archive";
+
+/// Archive layout written by `export_form_archive`; import refuses any other. Version 2
+/// added the form's question history and each submission's `form_version`.
+const ARCHIVE_VERSION: u32 = 2;
+
+/// Submissions read per query while streaming an archive
+const ARCHIVE_PAGE_SIZE: i64 = 200;
+
+/// Default cap on a decoded encrypted blob (200 KB). Overridable via `MAX_BLOB_SIZE_BYTES`,
+/// which must match the WASI module's setting of the same name.
+pub const DEFAULT_MAX_BLOB_SIZE: usize = 200 * 1024;
+
+/// Default cap on forms per `creator_id` in `POST /v1/forms`. Overridable via
+/// `MAX_FORMS_PER_CREATOR`; `0` removes the cap.
+pub const DEFAULT_MAX_FORMS_PER_CREATOR: u32 = 1000;
+
+/// Default cap on a `GET /v1/forms/:id/submissions` page body (32 MiB). Overridable via
+/// `MAX_RESPONSE_BYTES`; must leave room for one submission at the blob cap
+/// (see `request_body_limit`).
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Per-submission allowance in a page estimate for everything but the blob and
+/// submitter ID (id, timestamps, keys, quoting).
+const SUBMISSION_JSON_OVERHEAD_BYTES: usize = 256;
+
+/// Request body allowance on top of the hex blob for the JSON envelope
+/// (`form_id`, `submitter_id`, keys and quoting).
+const BODY_OVERHEAD_BYTES: usize = 100 * 1024;
+
+/// Request body limit for single-item routes, derived from the blob cap so a body that
+/// fits always reaches the handler's own size check (and its clearer error).
+fn request_body_limit(max_blob_size: usize) -> usize {
+    max_blob_size * 2 + BODY_OVERHEAD_BYTES
 }
 
 // ==================== Rate Limiting ====================
@@ -193,19 +852,118 @@ impl RateLimiter {
     }
 }
 
+// ==================== Form Cache ====================
+
+/// Default `FORM_CACHE_TTL_SECS`: how long `get_form` may serve a cached row.
+pub const DEFAULT_FORM_CACHE_TTL_SECS: u64 = 60;
+
+/// Maximum number of cached forms. When full, expired entries are dropped first and
+/// then uncached forms are simply read from the database.
+const MAX_CACHED_FORMS: usize = 1_000;
+
+/// TTL cache of `forms` rows for `get_form`, which the WASI module calls on every
+/// read and submit.
+///
+/// Handlers that change a form call `invalidate`, so this process never serves a stale
+/// row after its own writes. A miss that will be cached is read from the primary pool,
+/// not the replica, so a lagging replica cannot put a pre-write row back for a whole TTL.
+/// Misses are not cached, so a newly created or imported form is visible at once. Another db-api instance writing the same database is only
+/// picked up once the entry expires. `None` TTL disables caching.
+#[derive(Clone)]
+pub struct FormCache {
+    inner: Arc<std::sync::Mutex<HashMap<Uuid, (std::time::Instant, Form)>>>,
+    ttl: Option<std::time::Duration>,
+}
+
+impl FormCache {
+    pub fn new(ttl: Option<std::time::Duration>) -> Self {
+        Self {
+            inner: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.ttl.is_some()
+    }
+
+    /// The cached row for `form_id`, unless missing or expired.
+    fn get(&self, form_id: Uuid) -> Option<Form> {
+        let ttl = self.ttl?;
+        // Mutex poisoned — treat as a miss and read from the database
+        let entries = self.inner.lock().ok()?;
+        entries
+            .get(&form_id)
+            .filter(|(cached_at, _)| cached_at.elapsed() < ttl)
+            .map(|(_, form)| form.clone())
+    }
+
+    fn insert(&self, form: &Form) {
+        let Some(ttl) = self.ttl else { return };
+        let Ok(mut entries) = self.inner.lock() else { return };
+        if entries.len() >= MAX_CACHED_FORMS && !entries.contains_key(&form.id) {
+            entries.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+            if entries.len() >= MAX_CACHED_FORMS {
+                return;
+            }
+        }
+        entries.insert(form.id, (std::time::Instant::now(), form.clone()));
+    }
+
+    /// Drop `form_id` so the next `get_form` reads it from the database.
+    fn invalidate(&self, form_id: Uuid) {
+        if let Ok(mut entries) = self.inner.lock() {
+            entries.remove(&form_id);
+        }
+    }
+}
+
 // ==================== App State ====================
 
 #[derive(Clone)]
 pub struct AppState {
+    /// Primary pool — all writes (and the health ping) go here
     pub pool: PgPool,
-    pub api_secret: String,
+    /// Pool for read-only handlers (`get_form` with caching off, `get_submissions`). Points
+    /// at a read replica when `DATABASE_REPLICA_URL` is set, otherwise a clone of `pool`.
+    pub read_pool: PgPool,
+    /// SHA-256 of the shared API secret (see `hash_api_secret`); the raw secret is not kept
+    pub api_secret_hash: [u8; 32],
     pub rate_limiter: RateLimiter,
-    pub trust_proxy: bool,
+    /// Peers allowed to set `X-Forwarded-For` / `X-Real-IP` (see `extract_client_ip`)
+    pub trusted_proxies: Arc<[cidr::IpCidr]>,
+    /// In-flight request cap for protected routes; excess requests get 503 instead of queueing
+    pub max_concurrent_requests: usize,
+    /// HMAC key for submission tokens (see `derive_submission_token_key`)
+    pub submission_token_key: [u8; 32],
+    /// Maximum decoded `encrypted_blob` size in bytes (see `DEFAULT_MAX_BLOB_SIZE`).
+    /// Also sizes the request body limits in `build_app`.
+    pub max_blob_size: usize,
+    /// Limits for `questions` in `POST /v1/forms` (same as applied to the seeded form)
+    pub question_limits: QuestionLimits,
+    /// Forms one `creator_id` may own before `POST /v1/forms` is refused; None is unlimited
+    pub max_forms_per_creator: Option<u32>,
+    /// Fed by `relay_submission_events`; read by `stream_submissions`
+    pub submission_events: SubmissionEvents,
+    /// Rows served by `get_form`; see `FormCache`
+    pub form_cache: FormCache,
+    /// Largest `get_submissions` page body; bigger pages get 413 (see `DEFAULT_MAX_RESPONSE_BYTES`)
+    pub max_response_bytes: usize,
 }
 
 // ==================== Middleware ====================
 
-/// Middleware to verify API-Secret header (constant-time comparison)
+/// Hash an API secret for storage in `AppState`.
+///
+/// Only the digest is held in memory, so a heap dump or debug print of the state does not
+/// reveal a reusable credential. SHA-256 (rather than a slow password hash) is sufficient
+/// because `API_SECRET` is a high-entropy token of at least 32 characters, and it keeps the
+/// per-request check cheap.
+pub fn hash_api_secret(secret: &str) -> [u8; 32] {
+    Sha256::digest(secret.as_bytes()).into()
+}
+
+/// Middleware to verify API-Secret header (hash, then constant-time comparison)
 async fn require_api_secret(
     State(state): State<AppState>,
     request: Request,
@@ -218,8 +976,10 @@ async fn require_api_secret(
         .get("API-Secret")
         .and_then(|h| h.to_str().ok());
 
-    let provided = header.map(|h| h.as_bytes()).unwrap_or(&[]);
-    if !constant_time_eq(provided, state.api_secret.as_bytes()) {
+    // A missing header can never match: hashing "" is fine because the configured
+    // secret is at least 32 characters and therefore never empty.
+    let provided = hash_api_secret(header.unwrap_or(""));
+    if !constant_time_eq(&provided, &state.api_secret_hash) {
         return Err((StatusCode::UNAUTHORIZED, Json(ErrorResponse {
             error: "Invalid or missing API-Secret header".to_string(),
         })));
@@ -228,28 +988,147 @@ async fn require_api_secret(
     Ok(next.run(request).await)
 }
 
-/// Extract the client IP, optionally trusting X-Forwarded-For when behind a reverse proxy.
-/// Takes the first (leftmost) IP from X-Forwarded-For, falling back to the direct connection IP.
+/// Derive the submission-token HMAC key from the raw API secret.
+///
+/// Computed once at startup (before the raw secret is dropped) so tokens can be verified
+/// without keeping `API_SECRET` around, and so the stored `api_secret_hash` alone is not
+/// enough to mint tokens.
+pub fn derive_submission_token_key(secret: &str) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(b"db-api:submission-token");
+    mac.finalize().into_bytes().into()
+}
+
+/// Signed portion of a submission token: `{form_id}:{expires_unix}:{submitter_id}`.
+/// `:` never appears in a UUID, an integer or a NEAR account ID, so the fields are unambiguous.
+fn submission_token_payload(form_id: Uuid, expires_at: i64, submitter_id: Option<&str>) -> String {
+    format!("{}:{}:{}", form_id, expires_at, submitter_id.unwrap_or(""))
+}
+
+/// Mint a submission token: `{payload}:{hex HMAC-SHA256(key, payload)}`.
+pub fn mint_submission_token(
+    key: &[u8; 32],
+    form_id: Uuid,
+    submitter_id: Option<&str>,
+    expires_at: i64,
+) -> String {
+    let payload = submission_token_payload(form_id, expires_at, submitter_id);
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    format!("{}:{}", payload, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verify a submission token's signature and expiry (`now` in Unix seconds).
+/// Returns `None` for any malformed, forged or expired token.
+pub fn verify_submission_token(key: &[u8; 32], token: &str, now: i64) -> Option<SubmissionGrant> {
+    let (payload, signature) = token.rsplit_once(':')?;
+    let mut parts = payload.splitn(3, ':');
+    let form_id = Uuid::parse_str(parts.next()?).ok()?;
+    let expires_at: i64 = parts.next()?.parse().ok()?;
+    let submitter_id = parts.next()?;
+
+    // Check the signature before trusting any field (verify_slice is constant-time)
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&hex::decode(signature).ok()?).ok()?;
+
+    if now >= expires_at {
+        return None;
+    }
+    Some(SubmissionGrant {
+        form_id,
+        submitter_id: (!submitter_id.is_empty()).then(|| submitter_id.to_string()),
+    })
+}
+
+/// Middleware for `POST /v1/submissions` and the submission `exists` check: accepts the API-Secret header, or a valid
+/// `Submission-Token` for direct submission from a frontend.
+///
+/// Token requests are rate limited per IP like public routes, and carry a
+/// `SubmissionGrant` extension that the handler checks against the request body.
+async fn require_api_secret_or_token(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if request.headers().contains_key("API-Secret") {
+        return require_api_secret(State(state), request, next).await;
+    }
+    let Some(token) = request
+        .headers()
+        .get(SUBMISSION_TOKEN_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string)
+    else {
+        return require_api_secret(State(state), request, next).await;
+    };
+
+    let client_ip = request
+        .extensions()
+        .get::<axum::extract::ConnectInfo<SocketAddr>>()
+        .map(|ci| extract_client_ip(request.headers(), ci.0, &state.trusted_proxies))
+        .unwrap_or(std::net::IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED));
+    if !state.rate_limiter.check(client_ip) {
+        return Err((StatusCode::TOO_MANY_REQUESTS, Json(ErrorResponse {
+            error: "Too many requests".to_string(),
+        })));
+    }
+
+    let grant = verify_submission_token(&state.submission_token_key, &token, chrono::Utc::now().timestamp())
+        .ok_or((StatusCode::UNAUTHORIZED, Json(ErrorResponse {
+            error: "Invalid or expired submission token".to_string(),
+        })))?;
+    request.extensions_mut().insert(grant);
+    Ok(next.run(request).await)
+}
+
+/// Extract the client IP, trusting proxy headers only from peers in `trusted_proxies`.
+///
+/// When the connecting peer is trusted, X-Forwarded-For is walked right to left past every
+/// trusted hop and the first untrusted address is the client (with every network trusted,
+/// that is the leftmost entry). Without X-Forwarded-For, X-Real-IP is used. An untrusted
+/// peer, a missing header, or a malformed entry falls back to the direct connection IP,
+/// so a client cannot choose its own rate-limit key.
 fn extract_client_ip(
     headers: &axum::http::HeaderMap,
     connect_addr: SocketAddr,
-    trust_proxy: bool,
+    trusted_proxies: &[cidr::IpCidr],
 ) -> std::net::IpAddr {
-    if trust_proxy {
-        if let Some(xff) = headers.get("X-Forwarded-For").and_then(|h| h.to_str().ok()) {
-            if let Some(first_ip) = xff.split(',').next() {
-                if let Ok(ip) = first_ip.trim().parse::<std::net::IpAddr>() {
-                    return ip;
+    let peer = connect_addr.ip();
+    let trusted = |ip: std::net::IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+    if !trusted(peer) {
+        return peer;
+    }
+
+    if let Some(xff) = headers.get("X-Forwarded-For").and_then(|h| h.to_str().ok()) {
+        let mut client = peer;
+        for entry in xff.rsplit(',').map(str::trim) {
+            match entry.parse::<std::net::IpAddr>() {
+                Ok(ip) => {
+                    client = ip;
+                    if !trusted(ip) {
+                        break;
+                    }
+                }
+                Err(_) => {
+                    tracing::warn!(
+                        "X-Forwarded-For entry {:?} is not a valid IP — falling back to connection IP {}",
+                        entry,
+                        peer
+                    );
+                    return peer;
                 }
-                tracing::warn!(
-                    "X-Forwarded-For first entry {:?} is not a valid IP — falling back to connection IP {}",
-                    first_ip.trim(),
-                    connect_addr.ip()
-                );
             }
         }
+        return client;
     }
-    connect_addr.ip()
+
+    headers
+        .get("X-Real-IP")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(peer)
 }
 
 /// Rate limiting middleware for public endpoints (per-IP)
@@ -259,7 +1138,7 @@ async fn rate_limit(
     request: Request,
     next: Next,
 ) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
-    let client_ip = extract_client_ip(request.headers(), addr, state.trust_proxy);
+    let client_ip = extract_client_ip(request.headers(), addr, &state.trusted_proxies);
     if !state.rate_limiter.check(client_ip) {
         return Err((StatusCode::TOO_MANY_REQUESTS, Json(ErrorResponse {
             error: "Too many requests".to_string(),
@@ -268,12 +1147,124 @@ async fn rate_limit(
     Ok(next.run(request).await)
 }
 
-// ==================== Validation ====================
+/// `Content-Type` for every JSON body this API returns.
+pub const JSON_CONTENT_TYPE: &str = "application/json; charset=utf-8";
 
-/// Validate a string as a NEAR account ID (2-64 chars, lowercase alphanumeric + . - _).
-/// Rejects implicit accounts (64-char hex strings).
-pub fn validate_near_account_id(account_id: &str, field_name: &str) -> Result<(), String> {
-    if account_id.len() < 2 || account_id.len() > 64 {
+/// Add an explicit charset to JSON responses (axum's `Json` sends bare `application/json`).
+/// Applied to the whole router so success bodies and `ErrorResponse`s look the same.
+async fn json_charset(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let is_bare_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes() == b"application/json");
+    if is_bare_json {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(JSON_CONTENT_TYPE));
+    }
+    response
+}
+
+/// `Cache-Control: no-store` on authenticated routes so intermediaries and browsers never
+/// keep submission data (or auth errors about it).
+async fn no_store(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
+
+/// `Retry-After` (seconds) sent with 503s: a shed request or a pool wait that timed out
+/// usually clears within a second.
+const RETRY_AFTER_SECS: &str = "1";
+
+/// Add `Retry-After` to every 503 that lacks one, so load shedding, the stream cap and
+/// database pool exhaustion all tell clients when to come back.
+async fn retry_after(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+        response
+            .headers_mut()
+            .entry(header::RETRY_AFTER)
+            .or_insert(HeaderValue::from_static(RETRY_AFTER_SECS));
+    }
+    response
+}
+
+/// Response for a failed query. `PoolTimedOut` means no connection freed up within
+/// `DATABASE_ACQUIRE_TIMEOUT_SECS` — a 503 the caller can retry; anything else is logged
+/// and reported as a 500 with `message`.
+fn db_error_response(e: sqlx::Error, message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    if matches!(e, sqlx::Error::PoolTimedOut) {
+        warn!("Database pool exhausted: no connection available within the acquire timeout");
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(ErrorResponse {
+            error: "Database is busy, please retry shortly".to_string(),
+        }));
+    }
+    error!("Database error: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
+        error: message.to_string(),
+    }))
+}
+
+/// Map errors from the load-shedding stack to JSON responses.
+/// `Overloaded` means every concurrency permit is taken — tell the caller to back off.
+async fn handle_overload(err: BoxError) -> (StatusCode, Json<ErrorResponse>) {
+    if err.is::<tower::load_shed::error::Overloaded>() {
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(ErrorResponse {
+            error: "Server is busy, please retry shortly".to_string(),
+        }));
+    }
+    error!("Unhandled middleware error: {}", err);
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
+        error: "Internal server error".to_string(),
+    }))
+}
+
+/// `Json<T>` for request bodies, rejecting with the [`ErrorResponse`] envelope instead of
+/// axum's plain-text body. A body that parses as JSON but does not fit `T` (missing or
+/// mistyped field) is a 400 like any other invalid input, not axum's 422; a missing JSON
+/// `Content-Type` (415) and body read failures keep axum's status. The message is axum's,
+/// which names the offending field and position.
+pub struct ApiJson<T>(pub T);
+
+#[axum::async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(request, state).await {
+            Ok(Json(value)) => Ok(ApiJson(value)),
+            Err(rejection) => {
+                let status = match rejection {
+                    JsonRejection::JsonDataError(_) => StatusCode::BAD_REQUEST,
+                    _ => rejection.status(),
+                };
+                Err((status, Json(ErrorResponse { error: rejection.body_text() })))
+            }
+        }
+    }
+}
+
+// ==================== Validation ====================
+
+/// Format a timestamp as UTC RFC 3339 with microsecond precision and a `Z` suffix
+/// (e.g. `2026-03-01T12:00:00.123456Z`), matching PostgreSQL `TIMESTAMPTZ` resolution.
+/// All timestamps leave the API in this one shape so lexical and chronological order agree.
+pub fn format_timestamp(ts: &chrono::DateTime<chrono::Utc>) -> String {
+    ts.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Validate a string as a NEAR account ID (2-64 chars, lowercase alphanumeric + . - _).
+/// Rejects implicit accounts (64-char hex strings).
+pub fn validate_near_account_id(account_id: &str, field_name: &str) -> Result<(), String> {
+    if account_id.len() < 2 || account_id.len() > 64 {
         return Err(format!("{} must be 2-64 characters", field_name));
     }
     if !account_id
@@ -294,6 +1285,322 @@ pub fn validate_near_account_id(account_id: &str, field_name: &str) -> Result<()
     Ok(())
 }
 
+/// Maximum number of tags on a form.
+pub const MAX_TAGS: usize = 20;
+
+/// Maximum length (in characters) of a single tag.
+pub const MAX_TAG_LENGTH: usize = 50;
+
+/// Validate form tags: at most `MAX_TAGS`, each non-empty, no longer than
+/// `MAX_TAG_LENGTH` characters, free of control characters, and not repeated.
+/// Tags are matched exactly by the `?tag=` filter, so no case folding is applied.
+pub fn validate_tags(tags: &[String]) -> Result<(), String> {
+    if tags.len() > MAX_TAGS {
+        return Err(format!("form has {} tags (max {})", tags.len(), MAX_TAGS));
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tag.trim().is_empty() {
+            return Err(format!("tag #{} is empty", i + 1));
+        }
+        let len = tag.chars().count();
+        if len > MAX_TAG_LENGTH {
+            return Err(format!("tag #{} is {} characters (max {})", i + 1, len, MAX_TAG_LENGTH));
+        }
+        if tag.chars().any(char::is_control) {
+            return Err(format!("tag #{} contains control characters", i + 1));
+        }
+        if tags[..i].contains(tag) {
+            return Err(format!("duplicate tag '{}'", tag));
+        }
+    }
+    Ok(())
+}
+
+/// Longest `min_resubmit_interval_secs` a form may set (30 days).
+pub const MAX_RESUBMIT_INTERVAL_SECS: i32 = 30 * 24 * 60 * 60;
+
+/// Validate `min_resubmit_interval_secs`: 1 to `MAX_RESUBMIT_INTERVAL_SECS`, and only on
+/// `unlimited` forms (under `one_per_account` there is no second submission to throttle).
+pub fn validate_resubmit_interval(policy: SubmissionPolicy, interval_secs: Option<i32>) -> Result<(), String> {
+    let Some(secs) = interval_secs else {
+        return Ok(());
+    };
+    if !(1..=MAX_RESUBMIT_INTERVAL_SECS).contains(&secs) {
+        return Err(format!(
+            "min_resubmit_interval_secs must be between 1 and {}",
+            MAX_RESUBMIT_INTERVAL_SECS
+        ));
+    }
+    if policy != SubmissionPolicy::Unlimited {
+        return Err("min_resubmit_interval_secs requires submission_policy 'unlimited'".to_string());
+    }
+    Ok(())
+}
+
+/// Longest `retention_days` a form may set (100 years, the column's CHECK bound).
+pub const MAX_RETENTION_DAYS: i32 = 36500;
+
+/// Validate `retention_days`: 1 to `MAX_RETENTION_DAYS` when set.
+pub fn validate_retention_days(days: Option<i32>) -> Result<(), String> {
+    match days {
+        Some(days) if !(1..=MAX_RETENTION_DAYS).contains(&days) => {
+            Err(format!("retention_days must be between 1 and {}", MAX_RETENTION_DAYS))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// True for a well-formed `forms.derivation_salt`: 64 lowercase hex characters, the same
+/// rule as the column's CHECK constraint.
+pub fn is_derivation_salt(salt: &str) -> bool {
+    salt.len() == 64 && salt.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// True for a compressed secp256k1 public key in hex: `02` or `03` then 64 hex
+/// characters (either case; stored lowercase).
+pub fn is_compressed_public_key(key: &str) -> bool {
+    key.len() == 66
+        && (key.starts_with("02") || key.starts_with("03"))
+        && key.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Maximum number of owners (creator included) a form may have.
+pub const MAX_FORM_OWNERS: usize = 50;
+
+/// Maximum number of accounts on a form's `denied_submitters`.
+pub const MAX_DENIED_SUBMITTERS: usize = 1000;
+
+/// Validate `denied_submitters`: at most `MAX_DENIED_SUBMITTERS` valid, non-implicit NEAR
+/// account IDs (implicit accounts cannot submit anyway), none repeated.
+pub fn validate_denied_submitters(accounts: &[String]) -> Result<(), String> {
+    if accounts.len() > MAX_DENIED_SUBMITTERS {
+        return Err(format!(
+            "denied_submitters has {} accounts (max {})",
+            accounts.len(),
+            MAX_DENIED_SUBMITTERS
+        ));
+    }
+    let mut seen = std::collections::HashSet::with_capacity(accounts.len());
+    for account in accounts {
+        validate_near_account_id(account, "denied_submitters")?;
+        if !seen.insert(account.as_str()) {
+            return Err(format!("denied_submitters: duplicate account '{}'", account));
+        }
+    }
+    Ok(())
+}
+
+/// Maximum number of templates a form may extend.
+pub const MAX_EXTENDS: usize = 10;
+
+/// First template found twice on one path of the `extends` graph rooted at `roots`, or
+/// None when the graph is acyclic. `lineage` maps each form ID to its stored `extends`;
+/// IDs missing from it are treated as leaves.
+pub fn find_extends_cycle(roots: &[Uuid], lineage: &HashMap<Uuid, Vec<Uuid>>) -> Option<Uuid> {
+    fn visit(
+        id: Uuid,
+        lineage: &HashMap<Uuid, Vec<Uuid>>,
+        on_path: &mut std::collections::HashSet<Uuid>,
+        done: &mut std::collections::HashSet<Uuid>,
+    ) -> Option<Uuid> {
+        if done.contains(&id) {
+            return None;
+        }
+        if !on_path.insert(id) {
+            return Some(id);
+        }
+        for &parent in lineage.get(&id).into_iter().flatten() {
+            if let Some(cycle) = visit(parent, lineage, on_path, done) {
+                return Some(cycle);
+            }
+        }
+        on_path.remove(&id);
+        done.insert(id);
+        None
+    }
+
+    let mut on_path = std::collections::HashSet::new();
+    let mut done = std::collections::HashSet::new();
+    roots.iter().find_map(|&id| visit(id, lineage, &mut on_path, &mut done))
+}
+
+/// Concatenate question lists in order; a question whose `id` was already seen replaces the
+/// earlier definition in place (so a form can override a template question without moving
+/// it). Questions without a string `id` are appended as-is and left to `validate_questions`.
+pub fn merge_questions(sources: &[&serde_json::Value]) -> Result<serde_json::Value, String> {
+    let mut merged: Vec<serde_json::Value> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for source in sources {
+        let list = source
+            .as_array()
+            .ok_or_else(|| "questions must be a JSON array".to_string())?;
+        for question in list {
+            match question.get("id").and_then(|v| v.as_str()) {
+                Some(id) => match positions.get(id) {
+                    Some(&i) => merged[i] = question.clone(),
+                    None => {
+                        positions.insert(id.to_string(), merged.len());
+                        merged.push(question.clone());
+                    }
+                },
+                None => merged.push(question.clone()),
+            }
+        }
+    }
+    Ok(serde_json::Value::Array(merged))
+}
+
+/// Size limits applied to a form's `questions` array before it is stored.
+#[derive(Debug, Clone, Copy)]
+pub struct QuestionLimits {
+    /// Maximum number of questions in a form
+    pub max_questions: usize,
+    /// Maximum number of entries in a question's `options` list
+    pub max_options: usize,
+    /// Maximum length (in characters) of a question label or option
+    pub max_label_length: usize,
+}
+
+impl Default for QuestionLimits {
+    fn default() -> Self {
+        Self {
+            max_questions: 200,
+            max_options: 100,
+            max_label_length: 1000,
+        }
+    }
+}
+
+/// Validate a form's `questions` JSON against `limits`.
+/// Keeps oversized definitions out of the DB so `GET /v1/forms/:id` stays well under the
+/// 250 KB response cap used by the WASI module.
+///
+/// A question's optional `renamed_from` lists the IDs it had in earlier revisions, so
+/// ReadResponses can file old answers under the current ID. Each old ID may be claimed by
+/// one question only and must not be the ID of a current question.
+///
+/// Returns the first of [`question_errors`].
+pub fn validate_questions(questions: &serde_json::Value, limits: &QuestionLimits) -> Result<(), String> {
+    match question_errors(questions, limits).into_iter().next() {
+        Some(error) => Err(error.message),
+        None => Ok(()),
+    }
+}
+
+/// Every rule `validate_questions` enforces, checked in full rather than stopping at the
+/// first failure (what `POST /v1/forms/validate` reports). Errors are in question order.
+pub fn question_errors(questions: &serde_json::Value, limits: &QuestionLimits) -> Vec<QuestionError> {
+    let Some(list) = questions.as_array() else {
+        return vec![QuestionError { index: None, message: "questions must be a JSON array".to_string() }];
+    };
+    let mut errors = Vec::new();
+    let current_ids: std::collections::HashSet<&str> =
+        list.iter().filter_map(|q| q.get("id")?.as_str()).collect();
+    let mut renamed_ids = std::collections::HashSet::new();
+    if list.len() > limits.max_questions {
+        errors.push(QuestionError {
+            index: None,
+            message: format!("form has {} questions (max {})", list.len(), limits.max_questions),
+        });
+    }
+    for (i, question) in list.iter().enumerate() {
+        let mut error = |message: String| errors.push(QuestionError { index: Some(i), message });
+        let id = question
+            .get("id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("#{}", i + 1));
+        if let Some(label) = question.get("label").and_then(|v| v.as_str()) {
+            let len = label.chars().count();
+            if len > limits.max_label_length {
+                error(format!(
+                    "question {} label is {} characters (max {})",
+                    id, len, limits.max_label_length
+                ));
+            }
+        }
+        if let Some(options) = question.get("options").and_then(|v| v.as_array()) {
+            if options.len() > limits.max_options {
+                error(format!(
+                    "question {} has {} options (max {})",
+                    id,
+                    options.len(),
+                    limits.max_options
+                ));
+            }
+            for option in options.iter().filter_map(|o| o.as_str()) {
+                let len = option.chars().count();
+                if len > limits.max_label_length {
+                    error(format!(
+                        "question {} has an option of {} characters (max {})",
+                        id, len, limits.max_label_length
+                    ));
+                }
+            }
+        }
+        if let Some(renamed_from) = question.get("renamed_from") {
+            let old_ids = renamed_from
+                .as_array()
+                .filter(|ids| ids.iter().all(|v| v.as_str().is_some_and(|s| !s.is_empty())));
+            let Some(old_ids) = old_ids else {
+                error(format!("question {} renamed_from must be an array of question IDs", id));
+                continue;
+            };
+            for old_id in old_ids.iter().filter_map(|v| v.as_str()) {
+                if current_ids.contains(old_id) {
+                    error(format!("question {} renamed_from {} is still a question ID", id, old_id));
+                } else if !renamed_ids.insert(old_id) {
+                    error(format!("question ID {} is in more than one renamed_from", old_id));
+                }
+            }
+        }
+    }
+    errors
+}
+
+// ==================== Transactions ====================
+
+/// SQLSTATEs that mean "run the transaction again": serialization_failure, deadlock_detected.
+const RETRYABLE_SQLSTATES: &[&str] = &["40001", "40P01"];
+
+/// Attempts made by `retry_transaction` before giving up (first try included).
+pub const MAX_TRANSACTION_ATTEMPTS: u32 = 3;
+
+/// Backoff before the first retry; doubles on each further retry.
+const TRANSACTION_RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Whether `err` is a transient conflict that a fresh attempt can succeed past.
+fn is_retryable_transaction_error(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|db_err| db_err.code())
+        .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref()))
+}
+
+/// Run `attempt` up to `MAX_TRANSACTION_ATTEMPTS` times, retrying with exponential backoff
+/// when it fails with a serialization failure (40001) or deadlock (40P01).
+///
+/// `attempt` must begin and commit its own transaction so each retry starts clean (a
+/// transaction that hit 40001 is aborted and cannot continue). Any other error, or the last
+/// retryable one, is returned as-is for the caller to map.
+pub async fn retry_transaction<T, F, Fut>(mut attempt: F) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut delay = TRANSACTION_RETRY_BASE_DELAY;
+    for attempt_no in 1.. {
+        match attempt().await {
+            Err(e) if attempt_no < MAX_TRANSACTION_ATTEMPTS && is_retryable_transaction_error(&e) => {
+                tracing::warn!("Transaction conflict (attempt {}), retrying: {}", attempt_no, e);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    unreachable!("loop returns on the final attempt")
+}
+
 // ==================== Handlers ====================
 
 /// GET /health - Health check with database verification (no auth required)
@@ -312,11 +1619,156 @@ async fn health(State(state): State<AppState>) -> Result<Json<HealthResponse>, (
     }))
 }
 
+/// GET /health/ready - Readiness probe: 200 once the database schema is current (public)
+///
+/// Compares the migrations embedded in this binary (`MIGRATOR`) with the successful rows in
+/// `_sqlx_migrations`; returns 503 while any are pending, e.g. a new binary rolled out ahead
+/// of its migration, or the table doesn't exist yet.
+async fn health_ready(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<ReadinessResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let applied = applied_migrations(&state.pool).await.map_err(|e| {
+        error!("Readiness check failed: {}", e);
+        (StatusCode::SERVICE_UNAVAILABLE, Json(ErrorResponse {
+            error: "database unreachable".to_string(),
+        }))
+    })?;
+
+    let expected = embedded_migrations();
+    let pending_versions: Vec<i64> = expected
+        .iter()
+        .copied()
+        .filter(|v| !applied.contains(v))
+        .collect();
+    let ready = pending_versions.is_empty();
+
+    Ok((
+        if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE },
+        Json(ReadinessResponse {
+            status: if ready { "ready" } else { "migrations_pending" }.to_string(),
+            expected_version: expected.iter().copied().max(),
+            applied_version: applied.last().copied(),
+            pending_versions,
+        }),
+    ))
+}
+
+/// Successfully applied migration versions, ascending; empty if migrations never ran.
+async fn applied_migrations(pool: &PgPool) -> Result<Vec<i64>, sqlx::Error> {
+    match sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success ORDER BY version")
+        .fetch_all(pool)
+        .await
+    {
+        // 42P01 undefined_table: migrations have never run against this database
+        Err(e) if e.as_database_error().and_then(|d| d.code()).as_deref() == Some("42P01") => Ok(Vec::new()),
+        result => result,
+    }
+}
+
+/// Migration versions embedded in this binary (`MIGRATOR`), ascending.
+fn embedded_migrations() -> Vec<i64> {
+    MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| m.version)
+        .collect()
+}
+
+/// GET /version - Build and schema version of this deployment (public)
+///
+/// Still answers when the database is down (`migration_version` is then null), since
+/// "which build is this" matters most while debugging a broken deploy.
+async fn version(State(state): State<AppState>) -> Json<VersionResponse> {
+    let migration_version = match applied_migrations(&state.pool).await {
+        Ok(applied) => applied.last().copied(),
+        Err(e) => {
+            error!("Version check could not read migrations: {}", e);
+            None
+        }
+    };
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: env!("GIT_SHA").to_string(),
+        expected_migration_version: embedded_migrations().last().copied(),
+        migration_version,
+    })
+}
+
 /// GET /forms/:form_id - Get form details (public)
+/// Sets a strong `ETag` and returns 304 Not Modified when `If-None-Match` matches.
+/// Served from `FormCache` when possible.
 async fn get_form(
     State(state): State<AppState>,
     Path(form_id_str): Path<String>,
-) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let form = match state.form_cache.get(form_id) {
+        Some(form) => form,
+        None => {
+            // A row that will be cached comes from the primary (see `FormCache`)
+            let pool = if state.form_cache.is_enabled() { &state.pool } else { &state.read_pool };
+            let form = sqlx::query_as::<_, Form>("SELECT * FROM forms WHERE id = $1")
+                .bind(form_id)
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| db_error_response(e, "Database error"))?
+                .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+                    error: "Form not found".to_string(),
+                })))?;
+            state.form_cache.insert(&form);
+            form
+        }
+    };
+
+    let mut response = FormResponse::from(form);
+    // Who a creator has blocked is not public
+    response.denied_submitters.clear();
+
+    let etag = form_etag(&response);
+    let etag_header = HeaderValue::from_str(&etag).expect("ETag is always valid ASCII");
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| etag_matches(v, &etag));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response());
+    }
+
+    Ok(([(header::ETAG, etag_header)], Json(response)).into_response())
+}
+
+/// Strong ETag for a form: quoted SHA-256 of the serialized `FormResponse`.
+/// Covers every field (including `created_at`), so any change to the stored form changes the tag.
+fn form_etag(form: &FormResponse) -> String {
+    let bytes = serde_json::to_vec(form).expect("FormResponse serialization cannot fail");
+    format!("\"{}\"", hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Evaluate an `If-None-Match` header value against `etag` (RFC 9110 §13.1.2).
+/// Handles `*`, comma-separated lists, and weak (`W/`) validators — GET uses weak comparison.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if if_none_match.trim() == "*" {
+        return true;
+    }
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == etag)
+}
+
+/// GET /forms/:form_id/schema - JSON Schema for the form's answers object (public)
+/// Lets integrators validate answers locally before encrypting them.
+async fn get_form_schema(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
     let form_id = Uuid::parse_str(&form_id_str)
         .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
             error: "Invalid form ID".to_string(),
@@ -324,29 +1776,121 @@ async fn get_form(
 
     let form = sqlx::query_as::<_, Form>("SELECT * FROM forms WHERE id = $1")
         .bind(form_id)
-        .fetch_optional(&state.pool)
+        .fetch_optional(&state.read_pool)
         .await
-        .map_err(|e| {
-            error!("Database error: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-                error: "Database error".to_string(),
-            }))
-        })?
+        .map_err(|e| db_error_response(e, "Database error"))?
         .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
             error: "Form not found".to_string(),
         })))?;
 
-    Ok(Json(FormResponse {
-        id: form.id.to_string(),
-        creator_id: form.creator_id,
-        title: form.title,
-        questions: form.questions,
-        created_at: form.created_at.to_rfc3339(),
+    let schema = schema::questions_to_json_schema(&form.title, &form.questions).map_err(|e| {
+        error!("Form {} has an invalid question definition: {}", form_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
+            error: "Form definition cannot be converted to a schema".to_string(),
+        }))
+    })?;
+
+    Ok(Json(schema))
+}
+
+/// GET /forms/:form_id/versions - Every revision of the form's questions, oldest first (public)
+/// Each submission's `form_version` names the revision its answers refer to.
+async fn get_form_versions(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormVersionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let versions = sqlx::query_as::<_, FormVersion>(
+        "SELECT version, questions, created_at FROM form_versions WHERE form_id = $1 ORDER BY version"
+    )
+    .bind(form_id)
+    .fetch_all(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?;
+
+    // Every form has at least its first revision, so none means no such form
+    if versions.is_empty() {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })));
+    }
+
+    Ok(Json(FormVersionsResponse {
+        versions: versions.into_iter().map(FormVersionResponse::from).collect(),
     }))
 }
 
+/// GET /forms/:form_id/versions/:version - One revision of the form's questions (public)
+async fn get_form_version(
+    State(state): State<AppState>,
+    Path((form_id_str, version_str)): Path<(String, String)>,
+) -> Result<Json<FormVersionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    let version: i32 = version_str
+        .parse()
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form version".to_string(),
+        })))?;
+
+    let version = sqlx::query_as::<_, FormVersion>(
+        "SELECT version, questions, created_at FROM form_versions WHERE form_id = $1 AND version = $2"
+    )
+    .bind(form_id)
+    .bind(version)
+    .fetch_optional(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?
+    .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Form version not found".to_string(),
+    })))?;
+
+    Ok(Json(FormVersionResponse::from(version)))
+}
+
+/// GET /forms/:form_id/fields - `{ id, type, required }` per question (public)
+/// A stable, trimmed view of `questions` for clients that do not render the form.
+async fn get_form_fields(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormFieldsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let questions: serde_json::Value = sqlx::query_scalar("SELECT questions FROM forms WHERE id = $1")
+        .bind(form_id)
+        .fetch_optional(&state.read_pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+
+    let fields = schema::questions_to_fields(&questions).map_err(|e| {
+        error!("Form {} has an invalid question definition: {}", form_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
+            error: "Form definition cannot be summarized".to_string(),
+        }))
+    })?;
+
+    Ok(Json(FormFieldsResponse { fields }))
+}
+
 /// GET /forms/:form_id/submissions - Get submissions for a form (auth required)
-/// Supports pagination via ?offset=N&limit=N query params (default: offset=0, limit=200)
+/// Supports offset pagination (?offset=N&limit=N, default: offset=0, limit=200) and keyset
+/// pagination (?cursor=...). `?with_total=true` adds `total_count` on non-cursor pages.
+/// `?order=asc` returns oldest first (default `desc`). `?since=<rfc3339>` keeps only
+/// submissions made after that instant, for incremental sync; `total_count` then counts
+/// only those. Drafts are left out unless `?include_drafts=true`. A page whose body would
+/// exceed `MAX_RESPONSE_BYTES` is refused with 413 naming a `limit` that fits.
 async fn get_submissions(
     State(state): State<AppState>,
     Path(form_id_str): Path<String>,
@@ -365,140 +1909,2280 @@ async fn get_submissions(
     }
     let limit = pagination.limit.unwrap_or(200).clamp(1, 200);
 
-    // Get total count for pagination metadata
-    let total_count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM submissions WHERE form_id = $1"
-    )
-    .bind(form_id)
-    .fetch_one(&state.pool)
-    .await
-    .map_err(|e| {
-        error!("Database error: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-            error: "Database error".to_string(),
-        }))
-    })?;
+    // Only these two fixed fragments are ever interpolated into the query below; the user's
+    // value just selects between them. The keyset comparison flips with the direction.
+    let (direction, cursor_cmp) = match pagination.order.as_deref() {
+        None | Some("desc") => ("DESC", "<"),
+        Some("asc") => ("ASC", ">"),
+        Some(_) => {
+            return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+                error: "order must be 'asc' or 'desc'".to_string(),
+            })));
+        }
+    };
 
-    let submissions = sqlx::query_as::<_, Submission>(
-        "SELECT id, form_id, submitter_id, encrypted_blob, submitted_at FROM submissions WHERE form_id = $1 ORDER BY submitted_at DESC, id DESC LIMIT $2 OFFSET $3"
-    )
+    let cursor = match pagination.cursor.as_deref() {
+        Some(raw) => {
+            if offset > 0 {
+                return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+                    error: "cursor and offset cannot be combined".to_string(),
+                })));
+            }
+            Some(decode_cursor(raw).ok_or((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+                error: "Invalid cursor".to_string(),
+            })))?)
+        }
+        None => None,
+    };
+
+    let since = match pagination.since.as_deref() {
+        Some(raw) => Some(
+            chrono::DateTime::parse_from_rfc3339(raw)
+                .map(|ts| ts.with_timezone(&chrono::Utc))
+                .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+                    error: "since must be an RFC 3339 timestamp".to_string(),
+                })))?,
+        ),
+        None => None,
+    };
+
+    // The window count is only computed on the first page of a walk (no cursor): the
+    // cursor predicate would otherwise shrink the count, and re-counting every page is waste.
+    let want_total = pagination.with_total.unwrap_or(false) && cursor.is_none();
+    let include_drafts = pagination.include_drafts.unwrap_or(false);
+    let total_expr = if want_total { "COUNT(*) OVER ()" } else { "NULL::bigint" };
+    let (cursor_ts, cursor_id) = cursor.unzip();
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    // Fetch one extra row to learn whether another page exists
+    let mut rows = sqlx::query_as::<_, SubmissionPageRow>(&format!(
+        "SELECT id, form_id, submitter_id, encrypted_blob, submitted_at, received_at, is_draft, form_version, {} AS total_count
+         FROM submissions
+         WHERE form_id = $1 AND ($2::timestamptz IS NULL OR (submitted_at, id) {cmp} ($2, $3))
+           AND ($6::timestamptz IS NULL OR submitted_at > $6) AND ($7 OR NOT is_draft)
+         ORDER BY submitted_at {dir}, id {dir} LIMIT $4 OFFSET $5",
+        total_expr,
+        cmp = cursor_cmp,
+        dir = direction,
+    ))
     .bind(form_id)
-    .bind(limit)
+    .bind(cursor_ts)
+    .bind(cursor_id)
+    .bind(limit + 1)
     .bind(offset)
-    .fetch_all(&state.pool)
+    .bind(since)
+    .bind(include_drafts)
+    .fetch_all(&state.read_pool)
     .await
-    .map_err(|e| {
-        error!("Database error: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-            error: "Database error".to_string(),
-        }))
-    })?;
+    .map_err(db_error)?;
 
-    let items: Vec<SubmissionResponse> = submissions
-        .into_iter()
-        .map(|s| SubmissionResponse {
-            id: s.id.to_string(),
-            submitter_id: s.submitter_id,
-            encrypted_blob: s.encrypted_blob,
-            submitted_at: s.submitted_at.to_rfc3339(),
-        })
-        .collect();
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+
+    let total_count = if !want_total {
+        None
+    } else if let Some(row) = rows.first() {
+        row.total_count
+    } else if offset == 0 {
+        Some(0)
+    } else {
+        // Offset past the end: the window has no rows to report on, so count directly
+        Some(
+            sqlx::query_scalar(
+                "SELECT COUNT(*) FROM submissions
+                 WHERE form_id = $1 AND ($2::timestamptz IS NULL OR submitted_at > $2)
+                   AND ($3 OR NOT is_draft)",
+            )
+            .bind(form_id)
+            .bind(since)
+            .bind(include_drafts)
+            .fetch_one(&state.read_pool)
+            .await
+            .map_err(db_error)?,
+        )
+    };
+
+    let next_cursor = if has_more {
+        rows.last().map(|r| encode_cursor(&r.submission.submitted_at, r.submission.id))
+    } else {
+        None
+    };
+
+    // Refuse pages too big to send whole, saying how many rows would fit. Blobs dominate,
+    // so the estimate is their length plus a fixed allowance per row.
+    let mut page_bytes = 0usize;
+    let mut rows_that_fit = 0usize;
+    for row in &rows {
+        page_bytes += row.submission.encrypted_blob.len()
+            + row.submission.submitter_id.len()
+            + SUBMISSION_JSON_OVERHEAD_BYTES;
+        if page_bytes <= state.max_response_bytes {
+            rows_that_fit += 1;
+        }
+    }
+    if page_bytes > state.max_response_bytes {
+        return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(ErrorResponse {
+            error: format!(
+                "This page of {} submissions would be about {} bytes (max: {} bytes). \
+                 Request fewer per page, e.g. limit={}, and follow next_cursor for the rest.",
+                rows.len(),
+                page_bytes,
+                state.max_response_bytes,
+                rows_that_fit.max(1)
+            ),
+        })));
+    }
+
+    let items: Vec<SubmissionResponse> = rows
+        .into_iter()
+        .map(|row| SubmissionResponse::from(row.submission))
+        .collect();
+
+    Ok(Json(PaginatedSubmissions {
+        submissions: items,
+        total_count,
+        has_more,
+        next_cursor,
+    }))
+}
+
+/// GET /forms/:form_id/submissions/:submitter_id - One respondent's submission (auth required)
+///
+/// Served by the `(form_id, submitter_id)` index; 404 if that account hasn't submitted. On
+/// `unlimited` forms an account may have several submissions; the latest is returned.
+/// A draft is not a submission and is never returned here.
+async fn get_submission(
+    State(state): State<AppState>,
+    Path((form_id_str, submitter_id)): Path<(String, String)>,
+) -> Result<Json<SubmissionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    validate_near_account_id(&submitter_id, "submitter_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let submission = sqlx::query_as::<_, Submission>(
+        "SELECT id, form_id, submitter_id, encrypted_blob, submitted_at, received_at, is_draft, form_version
+         FROM submissions WHERE form_id = $1 AND submitter_id = $2 AND NOT is_draft
+         ORDER BY submitted_at DESC, id DESC
+         LIMIT 1"
+    )
+    .bind(form_id)
+    .bind(&submitter_id)
+    .fetch_optional(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?
+    .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Submission not found".to_string(),
+    })))?;
+
+    Ok(Json(SubmissionResponse::from(submission)))
+}
+
+/// GET /submitters/:submitter_id/submissions - One account's submissions across every form (auth required)
+///
+/// For subject-access and erasure requests. Returns metadata only (no blobs), newest first,
+/// with keyset pagination (?limit=N, default and max 200; ?cursor=...). Served by the
+/// `submitter_id` index rather than a per-form scan.
+async fn get_submitter_submissions(
+    State(state): State<AppState>,
+    Path(submitter_id): Path<String>,
+    Query(params): Query<SubmitterSubmissionsParams>,
+) -> Result<Json<SubmitterSubmissionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_near_account_id(&submitter_id, "submitter_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    let limit = params.limit.unwrap_or(200).clamp(1, 200);
+    let cursor = match params.cursor.as_deref() {
+        Some(raw) => Some(decode_cursor(raw).ok_or((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid cursor".to_string(),
+        })))?),
+        None => None,
+    };
+    let (cursor_ts, cursor_id) = cursor.unzip();
+
+    // Fetch one extra row to learn whether another page exists
+    let mut rows = sqlx::query_as::<_, SubmissionMetaRow>(
+        "SELECT id, form_id, submitted_at, received_at, is_draft
+         FROM submissions
+         WHERE submitter_id = $1 AND ($2::timestamptz IS NULL OR (submitted_at, id) < ($2, $3))
+         ORDER BY submitted_at DESC, id DESC LIMIT $4"
+    )
+    .bind(&submitter_id)
+    .bind(cursor_ts)
+    .bind(cursor_id)
+    .bind(limit + 1)
+    .fetch_all(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?;
+
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_cursor = if has_more {
+        rows.last().map(|r| encode_cursor(&r.submitted_at, r.id))
+    } else {
+        None
+    };
+
+    Ok(Json(SubmitterSubmissionsResponse {
+        submissions: rows.into_iter().map(SubmitterSubmission::from).collect(),
+        has_more,
+        next_cursor,
+    }))
+}
+
+/// GET /forms/:form_id/submissions/:submitter_id/exists - Has this account submitted? (auth or token)
+///
+/// Lets a frontend show "already responded" without a 409 on submit and without exposing the
+/// blob. Whether an account took part is itself sensitive, so this is not public: it needs the
+/// API secret, or a submission token minted for this form and bound to this submitter.
+async fn submission_exists(
+    State(state): State<AppState>,
+    grant: Option<Extension<SubmissionGrant>>,
+    Path((form_id_str, submitter_id)): Path<(String, String)>,
+) -> Result<Json<SubmissionExistsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    validate_near_account_id(&submitter_id, "submitter_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    // Unbound tokens would let any holder probe arbitrary accounts, so require a bound one
+    if let Some(Extension(grant)) = grant {
+        if grant.form_id != form_id || grant.submitter_id.as_deref() != Some(submitter_id.as_str()) {
+            return Err((StatusCode::FORBIDDEN, Json(ErrorResponse {
+                error: "Submission token is not valid for this form or submitter".to_string(),
+            })));
+        }
+    }
+
+    let submitted: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM submissions WHERE form_id = $1 AND submitter_id = $2 AND NOT is_draft)"
+    )
+    .bind(form_id)
+    .bind(&submitter_id)
+    .fetch_one(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?;
+
+    Ok(Json(SubmissionExistsResponse { submitted }))
+}
+
+/// Encode a keyset cursor as `<submitted_at unix micros>:<id>` — the sort key of the last row.
+fn encode_cursor(submitted_at: &chrono::DateTime<chrono::Utc>, id: Uuid) -> String {
+    format!("{}:{}", submitted_at.timestamp_micros(), id)
+}
+
+/// Decode a cursor produced by `encode_cursor`. Returns None for anything malformed.
+fn decode_cursor(raw: &str) -> Option<(chrono::DateTime<chrono::Utc>, Uuid)> {
+    let (micros, id) = raw.split_once(':')?;
+    let ts = chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+    Some((ts, Uuid::parse_str(id).ok()?))
+}
+
+/// Maximum length of a question ID key in a per-question `encrypted_blob`.
+const MAX_BLOB_FIELD_ID_LENGTH: usize = 128;
+
+/// Validate a submission request and return the parsed form ID plus the normalized blob.
+/// Shared by `create_submission` and `batch_create_submission` so single and bulk inserts
+/// enforce identical rules.
+///
+/// `encrypted_blob` is either one hex EC01 ciphertext over the whole answers object, or a
+/// JSON object mapping each question ID to its own hex EC01 ciphertext (so a reader can
+/// decrypt only the answers it needs). Hex is normalized to lowercase; the per-question
+/// form is re-serialized with sorted keys so identical submissions hash identically.
+fn validate_submission_request(
+    payload: &CreateSubmissionRequest,
+    max_blob_size: usize,
+) -> Result<(Uuid, String), (StatusCode, String)> {
+    let form_id = Uuid::parse_str(&payload.form_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid form ID".to_string()))?;
+
+    // Validate submitter_id as a NEAR account ID
+    validate_near_account_id(&payload.submitter_id, "submitter_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Say so plainly instead of letting an empty blob fail as "too short"
+    if payload.encrypted_blob.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "encrypted_blob must not be empty".to_string()));
+    }
+
+    // Enforce size limit on encrypted_blob to prevent storage abuse.
+    // MAX_BLOB_SIZE_BYTES caps decoded bytes; on the hex text that is 2 characters per byte
+    // (the whole JSON text for per-question blobs). The WASI module's `check_encoded_size`
+    // applies the same rule with the same message, so a blob passes both or neither.
+    if payload.encrypted_blob.len() > max_blob_size * 2 {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "encrypted_blob too long: {} characters (max: {} characters for {} bytes, 2 hex characters per byte)",
+                payload.encrypted_blob.len(),
+                max_blob_size * 2,
+                max_blob_size
+            ),
+        ));
+    }
+
+    if !payload.encrypted_blob.starts_with('{') {
+        let normalized_blob = normalize_ec01_hex(&payload.encrypted_blob, "encrypted_blob")?;
+        return Ok((form_id, normalized_blob));
+    }
+
+    let fields: BTreeMap<String, String> =
+        serde_json::from_str(&payload.encrypted_blob).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                "encrypted_blob must be hex or a JSON object of question ID to hex".to_string(),
+            )
+        })?;
+    if fields.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "encrypted_blob has no fields".to_string()));
+    }
+    let mut normalized = BTreeMap::new();
+    for (id, blob) in fields {
+        if id.is_empty() || id.len() > MAX_BLOB_FIELD_ID_LENGTH || id.chars().any(char::is_control) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "encrypted_blob field IDs must be 1-{} characters without control characters",
+                    MAX_BLOB_FIELD_ID_LENGTH
+                ),
+            ));
+        }
+        let hex = normalize_ec01_hex(&blob, &format!("encrypted_blob field '{}'", id))?;
+        normalized.insert(id, hex);
+    }
+    let normalized_blob = serde_json::to_string(&normalized)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "encrypted_blob could not be normalized".to_string()))?;
+    Ok((form_id, normalized_blob))
+}
+
+/// Check that `hex` looks like an EC01 ciphertext and return it lowercased.
+/// `what` names the value in error messages.
+fn normalize_ec01_hex(hex: &str, what: &str) -> Result<String, (StatusCode, String)> {
+    // Validate that the blob is valid hex to reject garbage early
+    // (WASI module would fail at hex::decode later, resulting in a skipped submission)
+    if !hex.len().is_multiple_of(2) {
+        return Err((StatusCode::BAD_REQUEST, format!("{} must have even length (hex-encoded bytes)", what)));
+    }
+    // Minimum size: EC01 header (4) + ephemeral pubkey (33) + nonce (12) + Poly1305 tag (16) = 65 bytes = 130 hex chars
+    if hex.len() < 130 {
+        return Err((StatusCode::BAD_REQUEST, format!("{} too short to be a valid EC01 ciphertext", what)));
+    }
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err((StatusCode::BAD_REQUEST, format!("{} must be valid hex", what)));
+    }
+
+    // Normalize hex to lowercase for consistent storage
+    let normalized = hex.to_ascii_lowercase();
+
+    // Validate EC01 magic bytes (first 4 bytes = "45433031" in hex)
+    if !normalized.starts_with("45433031") {
+        return Err((StatusCode::BAD_REQUEST, format!("{} must start with EC01 magic bytes", what)));
+    }
+
+    Ok(normalized)
+}
+
+/// Error returned when an identical ciphertext was already stored (possible replay).
+const BLOB_REPLAY_ERROR: &str =
+    "This encrypted submission has already been received. Please re-encrypt and submit again.";
+
+/// Error returned when the submitter is on the form's `denied_submitters`.
+const DENIED_SUBMITTER_ERROR: &str = "This account is not allowed to submit to this form.";
+
+/// Error returned for a final submission to a paused form.
+const FORM_PAUSED_ERROR: &str = "This form is paused and is not accepting submissions right now.";
+
+/// Error returned when the submitter already has a submission for the form.
+const ALREADY_SUBMITTED_ERROR: &str =
+    "You have already submitted this form. Each account can only submit once.";
+
+/// Name of the unique index on `submissions.blob_hash` (see migration 20260314000001).
+const BLOB_HASH_INDEX: &str = "idx_submissions_blob_hash";
+
+/// Error for a blob whose layout the form's `encryption_scheme` does not accept.
+fn scheme_mismatch_error(required: EncryptionScheme) -> String {
+    format!("This form requires encryption_scheme '{}'", required.as_str())
+}
+
+/// Hex SHA-256 of the normalized (lowercase hex) blob, stored in `submissions.blob_hash`.
+fn blob_hash(normalized_blob: &str) -> String {
+    hex::encode(Sha256::digest(normalized_blob.as_bytes()))
+}
+
+/// Take the per-account advisory lock that serializes writes from one submitter to a form
+/// with `min_resubmit_interval_secs` (a no-op on other forms), held until the transaction ends.
+async fn lock_resubmit_slot(
+    conn: &mut sqlx::PgConnection,
+    form_id: Uuid,
+    submitter_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "SELECT pg_advisory_xact_lock(hashtextextended($1::text || ':' || $2, 0))
+         FROM forms WHERE id = $1 AND min_resubmit_interval_secs IS NOT NULL"
+    )
+    .bind(form_id)
+    .bind(submitter_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 429 message for a write inside a form's `min_resubmit_interval_secs`.
+fn resubmit_too_soon_error(interval_secs: i32, retry_after_secs: i64) -> String {
+    format!(
+        "Submitted too recently: this form accepts one submission per account every {} seconds (retry in {} seconds)",
+        interval_secs,
+        retry_after_secs.max(1)
+    )
+}
+
+/// Why `create_submission`'s insert matched no row, read back from the form.
+#[derive(FromRow)]
+struct RefusalReasons {
+    /// The submitter is on `denied_submitters`
+    denied: bool,
+    paused: bool,
+    encryption_scheme: EncryptionScheme,
+    min_resubmit_interval_secs: Option<i32>,
+    /// Seconds until the account's last final submission clears the interval
+    /// (NULL without an interval or a previous submission)
+    retry_after_secs: Option<i64>,
+}
+
+/// POST /submissions - Store a new submission (auth required)
+///
+/// Responds with the submission `id` and the `blob_hash` of the blob as stored (after
+/// normalization), which clients can keep as a receipt.
+async fn create_submission(
+    State(state): State<AppState>,
+    grant: Option<Extension<SubmissionGrant>>,
+    ApiJson(payload): ApiJson<CreateSubmissionRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    let (form_id, normalized_blob) = validate_submission_request(&payload, state.max_blob_size)
+        .map_err(|(status, error)| (status, Json(ErrorResponse { error })))?;
+
+    // Token-authenticated requests may only submit what the token was minted for
+    if let Some(Extension(grant)) = grant {
+        let submitter_ok = grant
+            .submitter_id
+            .as_ref()
+            .is_none_or(|bound| bound == &payload.submitter_id);
+        if grant.form_id != form_id || !submitter_ok {
+            return Err((StatusCode::FORBIDDEN, Json(ErrorResponse {
+                error: "Submission token is not valid for this form or submitter".to_string(),
+            })));
+        }
+    }
+
+    // Insert submission (retried on serialization failure / deadlock). The form's policy is
+    // copied onto the row in the same statement; idx_submissions_one_per_account enforces it.
+    // A form whose encryption_scheme does not accept this layout matches no row.
+    //
+    // A draft is upserted into the account's single draft slot (idx_submissions_draft) and
+    // keeps its id; it is not counted for one-per-account, but is refused once the account
+    // has submitted such a form. A final submission deletes the account's draft.
+    // An account on the form's denied_submitters matches no row, and neither does a final
+    // submission to a paused form.
+    //
+    // On a form with min_resubmit_interval_secs, a final submission matches no row while the
+    // account's previous one is too recent. The per-account advisory lock makes concurrent
+    // submissions from one account take turns, so both cannot pass the check.
+    let pool = &state.pool;
+    let submitter_id = &payload.submitter_id;
+    let is_draft = payload.is_draft;
+    let hash = blob_hash(&normalized_blob);
+    let (blob, hash) = (&normalized_blob, &hash);
+    let stored = retry_transaction(|| async move {
+        let mut tx = pool.begin().await?;
+        let stored: Option<Uuid> = if is_draft {
+            sqlx::query_scalar(
+                "INSERT INTO submissions
+                     (id, form_id, submitter_id, encrypted_blob, blob_hash, one_per_account, is_draft, submitted_at, received_at)
+                 SELECT $1, f.id, $3, $4, $5, false, true, NOW(), NOW()
+                 FROM forms f WHERE f.id = $2 AND f.encryption_scheme IN ('any', $6)
+                   AND NOT $3 = ANY(f.denied_submitters)
+                   AND NOT EXISTS (SELECT 1 FROM submissions s
+                                   WHERE s.form_id = f.id AND s.submitter_id = $3 AND s.one_per_account)
+                 ON CONFLICT (form_id, submitter_id) WHERE is_draft DO UPDATE
+                     SET encrypted_blob = EXCLUDED.encrypted_blob, blob_hash = EXCLUDED.blob_hash,
+                         form_version = EXCLUDED.form_version, submitted_at = NOW(), received_at = NOW()
+                 RETURNING id"
+            )
+        } else {
+            lock_resubmit_slot(&mut tx, form_id, submitter_id).await?;
+            sqlx::query_scalar(
+                "INSERT INTO submissions
+                     (id, form_id, submitter_id, encrypted_blob, blob_hash, one_per_account, submitted_at, received_at)
+                 SELECT $1, f.id, $3, $4, $5, f.submission_policy = 'one_per_account', NOW(), NOW()
+                 FROM forms f WHERE f.id = $2 AND f.encryption_scheme IN ('any', $6)
+                   AND NOT $3 = ANY(f.denied_submitters) AND NOT f.paused
+                   AND NOT EXISTS (SELECT 1 FROM submissions s
+                                   WHERE s.form_id = f.id AND s.submitter_id = $3 AND NOT s.is_draft
+                                     AND s.submitted_at > NOW() - make_interval(secs => f.min_resubmit_interval_secs))
+                 RETURNING id"
+            )
+        }
+        .bind(Uuid::new_v4())
+        .bind(form_id)
+        .bind(submitter_id)
+        .bind(blob)
+        .bind(hash)
+        .bind(EncryptionScheme::of_blob(blob))
+        .fetch_optional(&mut *tx)
+        .await?;
+        if stored.is_some() && !is_draft {
+            sqlx::query("DELETE FROM submissions WHERE form_id = $1 AND submitter_id = $2 AND is_draft")
+                .bind(form_id)
+                .bind(submitter_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(stored)
+    })
+    .await
+    .map_err(|e| {
+        if let Some(db_err) = e.as_database_error() {
+            if let Some(code) = db_err.code().as_deref() {
+                const PG_UNIQUE_VIOLATION: &str = "23505";
+                const PG_FOREIGN_KEY_VIOLATION: &str = "23503";
+                if code == PG_UNIQUE_VIOLATION {
+                    let error = if db_err.constraint() == Some(BLOB_HASH_INDEX) {
+                        BLOB_REPLAY_ERROR
+                    } else {
+                        ALREADY_SUBMITTED_ERROR
+                    };
+                    return (StatusCode::CONFLICT, Json(ErrorResponse {
+                        error: error.to_string(),
+                    }));
+                }
+                if code == PG_FOREIGN_KEY_VIOLATION {
+                    return (StatusCode::NOT_FOUND, Json(ErrorResponse {
+                        error: "Form not found".to_string(),
+                    }));
+                }
+            }
+        }
+        db_error_response(e, "Failed to create submission")
+    })?;
+    let Some(submission_id) = stored else {
+        let form = sqlx::query_as::<_, RefusalReasons>(
+            "SELECT $2 = ANY(f.denied_submitters) AS denied, f.paused, f.encryption_scheme,
+                    f.min_resubmit_interval_secs,
+                    (SELECT CEIL(EXTRACT(EPOCH FROM MAX(s.submitted_at)
+                                 + make_interval(secs => f.min_resubmit_interval_secs) - NOW()))::bigint
+                     FROM submissions s
+                     WHERE s.form_id = f.id AND s.submitter_id = $2 AND NOT s.is_draft) AS retry_after_secs
+             FROM forms f WHERE f.id = $1"
+        )
+        .bind(form_id)
+        .bind(&payload.submitter_id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| db_error_response(e, "Failed to create submission"))?;
+        return Err(match form {
+            Some(form) if form.denied => (StatusCode::FORBIDDEN, Json(ErrorResponse {
+                error: DENIED_SUBMITTER_ERROR.to_string(),
+            })),
+            Some(form) if !form.encryption_scheme.accepts(&normalized_blob) => (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+                error: scheme_mismatch_error(form.encryption_scheme),
+            })),
+            Some(form) if form.paused && !is_draft => (StatusCode::LOCKED, Json(ErrorResponse {
+                error: FORM_PAUSED_ERROR.to_string(),
+            })),
+            // Only a draft is turned away by a form that accepts its layout
+            Some(_) if is_draft => (StatusCode::CONFLICT, Json(ErrorResponse {
+                error: ALREADY_SUBMITTED_ERROR.to_string(),
+            })),
+            // ...and only the interval turns away a final submission. If it has elapsed
+            // since the insert, report the shortest wait rather than nothing.
+            Some(form) => (StatusCode::TOO_MANY_REQUESTS, Json(ErrorResponse {
+                error: resubmit_too_soon_error(
+                    form.min_resubmit_interval_secs.unwrap_or_default(),
+                    form.retry_after_secs.unwrap_or_default(),
+                ),
+            })),
+            None => (StatusCode::NOT_FOUND, Json(ErrorResponse {
+                error: "Form not found".to_string(),
+            })),
+        });
+    };
+
+    Ok((StatusCode::CREATED, Json(serde_json::json!({
+        "id": submission_id.to_string(),
+        "blob_hash": hash,
+    }))))
+}
+
+/// PUT /submissions - Replace a submission's encrypted blob (auth required)
+///
+/// Used by the WASI module's ReEncryptResponses to move a blob from the old form key to the
+/// new one. Only `encrypted_blob` (and its `blob_hash`) change; timestamps are kept so
+/// pagination order is stable during a migration walk. The swap only happens if the stored
+/// blob still hashes to `previous_blob_hash`, so a concurrent change is reported as 409
+/// rather than overwritten. Repeating a completed update is a no-op that returns 200.
+///
+/// On a form with `min_resubmit_interval_secs`, a submission younger than the interval
+/// cannot be changed yet (429), under the same per-account lock as `create_submission`.
+async fn update_submission(
+    State(state): State<AppState>,
+    ApiJson(payload): ApiJson<UpdateSubmissionRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let (form_id, normalized_blob) = validate_submission_request(&payload.submission, state.max_blob_size)
+        .map_err(|(status, error)| (status, Json(ErrorResponse { error })))?;
+    let previous_hash = payload.previous_blob_hash.to_ascii_lowercase();
+    let new_hash = blob_hash(&normalized_blob);
+    let submitter_id = &payload.submission.submitter_id;
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    lock_resubmit_slot(&mut tx, form_id, submitter_id).await.map_err(db_error)?;
+
+    // The stored hash is recomputed rather than read from `blob_hash`, which is NULL on
+    // rows that predate replay protection. A row already holding the new blob is always
+    // matched, so a repeat stays a no-op even inside the interval.
+    let updated: Option<Uuid> = sqlx::query_scalar(
+        "UPDATE submissions s SET encrypted_blob = $3, blob_hash = $4
+         FROM forms f
+         WHERE f.id = s.form_id AND s.form_id = $1 AND s.submitter_id = $2
+           AND encode(sha256(convert_to(s.encrypted_blob, 'UTF8')), 'hex') IN ($5, $4)
+           AND (encode(sha256(convert_to(s.encrypted_blob, 'UTF8')), 'hex') = $4
+                OR f.min_resubmit_interval_secs IS NULL
+                OR s.submitted_at <= NOW() - make_interval(secs => f.min_resubmit_interval_secs))
+         RETURNING s.id"
+    )
+    .bind(form_id)
+    .bind(submitter_id)
+    .bind(&normalized_blob)
+    .bind(&new_hash)
+    .bind(&previous_hash)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        let replay = e.as_database_error().is_some_and(|d| d.constraint() == Some(BLOB_HASH_INDEX));
+        if replay {
+            return (StatusCode::CONFLICT, Json(ErrorResponse {
+                error: BLOB_REPLAY_ERROR.to_string(),
+            }));
+        }
+        db_error(e)
+    })?;
+
+    if let Some(id) = updated {
+        tx.commit().await.map_err(db_error)?;
+        return Ok(Json(serde_json::json!({ "id": id.to_string() })));
+    }
+
+    // (interval, seconds until the matching row clears it) when only the interval refused it
+    let too_soon: Option<(i32, i64)> = sqlx::query_as(
+        "SELECT f.min_resubmit_interval_secs,
+                CEIL(EXTRACT(EPOCH FROM s.submitted_at + make_interval(secs => f.min_resubmit_interval_secs) - NOW()))::bigint
+         FROM submissions s JOIN forms f ON f.id = s.form_id
+         WHERE s.form_id = $1 AND s.submitter_id = $2
+           AND encode(sha256(convert_to(s.encrypted_blob, 'UTF8')), 'hex') = $3
+           AND f.min_resubmit_interval_secs IS NOT NULL
+         LIMIT 1"
+    )
+    .bind(form_id)
+    .bind(submitter_id)
+    .bind(&previous_hash)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    if let Some((interval, retry_after)) = too_soon {
+        return Err((StatusCode::TOO_MANY_REQUESTS, Json(ErrorResponse {
+            error: resubmit_too_soon_error(interval, retry_after),
+        })));
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM submissions WHERE form_id = $1 AND submitter_id = $2)"
+    )
+    .bind(form_id)
+    .bind(submitter_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    Err(if exists {
+        (StatusCode::CONFLICT, Json(ErrorResponse {
+            error: "Submission changed since it was read (previous_blob_hash does not match)".to_string(),
+        }))
+    } else {
+        (StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Submission not found".to_string(),
+        }))
+    })
+}
+
+/// POST /submissions/batch - Bulk-insert submissions in one transaction (auth required)
+///
+/// Every item goes through the same validation as `create_submission`. Valid rows are
+/// inserted with a single `UNNEST` statement using `ON CONFLICT DO NOTHING`, so rows that
+/// collide with an existing submission (or an earlier row in the same batch) are reported
+/// individually as 409 instead of aborting the statement. A row refused by the form's
+/// `min_resubmit_interval_secs` is reported as 429. With `?atomic=true`, any failed item
+/// rolls back the whole batch and nothing is stored.
+async fn batch_create_submission(
+    State(state): State<AppState>,
+    Query(params): Query<BatchSubmissionParams>,
+    ApiJson(items): ApiJson<Vec<CreateSubmissionRequest>>,
+) -> Result<(StatusCode, Json<BatchSubmissionResponse>), (StatusCode, Json<ErrorResponse>)> {
+    if items.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Batch must contain at least one submission".to_string(),
+        })));
+    }
+    if items.len() > MAX_BATCH_SIZE {
+        return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(ErrorResponse {
+            error: format!("Batch exceeds maximum of {} submissions", MAX_BATCH_SIZE),
+        })));
+    }
+    let atomic = params.atomic.unwrap_or(false);
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Failed to create submissions");
+
+    let mut results: Vec<BatchItemResult> = Vec::with_capacity(items.len());
+    // (result index, id, form_id, submitter_id, normalized blob) for rows that passed validation
+    let mut pending: Vec<(usize, Uuid, Uuid, String, String)> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let validated = if item.is_draft {
+            Err((StatusCode::BAD_REQUEST, "Drafts cannot be created in a batch".to_string()))
+        } else {
+            validate_submission_request(item, state.max_blob_size)
+        };
+        match validated {
+            Ok((form_id, blob)) => {
+                pending.push((index, Uuid::new_v4(), form_id, item.submitter_id.clone(), blob));
+                results.push(BatchItemResult { index, status: StatusCode::CREATED.as_u16(), id: None, error: None });
+            }
+            Err((status, error)) => {
+                results.push(BatchItemResult { index, status: status.as_u16(), id: None, error: Some(error) });
+            }
+        }
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+
+    // Resolve referenced forms up front so a missing form fails only its own rows
+    // instead of tripping the foreign key and aborting the whole statement. Rows whose
+    // layout the form's encryption_scheme does not accept, whose submitter the form denies,
+    // or whose form is paused fail the same way.
+    let mut form_ids: Vec<Uuid> = pending.iter().map(|p| p.2).collect();
+    form_ids.sort();
+    form_ids.dedup();
+    let existing_forms: HashMap<Uuid, (EncryptionScheme, Vec<String>, bool, Option<i32>)> =
+        sqlx::query_as::<_, (Uuid, EncryptionScheme, Vec<String>, bool, Option<i32>)>(
+            "SELECT id, encryption_scheme, denied_submitters, paused, min_resubmit_interval_secs
+             FROM forms WHERE id = ANY($1)"
+        )
+        .bind(&form_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|(id, scheme, denied, paused, interval)| (id, (scheme, denied, paused, interval)))
+        .collect();
+    pending.retain(|(index, _, form_id, submitter_id, blob)| {
+        let (status, error) = match existing_forms.get(form_id) {
+            Some((_, denied, _, _)) if denied.contains(submitter_id) => {
+                (StatusCode::FORBIDDEN, DENIED_SUBMITTER_ERROR.to_string())
+            }
+            Some((scheme, _, _, _)) if !scheme.accepts(blob) => {
+                (StatusCode::BAD_REQUEST, scheme_mismatch_error(*scheme))
+            }
+            Some((_, _, true, _)) => (StatusCode::LOCKED, FORM_PAUSED_ERROR.to_string()),
+            Some(_) => return true,
+            None => (StatusCode::NOT_FOUND, "Form not found".to_string()),
+        };
+        results[*index].status = status.as_u16();
+        results[*index].error = Some(error);
+        false
+    });
+
+    // min_resubmit_interval_secs applies here as in create_submission: take the same
+    // per-account locks (in a fixed order, so two batches cannot deadlock), then refuse rows
+    // whose account submitted too recently, or already has an earlier row in this batch.
+    let mut throttled: Vec<(Uuid, String)> = pending
+        .iter()
+        .filter(|p| existing_forms.get(&p.2).is_some_and(|form| form.3.is_some()))
+        .map(|p| (p.2, p.3.clone()))
+        .collect();
+    throttled.sort();
+    throttled.dedup();
+    if !throttled.is_empty() {
+        for (form_id, submitter_id) in &throttled {
+            lock_resubmit_slot(&mut tx, *form_id, submitter_id).await.map_err(db_error)?;
+        }
+        let (forms, submitters): (Vec<Uuid>, Vec<String>) = throttled.into_iter().unzip();
+        let recent: HashMap<(Uuid, String), i64> = sqlx::query_as::<_, (Uuid, String, i64)>(
+            "SELECT s.form_id, s.submitter_id,
+                    CEIL(EXTRACT(EPOCH FROM MAX(s.submitted_at)
+                         + make_interval(secs => f.min_resubmit_interval_secs) - NOW()))::bigint
+             FROM submissions s JOIN forms f ON f.id = s.form_id
+             WHERE NOT s.is_draft
+               AND (s.form_id, s.submitter_id) IN (SELECT * FROM UNNEST($1::uuid[], $2::text[]))
+             GROUP BY s.form_id, s.submitter_id, f.min_resubmit_interval_secs
+             HAVING MAX(s.submitted_at) > NOW() - make_interval(secs => f.min_resubmit_interval_secs)"
+        )
+        .bind(&forms)
+        .bind(&submitters)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|(form_id, submitter_id, retry_after)| ((form_id, submitter_id), retry_after))
+        .collect();
+
+        let mut seen: std::collections::HashSet<(Uuid, String)> = std::collections::HashSet::new();
+        pending.retain(|(index, _, form_id, submitter_id, _)| {
+            let Some(interval) = existing_forms.get(form_id).and_then(|form| form.3) else {
+                return true;
+            };
+            let key = (*form_id, submitter_id.clone());
+            let retry_after = match recent.get(&key) {
+                Some(&retry_after) => retry_after,
+                None if seen.insert(key) => return true,
+                None => i64::from(interval),
+            };
+            results[*index].status = StatusCode::TOO_MANY_REQUESTS.as_u16();
+            results[*index].error = Some(resubmit_too_soon_error(interval, retry_after));
+            false
+        });
+    }
+
+    let inserted_ids: Vec<Uuid> = if pending.is_empty() {
+        Vec::new()
+    } else {
+        let ids: Vec<Uuid> = pending.iter().map(|p| p.1).collect();
+        let forms: Vec<Uuid> = pending.iter().map(|p| p.2).collect();
+        let submitters: Vec<String> = pending.iter().map(|p| p.3.clone()).collect();
+        let blobs: Vec<String> = pending.iter().map(|p| p.4.clone()).collect();
+        let hashes: Vec<String> = blobs.iter().map(|b| blob_hash(b)).collect();
+        // Policy via a correlated subquery, not a JOIN, so rows keep UNNEST order and the
+        // first of two colliding items is the one inserted
+        sqlx::query_scalar(
+            "INSERT INTO submissions
+                 (id, form_id, submitter_id, encrypted_blob, blob_hash, one_per_account, submitted_at, received_at)
+             SELECT t.id, t.form_id, t.submitter_id, t.encrypted_blob, t.blob_hash,
+                    COALESCE((SELECT f.submission_policy = 'one_per_account' FROM forms f WHERE f.id = t.form_id), true),
+                    NOW(), NOW()
+             FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::text[], $5::text[])
+                 AS t(id, form_id, submitter_id, encrypted_blob, blob_hash)
+             ON CONFLICT DO NOTHING
+             RETURNING id"
+        )
+        .bind(&ids)
+        .bind(&forms)
+        .bind(&submitters)
+        .bind(&blobs)
+        .bind(&hashes)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+    };
+
+    // ON CONFLICT DO NOTHING doesn't say which constraint fired. A skipped row whose
+    // (form, submitter) pair now exists on a one-per-account form is a resubmission;
+    // otherwise its blob hash collided.
+    let skipped: Vec<&(usize, Uuid, Uuid, String, String)> =
+        pending.iter().filter(|p| !inserted_ids.contains(&p.1)).collect();
+    let existing_pairs: Vec<(Uuid, String)> = if skipped.is_empty() {
+        Vec::new()
+    } else {
+        let forms: Vec<Uuid> = skipped.iter().map(|p| p.2).collect();
+        let submitters: Vec<String> = skipped.iter().map(|p| p.3.clone()).collect();
+        sqlx::query_as(
+            "SELECT form_id, submitter_id FROM submissions
+             WHERE one_per_account
+               AND (form_id, submitter_id) IN (SELECT * FROM UNNEST($1::uuid[], $2::text[]))"
+        )
+        .bind(&forms)
+        .bind(&submitters)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+    };
+
+    for (index, id, form_id, submitter_id, _) in &pending {
+        if inserted_ids.contains(id) {
+            results[*index].id = Some(id.to_string());
+        } else {
+            let resubmission = existing_pairs
+                .iter()
+                .any(|(f, s)| f == form_id && s == submitter_id);
+            results[*index].status = StatusCode::CONFLICT.as_u16();
+            results[*index].error = Some(
+                if resubmission { ALREADY_SUBMITTED_ERROR } else { BLOB_REPLAY_ERROR }.to_string(),
+            );
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if atomic && failed > 0 {
+        tx.rollback().await.map_err(db_error)?;
+        for result in results.iter_mut() {
+            result.id = None;
+        }
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(BatchSubmissionResponse {
+            inserted: 0,
+            failed,
+            results,
+        })));
+    }
+
+    tx.commit().await.map_err(db_error)?;
+
+    Ok((StatusCode::OK, Json(BatchSubmissionResponse {
+        inserted: inserted_ids.len(),
+        failed,
+        results,
+    })))
+}
+
+/// POST /submission-tokens - Mint a short-lived submission token (auth required)
+///
+/// The token lets a frontend call `POST /v1/submissions` directly with a `Submission-Token`
+/// header instead of the shared API secret. It is scoped to one form (and optionally one
+/// submitter) and can be reused until it expires; reads stay API-secret only. Unbound tokens
+/// do not authenticate `submitter_id` — bind one whenever the caller knows it.
+async fn create_submission_token(
+    State(state): State<AppState>,
+    ApiJson(payload): ApiJson<CreateSubmissionTokenRequest>,
+) -> Result<(StatusCode, Json<SubmissionTokenResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&payload.form_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    if let Some(submitter_id) = &payload.submitter_id {
+        validate_near_account_id(submitter_id, "submitter_id")
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    }
+    let ttl = payload.ttl_secs.unwrap_or(DEFAULT_SUBMISSION_TOKEN_TTL_SECS);
+    if ttl == 0 || ttl > MAX_SUBMISSION_TOKEN_TTL_SECS {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: format!("ttl_secs must be between 1 and {}", MAX_SUBMISSION_TOKEN_TTL_SECS),
+        })));
+    }
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM forms WHERE id = $1)")
+        .bind(form_id)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })));
+    }
+
+    // Tokens carry whole seconds; report exactly the expiry that is signed
+    let expires_unix = chrono::Utc::now().timestamp() + ttl as i64;
+    let expires_at = chrono::DateTime::from_timestamp(expires_unix, 0)
+        .expect("now + at most an hour is a valid timestamp");
+    let token = mint_submission_token(
+        &state.submission_token_key,
+        form_id,
+        payload.submitter_id.as_deref(),
+        expires_unix,
+    );
+    Ok((StatusCode::CREATED, Json(SubmissionTokenResponse {
+        token,
+        expires_at: format_timestamp(&expires_at),
+    })))
+}
+
+/// POST /forms/validate - Lint a `questions` array without creating anything (auth required)
+///
+/// Runs the same checks as `create_form` and seeding (`validate_questions`, against this
+/// instance's `question_limits`) but reports every failure, each with its question index.
+/// Always 200: a broken definition is the expected answer, not a bad request.
+async fn validate_form_questions(
+    State(state): State<AppState>,
+    ApiJson(payload): ApiJson<ValidateQuestionsRequest>,
+) -> Json<ValidateQuestionsResponse> {
+    let errors = question_errors(&payload.questions, &state.question_limits);
+    Json(ValidateQuestionsResponse { valid: errors.is_empty(), errors })
+}
+
+/// POST /forms - Create a form (auth required)
+///
+/// Applies the same account, question and tag validation as startup seeding. The WASI
+/// module only serves its hardcoded `FORM_ID`; forms created here are for db-api clients,
+/// and have no registered public key (see `get_form_public_key`).
+///
+/// `extends` lists template forms (`is_template = true`) whose questions come first, in the
+/// listed order, followed by `questions` (see `merge_questions`). Templates are resolved once,
+/// here, and the merged list is stored: later template edits do not change existing forms.
+/// The `extends` list itself is stored as lineage; before merging, the whole chain behind it
+/// is walked and a cycle is refused with 400.
+///
+/// A creator who already owns `max_forms_per_creator` forms gets 403. The count and insert
+/// run under a per-creator advisory lock, so concurrent requests cannot overshoot it.
+async fn create_form(
+    State(state): State<AppState>,
+    ApiJson(payload): ApiJson<CreateFormRequest>,
+) -> Result<(StatusCode, Json<FormResponse>), (StatusCode, Json<ErrorResponse>)> {
+    validate_near_account_id(&payload.creator_id, "creator_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    if payload.title.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "title must not be empty".to_string(),
+        })));
+    }
+    validate_tags(&payload.tags)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    validate_resubmit_interval(payload.submission_policy, payload.min_resubmit_interval_secs)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    validate_retention_days(payload.retention_days)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+    validate_denied_submitters(&payload.denied_submitters)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let mut template_ids = Vec::with_capacity(payload.extends.len());
+    let questions = if payload.extends.is_empty() {
+        payload.questions
+    } else {
+        let bad_request = |error: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }));
+        if payload.extends.len() > MAX_EXTENDS {
+            return Err(bad_request(format!(
+                "extends lists {} templates (max {})",
+                payload.extends.len(),
+                MAX_EXTENDS
+            )));
+        }
+        for raw in &payload.extends {
+            let id = Uuid::parse_str(raw)
+                .map_err(|_| bad_request(format!("extends: invalid template ID '{}'", raw)))?;
+            if template_ids.contains(&id) {
+                return Err(bad_request(format!("extends: duplicate template ID '{}'", raw)));
+            }
+            template_ids.push(id);
+        }
+
+        let templates: Vec<(Uuid, bool, serde_json::Value)> = sqlx::query_as(
+            "SELECT id, is_template, questions FROM forms WHERE id = ANY($1)"
+        )
+        .bind(&template_ids)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(db_error)?;
+
+        let mut sources = Vec::with_capacity(template_ids.len() + 1);
+        for id in &template_ids {
+            match templates.iter().find(|(tid, _, _)| tid == id) {
+                None => return Err(bad_request(format!("extends: template {} not found", id))),
+                Some((_, false, _)) => {
+                    return Err(bad_request(format!("extends: form {} is not a template", id)));
+                }
+                Some((_, true, questions)) => sources.push(questions),
+            }
+        }
+        // Walk the stored lineage breadth-first, one query per level, then check it for cycles
+        let mut lineage: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        let mut frontier = template_ids.clone();
+        while !frontier.is_empty() {
+            let level: Vec<(Uuid, Vec<Uuid>)> = sqlx::query_as("SELECT id, extends FROM forms WHERE id = ANY($1)")
+                .bind(&frontier)
+                .fetch_all(&state.pool)
+                .await
+                .map_err(db_error)?;
+            frontier = level
+                .iter()
+                .flat_map(|(_, parents)| parents.iter().copied())
+                .collect();
+            lineage.extend(level);
+            frontier.retain(|parent| !lineage.contains_key(parent));
+            frontier.sort_unstable();
+            frontier.dedup();
+        }
+        if let Some(id) = find_extends_cycle(&template_ids, &lineage) {
+            return Err(bad_request(format!("extends: template {} is part of a cycle", id)));
+        }
+
+        sources.push(&payload.questions);
+        merge_questions(&sources).map_err(bad_request)?
+    };
+    validate_questions(&questions, &state.question_limits)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    check_form_quota(&mut tx, state.max_forms_per_creator, &payload.creator_id).await?;
+
+    let form = sqlx::query_as::<_, Form>(
+        "INSERT INTO forms (creator_id, title, questions, strict_answers, tags, is_template, submission_policy, encryption_scheme, min_resubmit_interval_secs, denied_submitters, retention_days, extends, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW()) RETURNING *"
+    )
+    .bind(&payload.creator_id)
+    .bind(&payload.title)
+    .bind(&questions)
+    .bind(payload.strict_answers)
+    .bind(&payload.tags)
+    .bind(payload.is_template)
+    .bind(payload.submission_policy)
+    .bind(payload.encryption_scheme)
+    .bind(payload.min_resubmit_interval_secs)
+    .bind(&payload.denied_submitters)
+    .bind(payload.retention_days)
+    .bind(&template_ids)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    tracing::info!("Created form {} for creator {}", form.id, form.creator_id);
+    Ok((StatusCode::CREATED, Json(FormResponse::from(form))))
+}
+
+/// Refuse a new form for `creator_id` once it owns `max_forms` (403). Takes a per-creator
+/// advisory lock held until `tx` ends, so the caller's insert cannot race another's.
+async fn check_form_quota(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    max_forms: Option<u32>,
+    creator_id: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let Some(max_forms) = max_forms else {
+        return Ok(());
+    };
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+        .bind(creator_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(db_error)?;
+    // Served by idx_forms_creator
+    let owned: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM forms WHERE creator_id = $1")
+        .bind(creator_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(db_error)?;
+    if owned >= i64::from(max_forms) {
+        return Err((StatusCode::FORBIDDEN, Json(ErrorResponse {
+            error: format!("creator_id already owns the maximum of {} forms", max_forms),
+        })));
+    }
+    Ok(())
+}
+
+/// Rows buffered between the query task and the response body in `export_submissions_csv`
+const CSV_EXPORT_BUFFER: usize = 64;
+
+/// One CSV field per RFC 4180: quoted, with `"` doubled, when it holds a comma, quote or
+/// line break; otherwise as is.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+/// GET /forms/:form_id/submissions.csv - Encrypted submissions as CSV (auth required)
+///
+/// `submitter_id,encrypted_blob,submitted_at` with a header line and CRLF line endings,
+/// one final submission per line (drafts excluded), oldest first: for backup pipelines
+/// that do not hold the decryption key. Per-question blobs are JSON, so they are quoted.
+///
+/// Rows come straight off a single query: a task feeds them to the body through a small
+/// bounded channel, so memory use stays flat and a client that disconnects stops the
+/// query. A database error mid-stream is logged and cuts the body short.
+async fn export_submissions_csv(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM forms WHERE id = $1)")
+        .bind(form_id)
+        .fetch_one(&state.read_pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })));
+    }
+
+    let (sender, receiver) = tokio::sync::mpsc::channel::<Result<Vec<u8>, sqlx::Error>>(CSV_EXPORT_BUFFER);
+    let pool = state.read_pool.clone();
+    tokio::spawn(async move {
+        let mut rows = sqlx::query_as::<_, (String, String, chrono::DateTime<chrono::Utc>)>(
+            "SELECT submitter_id, encrypted_blob, submitted_at FROM submissions
+             WHERE form_id = $1 AND NOT is_draft
+             ORDER BY submitted_at, id"
+        )
+        .bind(form_id)
+        .fetch(&pool);
+        while let Some(row) = futures_util::StreamExt::next(&mut rows).await {
+            let line = row.map(|(submitter_id, blob, submitted_at)| {
+                format!("{},{},{}\r\n", csv_field(&submitter_id), csv_field(&blob), format_timestamp(&submitted_at))
+                    .into_bytes()
+            });
+            let failed = line.is_err();
+            if let Err(e) = &line {
+                error!("CSV export of form {} failed mid-stream: {}", form_id, e);
+            }
+            // A send error means the client went away
+            if sender.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let header = futures_util::stream::once(async { Ok(b"submitter_id,encrypted_blob,submitted_at\r\n".to_vec()) });
+    let lines = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|line| (line, receiver))
+    });
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"form-{}-submissions.csv\"", form_id)),
+        ],
+        axum::body::Body::from_stream(futures_util::StreamExt::chain(header, lines)),
+    )
+        .into_response())
+}
+
+/// Streaming state for `export_form_archive`: one snapshot transaction read in keyset pages.
+struct ArchiveExport {
+    /// None once the closing chunk has been produced
+    tx: Option<sqlx::Transaction<'static, sqlx::Postgres>>,
+    form_id: Uuid,
+    /// Last submission written (keyset position)
+    after: Option<(chrono::DateTime<chrono::Utc>, Uuid)>,
+    hasher: Sha256,
+    written: usize,
+}
+
+impl ArchiveExport {
+    /// The next piece of the `submissions` array, or the closing `],"checksum":...}`
+    /// after the last page. None when the archive is complete.
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, sqlx::Error> {
+        let Some(tx) = self.tx.as_mut() else {
+            return Ok(None);
+        };
+        let (after_ts, after_id) = self.after.unzip();
+        let rows = sqlx::query_as::<_, Submission>(
+            "SELECT id, form_id, submitter_id, encrypted_blob, submitted_at, received_at, is_draft, form_version
+             FROM submissions
+             WHERE form_id = $1 AND ($2::timestamptz IS NULL OR (submitted_at, id) > ($2, $3))
+             ORDER BY submitted_at, id LIMIT $4"
+        )
+        .bind(self.form_id)
+        .bind(after_ts)
+        .bind(after_id)
+        .bind(ARCHIVE_PAGE_SIZE)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let Some(last) = rows.last() else {
+            if let Some(tx) = self.tx.take() {
+                tx.commit().await?;
+            }
+            let checksum = hex::encode(std::mem::take(&mut self.hasher).finalize());
+            return Ok(Some(format!(r#"],"checksum":"{}"}}"#, checksum).into_bytes()));
+        };
+        self.after = Some((last.submitted_at, last.id));
+
+        let mut chunk = Vec::new();
+        for row in rows {
+            let json = serde_json::to_vec(&SubmissionResponse::from(row))
+                .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+            if self.written > 0 {
+                chunk.push(b',');
+            }
+            self.hasher.update(&json);
+            chunk.extend_from_slice(&json);
+            self.written += 1;
+        }
+        Ok(Some(chunk))
+    }
+}
+
+/// GET /forms/:form_id/archive - Export a form with all its submissions (auth required)
+///
+/// Streams `{"format","version","form","submissions":[...],"checksum"}` for backup, or for
+/// moving the form to another deployment with `POST /forms/import`. `form` carries the
+/// form's question history (`versions`) and each submission its `form_version`. Submissions (drafts
+/// included, oldest first) are read in keyset pages from one REPEATABLE READ snapshot, so
+/// memory use stays flat and the archive is consistent while new submissions arrive.
+/// `checksum` is the hex SHA-256 of the `form` object followed by each submission object,
+/// byte for byte as written. A database error mid-stream cuts the body short, and the
+/// truncated archive will not import.
+async fn export_form_archive(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let mut tx = state.read_pool.begin().await.map_err(db_error)?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    let form = sqlx::query_as::<_, Form>("SELECT * FROM forms WHERE id = $1")
+        .bind(form_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+
+    let versions = sqlx::query_as::<_, FormVersion>(
+        "SELECT version, questions, created_at FROM form_versions WHERE form_id = $1 ORDER BY version"
+    )
+    .bind(form_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    let archived = ArchivedForm {
+        derivation_salt: form.derivation_salt.clone(),
+        form: FormResponse::from(form),
+        versions: versions.into_iter().map(FormVersionResponse::from).collect(),
+    };
+    let form_json = serde_json::to_vec(&archived).map_err(|e| {
+        error!("Failed to serialize form {}: {}", form_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
+            error: "Failed to export form".to_string(),
+        }))
+    })?;
+    let mut hasher = Sha256::new();
+    hasher.update(&form_json);
+    let mut head = format!(r#"{{"format":"{}","version":{},"form":"#, ARCHIVE_FORMAT, ARCHIVE_VERSION).into_bytes();
+    head.extend_from_slice(&form_json);
+    head.extend_from_slice(br#","submissions":["#);
+
+    let export = ArchiveExport { tx: Some(tx), form_id, after: None, hasher, written: 0 };
+    let rest = futures_util::stream::unfold(Some(export), |export| async move {
+        let mut export = export?;
+        match export.next_chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(export))),
+            Ok(None) => None,
+            Err(e) => {
+                error!("Archive export of form {} failed mid-stream: {}", export.form_id, e);
+                Some((Err(e), None))
+            }
+        }
+    });
+    let body = futures_util::StreamExt::chain(futures_util::stream::once(async { Ok(head) }), rest);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"form-{}.json\"", form_id)),
+        ],
+        axum::body::Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// POST /forms/import - Recreate a form and its submissions from an archive (auth required)
+///
+/// Takes the output of `GET /forms/:form_id/archive`. The format, version and checksum are
+/// checked first, then the form and every submission go through the same validation as
+/// `create_form` and `create_submission` (including the per-creator form quota), and
+/// everything is inserted in one transaction: either the whole archive is imported or
+/// nothing is. Timestamps, the question history and each submission's `form_version` are
+/// kept as archived (the versioning triggers stand aside for the import transaction), so a
+/// restored form re-exports unchanged.
+///
+/// The form keeps its archived ID unless `?new_id=true` (409 if that ID exists). A new ID
+/// does not re-key anything: blobs stay encrypted to the key derived from the original ID,
+/// and the archived `public_key` is dropped, so the copy has none until one is registered.
+/// Blob replay protection is global, so copying a form within one database is refused.
+async fn import_form_archive(
+    State(state): State<AppState>,
+    Query(params): Query<ImportArchiveParams>,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ImportArchiveResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let bad_request = |error: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }));
+
+    let archive: FormArchive = serde_json::from_slice(&body)
+        .map_err(|e| bad_request(format!("Invalid archive: {}", e)))?;
+    if archive.format != ARCHIVE_FORMAT {
+        return Err(bad_request(format!("format must be '{}'", ARCHIVE_FORMAT)));
+    }
+    if archive.version != ARCHIVE_VERSION {
+        return Err(bad_request(format!(
+            "Unsupported archive version {} (this db-api reads version {})",
+            archive.version, ARCHIVE_VERSION
+        )));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(archive.form.get().as_bytes());
+    for submission in &archive.submissions {
+        hasher.update(submission.get().as_bytes());
+    }
+    if hex::encode(hasher.finalize()) != archive.checksum.to_ascii_lowercase() {
+        return Err(bad_request("Archive checksum does not match its contents".to_string()));
+    }
+
+    let ArchivedForm { form, versions, derivation_salt } = serde_json::from_str(archive.form.get())
+        .map_err(|e| bad_request(format!("form: {}", e)))?;
+    validate_near_account_id(&form.creator_id, "creator_id").map_err(bad_request)?;
+    if form.title.trim().is_empty() {
+        return Err(bad_request("title must not be empty".to_string()));
+    }
+    validate_tags(&form.tags).map_err(bad_request)?;
+    validate_resubmit_interval(form.submission_policy, form.min_resubmit_interval_secs).map_err(bad_request)?;
+    validate_retention_days(form.retention_days).map_err(bad_request)?;
+    validate_denied_submitters(&form.denied_submitters).map_err(bad_request)?;
+    validate_questions(&form.questions, &state.question_limits).map_err(bad_request)?;
+    if derivation_salt.as_deref().is_some_and(|salt| !is_derivation_salt(salt)) {
+        return Err(bad_request("form: derivation_salt must be 64 lowercase hex characters".to_string()));
+    }
+    if form.public_key.as_deref().is_some_and(|key| !is_compressed_public_key(key)) {
+        return Err(bad_request("form: public_key must be a compressed secp256k1 public key in hex".to_string()));
+    }
+    let new_id = params.new_id.unwrap_or(false);
+    let form_id = if new_id {
+        Uuid::new_v4()
+    } else {
+        Uuid::parse_str(&form.id).map_err(|_| bad_request("form: invalid id".to_string()))?
+    };
+    let created_at = chrono::DateTime::parse_from_rfc3339(&form.created_at)
+        .map_err(|_| bad_request("form: created_at must be an RFC 3339 timestamp".to_string()))?
+        .with_timezone(&chrono::Utc);
+
+    let version_numbers: Vec<i32> = versions.iter().map(|v| v.version).collect();
+    if version_numbers.first().is_some_and(|&first| first < 1) || !version_numbers.windows(2).all(|w| w[0] < w[1]) {
+        return Err(bad_request("form: versions must be numbered from 1 up, oldest first".to_string()));
+    }
+    if !versions.last().is_some_and(|latest| latest.version == form.version && latest.questions == form.questions) {
+        return Err(bad_request("form: the last of versions must be the form's current version and questions".to_string()));
+    }
+    let mut version_questions = Vec::with_capacity(versions.len());
+    let mut version_created_ats = Vec::with_capacity(versions.len());
+    for version in versions {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&version.created_at)
+            .map_err(|_| bad_request(format!("form: versions: {}: created_at must be an RFC 3339 timestamp", version.version)))?;
+        version_questions.push(version.questions);
+        version_created_ats.push(created_at.with_timezone(&chrono::Utc));
+    }
+
+    let mut ids = Vec::with_capacity(archive.submissions.len());
+    let mut submitters = Vec::with_capacity(archive.submissions.len());
+    let mut blobs = Vec::with_capacity(archive.submissions.len());
+    let mut hashes = Vec::with_capacity(archive.submissions.len());
+    let mut drafts = Vec::with_capacity(archive.submissions.len());
+    let mut submitted_ats = Vec::with_capacity(archive.submissions.len());
+    let mut received_ats = Vec::with_capacity(archive.submissions.len());
+    let mut form_versions = Vec::with_capacity(archive.submissions.len());
+    for (index, raw) in archive.submissions.iter().enumerate() {
+        let item_error = |e: String| bad_request(format!("submissions[{}]: {}", index, e));
+        let submission: SubmissionResponse = serde_json::from_str(raw.get()).map_err(|e| item_error(e.to_string()))?;
+        if version_numbers.binary_search(&submission.form_version).is_err() {
+            return Err(item_error(format!("form_version {} is not one of the form's versions", submission.form_version)));
+        }
+        form_versions.push(submission.form_version);
+        let request = CreateSubmissionRequest {
+            form_id: form_id.to_string(),
+            submitter_id: submission.submitter_id,
+            encrypted_blob: submission.encrypted_blob,
+            is_draft: submission.is_draft,
+        };
+        let (_, blob) = validate_submission_request(&request, state.max_blob_size).map_err(|(_, e)| item_error(e))?;
+        if !form.encryption_scheme.accepts(&blob) {
+            return Err(item_error(scheme_mismatch_error(form.encryption_scheme)));
+        }
+        ids.push(if new_id {
+            Uuid::new_v4()
+        } else {
+            Uuid::parse_str(&submission.id).map_err(|_| item_error("invalid id".to_string()))?
+        });
+        let timestamp = |value: &str, field: &str| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|_| item_error(format!("{} must be an RFC 3339 timestamp", field)))
+        };
+        submitted_ats.push(timestamp(&submission.submitted_at, "submitted_at")?);
+        received_ats.push(timestamp(&submission.received_at, "received_at")?);
+        hashes.push(blob_hash(&blob));
+        blobs.push(blob);
+        submitters.push(request.submitter_id);
+        drafts.push(request.is_draft);
+    }
+
+    let conflict_or_db_error = |e: sqlx::Error| {
+        let constraint = e.as_database_error().and_then(|d| d.constraint().map(str::to_string));
+        let conflict = match constraint.as_deref() {
+            Some("forms_pkey") => "A form with this ID already exists (import with ?new_id=true for a copy)",
+            Some("submissions_pkey") => "A submission ID in the archive already exists",
+            Some(BLOB_HASH_INDEX) => "A submission blob in the archive is already stored (blob replay protection)",
+            Some("idx_submissions_one_per_account") | Some("idx_submissions_draft") => {
+                "The archive has more than one submission or draft per account for this form's policy"
+            }
+            _ => return db_error_response(e, "Database error"),
+        };
+        (StatusCode::CONFLICT, Json(ErrorResponse { error: conflict.to_string() }))
+    };
+
+    let mut tx = state.pool.begin().await.map_err(conflict_or_db_error)?;
+    check_form_quota(&mut tx, state.max_forms_per_creator, &form.creator_id).await?;
+    // Until commit, the versioning triggers keep the archived versions instead of
+    // recording and stamping their own (see the form_versions migration)
+    sqlx::query("SELECT set_config('near_forms.importing', 'on', true)")
+        .execute(&mut *tx)
+        .await
+        .map_err(conflict_or_db_error)?;
+    let stored = sqlx::query_as::<_, Form>(
+        "INSERT INTO forms (id, creator_id, title, questions, strict_answers, tags, is_template, submission_policy, encryption_scheme, min_resubmit_interval_secs, denied_submitters, paused, derivation_salt, public_key, retention_days, created_at, version) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17) RETURNING *"
+    )
+    .bind(form_id)
+    .bind(&form.creator_id)
+    .bind(&form.title)
+    .bind(&form.questions)
+    .bind(form.strict_answers)
+    .bind(&form.tags)
+    .bind(form.is_template)
+    .bind(form.submission_policy)
+    .bind(form.encryption_scheme)
+    .bind(form.min_resubmit_interval_secs)
+    .bind(&form.denied_submitters)
+    .bind(form.paused)
+    .bind(&derivation_salt)
+    // Derived from the form ID, so a copy under a new ID must be registered again
+    .bind(form.public_key.filter(|_| !new_id).map(|key| key.to_ascii_lowercase()))
+    .bind(form.retention_days)
+    .bind(created_at)
+    .bind(form.version)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(conflict_or_db_error)?;
+
+    sqlx::query(
+        "INSERT INTO form_versions (form_id, version, questions, created_at)
+         SELECT $1, t.version, t.questions, t.created_at
+         FROM UNNEST($2::int4[], $3::jsonb[], $4::timestamptz[]) AS t(version, questions, created_at)"
+    )
+    .bind(form_id)
+    .bind(&version_numbers)
+    .bind(&version_questions)
+    .bind(&version_created_ats)
+    .execute(&mut *tx)
+    .await
+    .map_err(conflict_or_db_error)?;
+
+    sqlx::query(
+        "INSERT INTO submissions
+             (id, form_id, submitter_id, encrypted_blob, blob_hash, one_per_account, is_draft, submitted_at, received_at, form_version)
+         SELECT t.id, $2, t.submitter_id, t.encrypted_blob, t.blob_hash, $3 AND NOT t.is_draft, t.is_draft, t.submitted_at, t.received_at, t.form_version
+         FROM UNNEST($1::uuid[], $4::text[], $5::text[], $6::text[], $7::bool[], $8::timestamptz[], $9::timestamptz[], $10::int4[])
+             AS t(id, submitter_id, encrypted_blob, blob_hash, is_draft, submitted_at, received_at, form_version)"
+    )
+    .bind(&ids)
+    .bind(form_id)
+    .bind(stored.submission_policy == SubmissionPolicy::OnePerAccount)
+    .bind(&submitters)
+    .bind(&blobs)
+    .bind(&hashes)
+    .bind(&drafts)
+    .bind(&submitted_ats)
+    .bind(&received_ats)
+    .bind(&form_versions)
+    .execute(&mut *tx)
+    .await
+    .map_err(conflict_or_db_error)?;
+    tx.commit().await.map_err(conflict_or_db_error)?;
+
+    tracing::info!("Imported form {} with {} submissions", stored.id, ids.len());
+    Ok((StatusCode::CREATED, Json(ImportArchiveResponse {
+        form: FormResponse::from(stored),
+        imported_submissions: ids.len(),
+    })))
+}
+
+/// GET /creators/:creator_id/forms - List a creator's forms, newest first (auth required)
+///
+/// `?tag=x` keeps only forms carrying that exact tag (`tags @> ARRAY[x]`, served by the
+/// GIN index on `forms.tags`).
+async fn list_creator_forms(
+    State(state): State<AppState>,
+    Path(creator_id): Path<String>,
+    Query(params): Query<ListFormsParams>,
+) -> Result<Json<FormListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_near_account_id(&creator_id, "creator_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let forms = sqlx::query_as::<_, Form>(
+        "SELECT * FROM forms \
+         WHERE creator_id = $1 AND ($2::TEXT IS NULL OR tags @> ARRAY[$2::TEXT]) \
+         ORDER BY created_at DESC, id DESC"
+    )
+    .bind(&creator_id)
+    .bind(&params.tag)
+    .fetch_all(&state.read_pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?;
+
+    Ok(Json(FormListResponse {
+        forms: forms.into_iter().map(FormResponse::from).collect(),
+    }))
+}
+
+/// PATCH /forms/:form_id/creator - Transfer form ownership (auth required)
+///
+/// The WASI module authorizes on `form_owners`, which always includes `creator_id`, so this
+/// hands read access to decrypted responses to `new_creator_id` and takes it from the old
+/// creator (other co-owners keep theirs). Note that db-api's startup seeding
+/// rewrites the hardcoded form's creator from `FORM_CREATOR_ID`; update that too or the
+/// transfer is undone on the next restart.
+async fn update_form_creator(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+    ApiJson(payload): ApiJson<UpdateCreatorRequest>,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    validate_near_account_id(&payload.new_creator_id, "new_creator_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let old_creator: Option<String> = sqlx::query_scalar(
+        "SELECT creator_id FROM forms WHERE id = $1"
+    )
+    .bind(form_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?;
+
+    let form = sqlx::query_as::<_, Form>(
+        "UPDATE forms SET creator_id = $2 WHERE id = $1 RETURNING *"
+    )
+    .bind(form_id)
+    .bind(&payload.new_creator_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?
+    .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Form not found".to_string(),
+    })))?;
+
+    state.form_cache.invalidate(form_id);
+    tracing::info!(
+        "Form {} creator changed from '{}' to '{}'",
+        form_id,
+        old_creator.unwrap_or_default(),
+        form.creator_id
+    );
+
+    Ok(Json(FormResponse::from(form)))
+}
+
+/// A form's owners, creator first. Empty only when the form does not exist, since the
+/// creator is always an owner.
+async fn fetch_form_owners<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    form_id: Uuid,
+) -> Result<Vec<FormOwnerResponse>, sqlx::Error> {
+    let owners = sqlx::query_as::<_, FormOwner>(
+        "SELECT o.account_id, o.added_at, o.account_id = f.creator_id AS is_creator, o.visible_questions
+         FROM forms f JOIN form_owners o ON o.form_id = f.id
+         WHERE f.id = $1
+         ORDER BY is_creator DESC, o.added_at, o.account_id"
+    )
+    .bind(form_id)
+    .fetch_all(executor)
+    .await?;
+    Ok(owners.into_iter().map(FormOwnerResponse::from).collect())
+}
+
+/// GET /forms/:form_id/owners - Accounts that own a form (auth required)
+///
+/// The WASI module authorizes ReadResponses, ImportResponses and ReEncryptResponses
+/// against this list. Read from the primary so a removal takes effect at once.
+async fn get_form_owners(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormOwnersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let owners = fetch_form_owners(&state.pool, form_id).await.map_err(|e| db_error_response(e, "Database error"))?;
+    if owners.is_empty() {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })));
+    }
+    Ok(Json(FormOwnersResponse { owners }))
+}
+
+/// PUT /forms/:form_id/owners/:account_id - Add a co-owner (auth required)
+///
+/// Idempotent: adding an existing owner returns the list unchanged. At most
+/// `MAX_FORM_OWNERS` owners per form.
+async fn add_form_owner(
+    State(state): State<AppState>,
+    Path((form_id_str, account_id)): Path<(String, String)>,
+) -> Result<Json<FormOwnersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    validate_near_account_id(&account_id, "account_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    // Lock the form so concurrent adds cannot both pass the owner cap
+    let exists: Option<i32> = sqlx::query_scalar("SELECT 1 FROM forms WHERE id = $1 FOR UPDATE")
+        .bind(form_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    if exists.is_none() {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })));
+    }
+
+    let owners = fetch_form_owners(&mut *tx, form_id).await.map_err(db_error)?;
+    if owners.iter().any(|o| o.account_id == account_id) {
+        return Ok(Json(FormOwnersResponse { owners }));
+    }
+    if owners.len() >= MAX_FORM_OWNERS {
+        return Err((StatusCode::CONFLICT, Json(ErrorResponse {
+            error: format!("Form already has the maximum of {} owners", MAX_FORM_OWNERS),
+        })));
+    }
+
+    sqlx::query("INSERT INTO form_owners (form_id, account_id) VALUES ($1, $2)")
+        .bind(form_id)
+        .bind(&account_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    let owners = fetch_form_owners(&mut *tx, form_id).await.map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    tracing::info!("Form {} owner '{}' added", form_id, account_id);
+    Ok(Json(FormOwnersResponse { owners }))
+}
+
+/// PUT /forms/:form_id/owners/:account_id/visible-questions - Limit which answers a
+/// co-owner can read (auth required)
+///
+/// The WASI module's ReadResponses omits answers to every other question for this owner
+/// (e.g. keeping PII questions to some reviewers). `null` lifts the restriction; an empty
+/// list hides every answer. IDs are not checked against the current questions, so a
+/// restriction survives question edits. The creator cannot be restricted (409).
+async fn set_owner_visible_questions(
+    State(state): State<AppState>,
+    Path((form_id_str, account_id)): Path<(String, String)>,
+    ApiJson(payload): ApiJson<SetVisibleQuestionsRequest>,
+) -> Result<Json<FormOwnersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let bad_request = |error: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }));
+    let form_id = Uuid::parse_str(&form_id_str).map_err(|_| bad_request("Invalid form ID".to_string()))?;
+    validate_near_account_id(&account_id, "account_id").map_err(bad_request)?;
+    if let Some(ids) = &payload.visible_questions {
+        if ids.len() > state.question_limits.max_questions {
+            return Err(bad_request(format!(
+                "visible_questions has {} IDs (max {})",
+                ids.len(),
+                state.question_limits.max_questions
+            )));
+        }
+        let mut seen = std::collections::HashSet::with_capacity(ids.len());
+        for id in ids {
+            if id.trim().is_empty() {
+                return Err(bad_request("visible_questions: question IDs must not be empty".to_string()));
+            }
+            if !seen.insert(id.as_str()) {
+                return Err(bad_request(format!("visible_questions: duplicate question ID '{}'", id)));
+            }
+        }
+    }
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let is_creator: Option<bool> = sqlx::query_scalar(
+        "UPDATE form_owners o SET visible_questions = CASE WHEN o.account_id = f.creator_id THEN NULL ELSE $3 END
+         FROM forms f
+         WHERE o.form_id = f.id AND f.id = $1 AND o.account_id = $2
+         RETURNING o.account_id = f.creator_id"
+    )
+    .bind(form_id)
+    .bind(&account_id)
+    .bind(&payload.visible_questions)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error)?;
+    match is_creator {
+        None => {
+            return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+                error: "Form or owner not found".to_string(),
+            })))
+        }
+        Some(true) => {
+            return Err((StatusCode::CONFLICT, Json(ErrorResponse {
+                error: "The form's creator always sees every question".to_string(),
+            })))
+        }
+        Some(false) => {}
+    }
+
+    let owners = fetch_form_owners(&state.pool, form_id).await.map_err(db_error)?;
+    tracing::info!(
+        "Form {} owner '{}' visible questions set to {}",
+        form_id,
+        account_id,
+        payload.visible_questions.as_ref().map_or_else(|| "all".to_string(), |ids| ids.len().to_string())
+    );
+    Ok(Json(FormOwnersResponse { owners }))
+}
+
+/// DELETE /forms/:form_id/owners/:account_id - Remove a co-owner (auth required)
+///
+/// The creator cannot be removed (409); transfer the form with `PATCH /v1/forms/:id/creator`
+/// instead.
+async fn remove_form_owner(
+    State(state): State<AppState>,
+    Path((form_id_str, account_id)): Path<(String, String)>,
+) -> Result<Json<FormOwnersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    validate_near_account_id(&account_id, "account_id")
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let db_error = |e: sqlx::Error| db_error_response(e, "Database error");
+
+    let removed = sqlx::query(
+        "DELETE FROM form_owners o USING forms f
+         WHERE o.form_id = f.id AND f.id = $1 AND o.account_id = $2 AND o.account_id <> f.creator_id"
+    )
+    .bind(form_id)
+    .bind(&account_id)
+    .execute(&state.pool)
+    .await
+    .map_err(db_error)?
+    .rows_affected();
+
+    let owners = fetch_form_owners(&state.pool, form_id).await.map_err(db_error)?;
+    if removed == 0 {
+        return Err(if owners.is_empty() {
+            (StatusCode::NOT_FOUND, Json(ErrorResponse {
+                error: "Form not found".to_string(),
+            }))
+        } else if owners.iter().any(|o| o.is_creator && o.account_id == account_id) {
+            (StatusCode::CONFLICT, Json(ErrorResponse {
+                error: "The creator is always an owner; transfer the form to another account first".to_string(),
+            }))
+        } else {
+            (StatusCode::NOT_FOUND, Json(ErrorResponse {
+                error: "Account is not an owner of this form".to_string(),
+            }))
+        });
+    }
+
+    tracing::info!("Form {} owner '{}' removed", form_id, account_id);
+    Ok(Json(FormOwnersResponse { owners }))
+}
+
+/// PUT /forms/:form_id/denied-submitters - Replace a form's denylist (auth required)
+///
+/// Listed accounts get 403 from `create_submission` from now on; their existing
+/// submissions are kept. An empty list clears it.
+async fn update_denied_submitters(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+    ApiJson(payload): ApiJson<UpdateDeniedSubmittersRequest>,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    validate_denied_submitters(&payload.denied_submitters)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let form = sqlx::query_as::<_, Form>(
+        "UPDATE forms SET denied_submitters = $2 WHERE id = $1 RETURNING *"
+    )
+    .bind(form_id)
+    .bind(&payload.denied_submitters)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?
+    .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Form not found".to_string(),
+    })))?;
+
+    state.form_cache.invalidate(form_id);
+    tracing::info!("Form {} denylist set to {} account(s)", form_id, form.denied_submitters.len());
+    Ok(Json(FormResponse::from(form)))
+}
+
+/// PUT /forms/:form_id/retention - Set how long a form's submissions are kept (auth required)
+///
+/// Submissions older than `retention_days` are deleted by the next retention sweep (see
+/// `delete_expired_submissions`), including ones already past it when this is set. `null`
+/// keeps them forever.
+async fn update_form_retention(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+    ApiJson(payload): ApiJson<UpdateRetentionRequest>,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    validate_retention_days(payload.retention_days)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
+
+    let form = sqlx::query_as::<_, Form>(
+        "UPDATE forms SET retention_days = $2 WHERE id = $1 RETURNING *"
+    )
+    .bind(form_id)
+    .bind(payload.retention_days)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| db_error_response(e, "Database error"))?
+    .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Form not found".to_string(),
+    })))?;
+
+    state.form_cache.invalidate(form_id);
+    tracing::info!(
+        "Form {} retention set to {}",
+        form_id,
+        form.retention_days.map_or_else(|| "forever".to_string(), |days| format!("{} day(s)", days))
+    );
+    Ok(Json(FormResponse::from(form)))
+}
+
+/// GET /forms/:form_id/public-key - The form's encryption public key (public)
+///
+/// Compressed hex of the exact point clients encrypt submissions to, as registered by the
+/// WASI module. 404 until it has been registered.
+///
+/// db-api never derives keys: the WASI module registers one only for its own `FORM_ID`, the
+/// first time `GetFormPublicKey` runs. Forms created with `POST /forms`, or imported with
+/// `?new_id=true`, get 404 here until a key is registered with `PUT /forms/:form_id/public-key`.
+async fn get_form_public_key(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormPublicKeyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let public_key: Option<String> = sqlx::query_scalar("SELECT public_key FROM forms WHERE id = $1")
+        .bind(form_id)
+        .fetch_optional(&state.read_pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+    let public_key = public_key.ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+        error: "Form public key not registered yet".to_string(),
+    })))?;
+
+    Ok(Json(FormPublicKeyResponse { form_id: form_id.to_string(), public_key }))
+}
+
+/// GET /forms/:form_id/derivation-salt - The form's key derivation salt (auth required)
+///
+/// For the WASI module only: the salt is what keeps a form's key from being derivable from
+/// public values, so `get_form` never returns it. `derivation_salt` is null for older forms.
+async fn get_form_derivation_salt(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormDerivationSaltResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let derivation_salt: Option<String> = sqlx::query_scalar("SELECT derivation_salt FROM forms WHERE id = $1")
+        .bind(form_id)
+        .fetch_optional(&state.read_pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+
+    Ok(Json(FormDerivationSaltResponse { form_id: form_id.to_string(), derivation_salt }))
+}
+
+/// PUT /forms/:form_id/public-key - Register the form's encryption public key (auth required)
+///
+/// Called by the WASI module, which derives the key; db-api only checks that it is a
+/// compressed secp256k1 key and caches it. Re-registering replaces it (e.g. after a master
+/// key rotation).
+async fn set_form_public_key(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+    ApiJson(payload): ApiJson<SetFormPublicKeyRequest>,
+) -> Result<Json<FormPublicKeyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+    if !is_compressed_public_key(&payload.public_key) {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "public_key must be a compressed secp256k1 public key in hex (66 characters, 02 or 03 prefix)".to_string(),
+        })));
+    }
+
+    let public_key: String = sqlx::query_scalar("UPDATE forms SET public_key = $2 WHERE id = $1 RETURNING public_key")
+        .bind(form_id)
+        .bind(payload.public_key.to_ascii_lowercase())
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+
+    state.form_cache.invalidate(form_id);
+    tracing::info!("Form {} public key registered", form_id);
+    Ok(Json(FormPublicKeyResponse { form_id: form_id.to_string(), public_key }))
+}
+
+/// PATCH /forms/:form_id/pause - Stop accepting submissions until resumed (auth required)
+async fn pause_form(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    set_form_paused(&state, &form_id_str, true).await
+}
+
+/// PATCH /forms/:form_id/resume - Accept submissions again after a pause (auth required)
+async fn resume_form(
+    State(state): State<AppState>,
+    Path(form_id_str): Path<String>,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    set_form_paused(&state, &form_id_str, false).await
+}
+
+/// Set `forms.paused`. While paused, `create_submission` answers final submissions with
+/// 423; drafts can still be saved and every read works as before. Repeating a pause or
+/// resume is a no-op that returns the form.
+async fn set_form_paused(
+    state: &AppState,
+    form_id_str: &str,
+    paused: bool,
+) -> Result<Json<FormResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(form_id_str)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "Invalid form ID".to_string(),
+        })))?;
+
+    let form = sqlx::query_as::<_, Form>("UPDATE forms SET paused = $2 WHERE id = $1 RETURNING *")
+        .bind(form_id)
+        .bind(paused)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?
+        .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
+        })))?;
+
+    state.form_cache.invalidate(form_id);
+    tracing::info!("Form {} {}", form_id, if paused { "paused" } else { "resumed" });
+    Ok(Json(FormResponse::from(form)))
+}
+
+// ==================== Submission Events ====================
+
+/// Postgres channel the `submissions_notify_inserted` trigger notifies on
+const SUBMISSION_EVENTS_CHANNEL: &str = "submission_inserted";
+
+/// Events buffered per stream before a slow client is told it lagged
+const SUBMISSION_EVENT_BUFFER: usize = 256;
+
+/// Open `GET /v1/forms/:form_id/submissions/stream` connections allowed at once.
+/// Streams sit outside the request concurrency limit, so they get their own cap.
+const MAX_SUBMISSION_STREAMS: usize = 100;
+
+/// Metadata of a newly committed submission, as sent to stream clients (`event: submission`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionEvent {
+    pub id: String,
+    pub form_id: String,
+    pub submitter_id: String,
+    pub submitted_at: String,
+}
+
+/// Payload of a `submission_inserted` notification (built by the trigger with `json_build_object`)
+#[derive(Debug, Deserialize)]
+struct SubmissionNotification {
+    id: Uuid,
+    form_id: Uuid,
+    submitter_id: String,
+    submitted_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<SubmissionNotification> for SubmissionEvent {
+    fn from(n: SubmissionNotification) -> Self {
+        Self {
+            id: n.id.to_string(),
+            form_id: n.form_id.to_string(),
+            submitter_id: n.submitter_id,
+            submitted_at: format_timestamp(&n.submitted_at),
+        }
+    }
+}
+
+/// Fan-out from the one LISTEN connection (`relay_submission_events`) to every open stream.
+/// `None` on the channel means the server is shutting down and streams should end.
+#[derive(Clone)]
+pub struct SubmissionEvents {
+    sender: broadcast::Sender<Option<SubmissionEvent>>,
+}
+
+impl SubmissionEvents {
+    pub fn new() -> Self {
+        Self { sender: broadcast::channel(SUBMISSION_EVENT_BUFFER).0 }
+    }
+}
+
+impl Default for SubmissionEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Relay `submission_inserted` notifications to `events` until shutdown, then end all streams.
+///
+/// Holds one connection from `pool` for LISTEN; it must be the primary, since NOTIFY is not
+/// delivered on replicas. If that connection drops, PgListener reconnects and notifications
+/// sent in between are lost: streams are live updates, not a log, and a client that needs
+/// every row re-reads `GET /forms/:form_id/submissions` with `since` after reconnecting.
+pub async fn relay_submission_events(pool: PgPool, events: SubmissionEvents, mut shutdown_rx: watch::Receiver<bool>) {
+    let retry_delay = std::time::Duration::from_secs(5);
+    let mut listener = loop {
+        let connected = async {
+            let mut listener = sqlx::postgres::PgListener::connect_with(&pool).await?;
+            listener.listen(SUBMISSION_EVENTS_CHANNEL).await?;
+            Ok::<_, sqlx::Error>(listener)
+        };
+        match connected.await {
+            Ok(listener) => break listener,
+            Err(e) => error!("Submission event listener failed to start, retrying: {}", e),
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(retry_delay) => {}
+            _ = shutdown_rx.changed() => {
+                let _ = events.sender.send(None);
+                return;
+            }
+        }
+    };
 
-    Ok(Json(PaginatedSubmissions {
-        submissions: items,
-        total_count,
-    }))
+    loop {
+        let notification = tokio::select! {
+            notification = listener.recv() => notification,
+            _ = shutdown_rx.changed() => break,
+        };
+        match notification {
+            Ok(notification) => match serde_json::from_str::<SubmissionNotification>(notification.payload()) {
+                // No receivers (no open streams) is not an error
+                Ok(payload) => {
+                    let _ = events.sender.send(Some(payload.into()));
+                }
+                Err(e) => error!("Malformed {} payload: {}", SUBMISSION_EVENTS_CHANNEL, e),
+            },
+            Err(e) => {
+                // recv reconnects on the next call; pause so a down database isn't hammered
+                error!("Submission event listener lost its connection: {}", e);
+                tokio::select! {
+                    _ = tokio::time::sleep(retry_delay) => {}
+                    _ = shutdown_rx.changed() => break,
+                }
+            }
+        }
+    }
+    let _ = events.sender.send(None);
 }
 
-/// POST /submissions - Store a new submission (auth required)
-async fn create_submission(
+/// GET /forms/:form_id/submissions/stream - Live submission metadata as Server-Sent Events (auth required)
+///
+/// Sends `event: submission` (`id:` the submission ID, `data:` a [`SubmissionEvent`]) for
+/// each final submission to the form committed while connected; drafts are not sent. No
+/// backlog is replayed: read `GET /forms/:form_id/submissions` first. A client too slow to
+/// keep up gets `event: lagged` with the number of events it missed and should re-read.
+/// Idle connections get keep-alive comments. Holds no database connection while open.
+///
+/// The static `stream` segment takes precedence over `:submitter_id`, so the submission of
+/// an account literally named `stream` is not reachable at `.../submissions/stream`.
+async fn stream_submissions(
     State(state): State<AppState>,
-    Json(payload): Json<CreateSubmissionRequest>,
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
-    let form_id = Uuid::parse_str(&payload.form_id)
+    Path(form_id_str): Path<String>,
+) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>>>, (StatusCode, Json<ErrorResponse>)> {
+    let form_id = Uuid::parse_str(&form_id_str)
         .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
             error: "Invalid form ID".to_string(),
         })))?;
-
-    // Validate submitter_id as a NEAR account ID
-    validate_near_account_id(&payload.submitter_id, "submitter_id")
-        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))?;
-
-    // Enforce size limit on encrypted_blob to prevent storage abuse.
-    // The blob is hex-encoded, so 400 KB hex = 200 KB binary — matching the WASI module's
-    // MAX_BLOB_SIZE which checks decoded byte length.
-    const MAX_BLOB_HEX_SIZE: usize = 400 * 1024; // 400 KB hex = 200 KB binary
-    if payload.encrypted_blob.len() > MAX_BLOB_HEX_SIZE {
-        return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(ErrorResponse {
-            error: "encrypted_blob exceeds maximum size".to_string(),
-        })));
-    }
-
-    // Validate that encrypted_blob is valid hex to reject garbage early
-    // (WASI module would fail at hex::decode later, resulting in a skipped submission)
-    if payload.encrypted_blob.len() % 2 != 0 {
-        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
-            error: "encrypted_blob must have even length (hex-encoded bytes)".to_string(),
-        })));
-    }
-    // Minimum size: EC01 header (4) + ephemeral pubkey (33) + nonce (12) + Poly1305 tag (16) = 65 bytes = 130 hex chars
-    if payload.encrypted_blob.len() < 130 {
-        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
-            error: "encrypted_blob too short to be a valid EC01 ciphertext".to_string(),
+    if state.submission_events.sender.receiver_count() >= MAX_SUBMISSION_STREAMS {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, Json(ErrorResponse {
+            error: "Too many open submission streams, please retry shortly".to_string(),
         })));
     }
-    if !payload.encrypted_blob.bytes().all(|b| b.is_ascii_hexdigit()) {
-        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
-            error: "encrypted_blob must be valid hex".to_string(),
-        })));
-    }
-
-    // Normalize hex to lowercase for consistent storage
-    let normalized_blob = payload.encrypted_blob.to_ascii_lowercase();
+    // Subscribe before the existence check so nothing committed after it is missed
+    let receiver = state.submission_events.sender.subscribe();
 
-    // Validate EC01 magic bytes (first 4 bytes = "45433031" in hex)
-    if !normalized_blob.starts_with("45433031") {
-        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
-            error: "encrypted_blob must start with EC01 magic bytes".to_string(),
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM forms WHERE id = $1)")
+        .bind(form_id)
+        .fetch_one(&state.read_pool)
+        .await
+        .map_err(|e| db_error_response(e, "Database error"))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse {
+            error: "Form not found".to_string(),
         })));
     }
 
-    // Insert submission
-    let submission_id = Uuid::new_v4();
-    sqlx::query(
-        "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at)
-         VALUES ($1, $2, $3, $4, NOW())"
-    )
-    .bind(submission_id)
-    .bind(form_id)
-    .bind(&payload.submitter_id)
-    .bind(&normalized_blob)
-    .execute(&state.pool)
-    .await
-    .map_err(|e| {
-        if let Some(db_err) = e.as_database_error() {
-            if let Some(code) = db_err.code().as_deref() {
-                const PG_UNIQUE_VIOLATION: &str = "23505";
-                const PG_FOREIGN_KEY_VIOLATION: &str = "23503";
-                if code == PG_UNIQUE_VIOLATION {
-                    return (StatusCode::CONFLICT, Json(ErrorResponse {
-                        error: "You have already submitted this form. Each account can only submit once.".to_string(),
-                    }));
-                }
-                if code == PG_FOREIGN_KEY_VIOLATION {
-                    return (StatusCode::NOT_FOUND, Json(ErrorResponse {
-                        error: "Form not found".to_string(),
-                    }));
+    let form_id = form_id.to_string();
+    let stream = futures_util::stream::unfold(receiver, move |mut receiver| {
+        let form_id = form_id.clone();
+        async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(Some(event)) if event.form_id == form_id => event,
+                    Ok(Some(_)) => continue,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        let lagged = Event::default().event("lagged").data(missed.to_string());
+                        return Some((Ok(lagged), receiver));
+                    }
+                    Ok(None) | Err(broadcast::error::RecvError::Closed) => return None,
+                };
+                match Event::default().event("submission").id(event.id.clone()).json_data(&event) {
+                    Ok(sse) => return Some((Ok(sse), receiver)),
+                    Err(e) => error!("Failed to encode submission event {}: {}", event.id, e),
                 }
             }
         }
-        error!("Database error: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-            error: "Failed to create submission".to_string(),
-        }))
-    })?;
-
-    Ok((StatusCode::CREATED, Json(serde_json::json!({ "id": submission_id.to_string() }))))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 // ==================== App Builder ====================
@@ -509,36 +4193,557 @@ pub fn build_app(state: AppState, cors_origin: Option<&str>) -> Router {
         Some(origin) => CorsLayer::new()
             .allow_origin(origin.parse::<HeaderValue>().expect("Invalid CORS origin"))
             .allow_methods([Method::GET])
-            .allow_headers([axum::http::header::CONTENT_TYPE]),
+            .allow_headers([header::CONTENT_TYPE, header::IF_NONE_MATCH])
+            .expose_headers([header::ETAG]),
+        None => CorsLayer::permissive(),
+    };
+    let submit_cors = match cors_origin {
+        Some(origin) => CorsLayer::new()
+            .allow_origin(origin.parse::<HeaderValue>().expect("Invalid CORS origin"))
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([header::CONTENT_TYPE, HeaderName::from_static("submission-token")]),
         None => CorsLayer::permissive(),
     };
 
+    // Backpressure for protected routes: one permit pool shared by every protected route
+    // (GlobalConcurrencyLimitLayer clones share a semaphore). When all permits are taken,
+    // LoadShed rejects immediately with 503 rather than queueing on pool acquisition.
+    // Applied inside auth so unauthenticated requests never consume a permit.
+    let load_shed = ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(handle_overload))
+        .layer(LoadShedLayer::new())
+        .layer(GlobalConcurrencyLimitLayer::new(state.max_concurrent_requests));
+
     let protected_routes = Router::new()
+        .route("/forms", post(create_form))
+        .route("/forms/validate", post(validate_form_questions))
+        .route("/creators/:creator_id/forms", get(list_creator_forms))
         .route("/forms/:form_id/submissions", get(get_submissions))
-        .route("/submissions", post(create_submission))
+        .route("/forms/:form_id/submissions/:submitter_id", get(get_submission))
+        .route("/submitters/:submitter_id/submissions", get(get_submitter_submissions))
+        .route("/forms/:form_id/creator", patch(update_form_creator))
+        .route("/forms/:form_id/archive", get(export_form_archive))
+        .route("/forms/:form_id/submissions.csv", get(export_submissions_csv))
+        .route("/forms/:form_id/denied-submitters", put(update_denied_submitters))
+        .route("/forms/:form_id/retention", put(update_form_retention))
+        .route("/forms/:form_id/owners", get(get_form_owners))
+        .route("/forms/:form_id/owners/:account_id", put(add_form_owner).delete(remove_form_owner))
+        .route("/forms/:form_id/owners/:account_id/visible-questions", put(set_owner_visible_questions))
+        .route("/forms/:form_id/public-key", put(set_form_public_key))
+        .route("/forms/:form_id/derivation-salt", get(get_form_derivation_salt))
+        .route("/forms/:form_id/pause", patch(pause_form))
+        .route("/forms/:form_id/resume", patch(resume_form))
+        .route("/submission-tokens", post(create_submission_token))
+        .route("/submissions", put(update_submission))
+        .layer(load_shed.clone())
         .layer(middleware::from_fn_with_state(
             state.clone(),
             require_api_secret,
-        ));
+        ))
+        .layer(middleware::from_fn(no_store));
+
+    // Submissions (and the "already submitted?" check) accept the API secret or a submission
+    // token. Token holders are browsers, so these routes also get CORS for the token header.
+    let submit_routes = Router::new()
+        .route("/submissions", post(create_submission))
+        .route("/forms/:form_id/submissions/:submitter_id/exists", get(submission_exists))
+        .layer(load_shed.clone())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_secret_or_token,
+        ))
+        .layer(middleware::from_fn(no_store))
+        .layer(submit_cors);
 
     let public_routes = Router::new()
         .route("/health", get(health))
+        .route("/health/ready", get(health_ready))
+        .route("/version", get(version))
         .route("/forms/:form_id", get(get_form))
+        .route("/forms/:form_id/schema", get(get_form_schema))
+        .route("/forms/:form_id/fields", get(get_form_fields))
+        .route("/forms/:form_id/public-key", get(get_form_public_key))
+        .route("/forms/:form_id/versions", get(get_form_versions))
+        .route("/forms/:form_id/versions/:version", get(get_form_version))
         .layer(cors)
         .layer(middleware::from_fn_with_state(state.clone(), rate_limit));
 
+    // Bulk import gets its own, larger body limit; `.layer` only wraps routes
+    // registered before it, so the single-item limit below does not apply here.
+    let body_limit = request_body_limit(state.max_blob_size);
+    let batch_routes = Router::new()
+        .route("/submissions/batch", post(batch_create_submission))
+        .layer(RequestBodyLimitLayer::new(MAX_BATCH_BODY_BYTES.max(body_limit)))
+        .layer(load_shed.clone())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_secret,
+        ))
+        .layer(middleware::from_fn(no_store));
+
+    let archive_routes = Router::new()
+        .route("/forms/import", post(import_form_archive))
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(MAX_ARCHIVE_BODY_BYTES.max(body_limit)))
+        .layer(load_shed.clone())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_secret,
+        ))
+        .layer(middleware::from_fn(no_store));
+
+    // Streams stay open indefinitely, so they are kept out of `load_shed` (each would pin a
+    // permit); `stream_submissions` caps them with MAX_SUBMISSION_STREAMS instead
+    let stream_routes = Router::new()
+        .route("/forms/:form_id/submissions/stream", get(stream_submissions))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_secret,
+        ))
+        .layer(middleware::from_fn(no_store));
+
+    let v1 = Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .merge(submit_routes)
+        .layer(RequestBodyLimitLayer::new(body_limit))
+        .merge(batch_routes)
+        .merge(archive_routes)
+        .merge(stream_routes);
+
     Router::new()
-        .nest("/v1", Router::new().merge(public_routes).merge(protected_routes))
-        .layer(RequestBodyLimitLayer::new(500 * 1024))
+        .nest("/v1", v1)
+        .layer(middleware::from_fn(json_charset))
+        .layer(middleware::from_fn(retry_after))
         .with_state(state)
 }
 
+// ==================== Maintenance ====================
+
+/// Delete submissions whose `form_id` has no matching `forms` row.
+///
+/// The `submissions.form_id` foreign key normally makes this a no-op; it catches rows left
+/// behind when a form is force-deleted outside the API (constraint dropped, replication role
+/// bypass, manual restore). Runs in a transaction and only touches orphaned rows, so it is
+/// safe alongside live traffic. Returns the number of rows deleted.
+pub async fn delete_orphaned_submissions(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let deleted = sqlx::query(
+        "DELETE FROM submissions s
+         WHERE NOT EXISTS (SELECT 1 FROM forms f WHERE f.id = s.form_id)"
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    tx.commit().await?;
+    Ok(deleted)
+}
+
+/// Delete submissions older than their form's `retention_days`, drafts included.
+///
+/// One statement in a transaction, so a form's expired rows go together or not at all;
+/// forms without a retention period are never touched. Submissions are hard-deleted (db-api
+/// has no soft delete). Returns `(form_id, rows deleted)` for each form that lost any.
+pub async fn delete_expired_submissions(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let deleted = sqlx::query_as::<_, (Uuid, i64)>(
+        "WITH expired AS (
+             DELETE FROM submissions s
+             USING forms f
+             WHERE s.form_id = f.id
+               AND f.retention_days IS NOT NULL
+               AND s.submitted_at < NOW() - make_interval(days => f.retention_days)
+             RETURNING s.form_id
+         )
+         SELECT form_id, COUNT(*) FROM expired GROUP BY form_id ORDER BY form_id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(deleted)
+}
+
 // ==================== Unit Tests ====================
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ==================== request_body_limit ====================
+
+    #[test]
+    fn default_body_limit_unchanged() {
+        assert_eq!(request_body_limit(DEFAULT_MAX_BLOB_SIZE), 500 * 1024);
+    }
+
+    // ==================== submission tokens ====================
+
+    const TOKEN_KEY: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn submission_token_roundtrip() {
+        let form_id = Uuid::new_v4();
+        let token = mint_submission_token(&TOKEN_KEY, form_id, Some("alice.near"), 1_000);
+        let grant = verify_submission_token(&TOKEN_KEY, &token, 999).unwrap();
+        assert_eq!(grant, SubmissionGrant { form_id, submitter_id: Some("alice.near".to_string()) });
+
+        let unbound = mint_submission_token(&TOKEN_KEY, form_id, None, 1_000);
+        assert_eq!(verify_submission_token(&TOKEN_KEY, &unbound, 0).unwrap().submitter_id, None);
+    }
+
+    #[test]
+    fn submission_token_rejects_expired_forged_and_malformed() {
+        let form_id = Uuid::new_v4();
+        let token = mint_submission_token(&TOKEN_KEY, form_id, None, 1_000);
+        assert!(verify_submission_token(&TOKEN_KEY, &token, 1_000).is_none());
+        assert!(verify_submission_token(&[8u8; 32], &token, 0).is_none());
+
+        // Extending the expiry invalidates the signature
+        let tampered = token.replacen(":1000:", ":9999:", 1);
+        assert!(verify_submission_token(&TOKEN_KEY, &tampered, 0).is_none());
+
+        assert!(verify_submission_token(&TOKEN_KEY, "", 0).is_none());
+        assert!(verify_submission_token(&TOKEN_KEY, "not:a:token", 0).is_none());
+    }
+
+    #[test]
+    fn token_key_differs_from_secret_hash() {
+        let secret = "a".repeat(32);
+        assert_ne!(derive_submission_token_key(&secret), hash_api_secret(&secret));
+    }
+
+    // ==================== hash_api_secret ====================
+
+    #[test]
+    fn api_secret_hash_is_sha256() {
+        // SHA-256("abc") test vector
+        assert_eq!(
+            hex::encode(hash_api_secret("abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_api_secret("secret-a"), hash_api_secret("secret-b"));
+    }
+
+    // ==================== form ETag ====================
+
+    fn sample_form() -> FormResponse {
+        FormResponse {
+            id: "daf14a0c-20f7-4199-a07b-c6456d53ef2d".to_string(),
+            creator_id: "alice.testnet".to_string(),
+            title: "Survey".to_string(),
+            questions: serde_json::json!([]),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            strict_answers: false,
+            tags: vec![],
+            is_template: false,
+            submission_policy: SubmissionPolicy::OnePerAccount,
+            encryption_scheme: EncryptionScheme::Any,
+            min_resubmit_interval_secs: None,
+            retention_days: None,
+            denied_submitters: vec![],
+            paused: false,
+            version: 1,
+            public_key: None,
+        }
+    }
+
+    #[test]
+    fn etag_is_quoted_and_stable() {
+        let etag = form_etag(&sample_form());
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag.len(), 66);
+        assert_eq!(etag, form_etag(&sample_form()));
+    }
+
+    #[test]
+    fn etag_changes_with_content() {
+        let mut changed = sample_form();
+        changed.created_at = "2026-01-02T00:00:00+00:00".to_string();
+        assert_ne!(form_etag(&sample_form()), form_etag(&changed));
+    }
+
+    #[test]
+    fn etag_matching_rules() {
+        let etag = "\"abc\"";
+        assert!(etag_matches("\"abc\"", etag));
+        assert!(etag_matches("W/\"abc\"", etag));
+        assert!(etag_matches("\"x\", \"abc\"", etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches("\"abcd\"", etag));
+        assert!(!etag_matches("abc", etag));
+    }
+
+    // ==================== cursor encoding ====================
+
+    #[test]
+    fn cursor_round_trip() {
+        let submission = Submission {
+            id: Uuid::new_v4(),
+            form_id: Uuid::new_v4(),
+            submitter_id: "bob.testnet".to_string(),
+            encrypted_blob: String::new(),
+            submitted_at: chrono::DateTime::from_timestamp_micros(1_772_000_000_123_456).unwrap(),
+            received_at: chrono::Utc::now(),
+            is_draft: false,
+            form_version: 1,
+        };
+        let cursor = encode_cursor(&submission.submitted_at, submission.id);
+        assert_eq!(decode_cursor(&cursor), Some((submission.submitted_at, submission.id)));
+    }
+
+    #[test]
+    fn cursor_rejects_malformed() {
+        assert!(decode_cursor("").is_none());
+        assert!(decode_cursor("123").is_none());
+        assert!(decode_cursor("abc:daf14a0c-20f7-4199-a07b-c6456d53ef2d").is_none());
+        assert!(decode_cursor("123:not-a-uuid").is_none());
+    }
+
+    // ==================== format_timestamp ====================
+
+    #[test]
+    fn timestamp_is_utc_micros_with_z() {
+        let ts = chrono::DateTime::parse_from_rfc3339("2026-03-01T14:00:00.5+02:00")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(format_timestamp(&ts), "2026-03-01T12:00:00.500000Z");
+    }
+
+    // ==================== validate_questions ====================
+
+    fn question(id: &str, label: &str, options: usize) -> serde_json::Value {
+        let options: Vec<String> = (0..options).map(|i| format!("Option {}", i)).collect();
+        serde_json::json!({ "id": id, "label": label, "type": "single_select", "options": options })
+    }
+
+    #[test]
+    fn questions_within_limits() {
+        let questions = serde_json::json!([question("q1", "Pick one", 3), question("q2", "Another", 0)]);
+        assert!(validate_questions(&questions, &QuestionLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn seed_questions_pass_default_limits() {
+        let questions: serde_json::Value =
+            serde_json::from_str(include_str!("../seed/questions.json")).unwrap();
+        assert!(validate_questions(&questions, &QuestionLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn renamed_from_must_name_retired_ids_once() {
+        let renamed = |id: &str, from: serde_json::Value| {
+            let mut q = question(id, "a", 0);
+            q["renamed_from"] = from;
+            q
+        };
+        let limits = QuestionLimits::default();
+        let ok = serde_json::json!([renamed("q1b", serde_json::json!(["q1", "q1a"])), question("q2", "b", 0)]);
+        assert!(validate_questions(&ok, &limits).is_ok());
+
+        let err = validate_questions(&serde_json::json!([renamed("q1b", serde_json::json!("q1"))]), &limits).unwrap_err();
+        assert!(err.contains("must be an array"), "{}", err);
+        let err = validate_questions(&serde_json::json!([renamed("q1b", serde_json::json!(["q2"])), question("q2", "b", 0)]), &limits).unwrap_err();
+        assert!(err.contains("renamed_from q2 is still a question ID"), "{}", err);
+        let err = validate_questions(
+            &serde_json::json!([renamed("q1b", serde_json::json!(["q1"])), renamed("q1c", serde_json::json!(["q1"]))]),
+            &limits,
+        )
+        .unwrap_err();
+        assert!(err.contains("q1 is in more than one renamed_from"), "{}", err);
+    }
+
+    #[test]
+    fn questions_not_array() {
+        let err = validate_questions(&serde_json::json!({}), &QuestionLimits::default()).unwrap_err();
+        assert!(err.contains("JSON array"));
+    }
+
+    #[test]
+    fn too_many_questions() {
+        let limits = QuestionLimits { max_questions: 1, ..QuestionLimits::default() };
+        let questions = serde_json::json!([question("q1", "a", 0), question("q2", "b", 0)]);
+        let err = validate_questions(&questions, &limits).unwrap_err();
+        assert!(err.contains("2 questions (max 1)"));
+    }
+
+    #[test]
+    fn too_many_options() {
+        let limits = QuestionLimits { max_options: 2, ..QuestionLimits::default() };
+        let questions = serde_json::json!([question("q7", "a", 3)]);
+        let err = validate_questions(&questions, &limits).unwrap_err();
+        assert!(err.contains("question q7 has 3 options"));
+    }
+
+    #[test]
+    fn label_and_option_too_long() {
+        let limits = QuestionLimits { max_label_length: 5, ..QuestionLimits::default() };
+        let err = validate_questions(&serde_json::json!([question("q1", "too long", 0)]), &limits).unwrap_err();
+        assert!(err.contains("label is 8 characters"));
+        let err = validate_questions(&serde_json::json!([question("q1", "ok", 1)]), &limits).unwrap_err();
+        assert!(err.contains("option of 8 characters"));
+    }
+
+    #[test]
+    fn question_errors_reports_every_failure_with_index() {
+        let limits = QuestionLimits { max_questions: 2, max_label_length: 5, ..QuestionLimits::default() };
+        let questions = serde_json::json!([question("q1", "ok", 0), question("q2", "too long", 1), question("q3", "ok", 0)]);
+        let errors = question_errors(&questions, &limits);
+        let indices: Vec<Option<usize>> = errors.iter().map(|e| e.index).collect();
+        assert_eq!(indices, [None, Some(1), Some(1)]);
+        assert!(errors[1].message.contains("label is 8 characters"), "{}", errors[1].message);
+        assert_eq!(validate_questions(&questions, &limits).unwrap_err(), errors[0].message);
+        assert!(question_errors(&serde_json::json!([question("q1", "ok", 0)]), &limits).is_empty());
+    }
+
+    // ==================== validate_submission_request ====================
+
+    fn submission(blob: &str) -> CreateSubmissionRequest {
+        CreateSubmissionRequest {
+            form_id: Uuid::new_v4().to_string(),
+            submitter_id: "bob.testnet".to_string(),
+            encrypted_blob: blob.to_string(),
+            is_draft: false,
+        }
+    }
+
+    fn ec01_hex() -> String {
+        format!("45433031{}", "02".repeat(61))
+    }
+
+    #[test]
+    fn submission_blob_whole_is_lowercased() {
+        let (_, blob) = validate_submission_request(&submission(&ec01_hex().to_uppercase()), 1024).unwrap();
+        assert_eq!(blob, ec01_hex());
+    }
+
+    #[test]
+    fn submission_blob_per_question_is_canonical() {
+        let raw = format!(r#"{{ "b": "{0}", "a": "{0}" }}"#, ec01_hex().to_uppercase());
+        let (_, blob) = validate_submission_request(&submission(&raw), 1024).unwrap();
+        assert_eq!(blob, format!(r#"{{"a":"{0}","b":"{0}"}}"#, ec01_hex()));
+    }
+
+    #[test]
+    fn submission_blob_per_question_rejects_bad_fields() {
+        let cases = [
+            "{}".to_string(),
+            "{not json".to_string(),
+            r#"{"a": 1}"#.to_string(),
+            format!(r#"{{"": "{}"}}"#, ec01_hex()),
+            format!(r#"{{"a": "{}"}}"#, &ec01_hex()[8..]),
+        ];
+        for raw in &cases {
+            let (status, _) = validate_submission_request(&submission(raw), 1024).unwrap_err();
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", raw);
+        }
+    }
+
+    // ==================== validate_tags ====================
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn tags_valid() {
+        assert!(validate_tags(&[]).is_ok());
+        assert!(validate_tags(&tags(&["research", "Q3 2026", "émigré"])).is_ok());
+    }
+
+    #[test]
+    fn tags_rejected() {
+        assert!(validate_tags(&tags(&["ok", " "])).unwrap_err().contains("tag #2 is empty"));
+        assert!(validate_tags(&["x".repeat(MAX_TAG_LENGTH + 1)]).unwrap_err().contains("max 50"));
+        assert!(validate_tags(&tags(&["a\nb"])).unwrap_err().contains("control characters"));
+        assert!(validate_tags(&tags(&["a", "b", "a"])).unwrap_err().contains("duplicate tag 'a'"));
+        let many: Vec<String> = (0..=MAX_TAGS).map(|i| i.to_string()).collect();
+        assert!(validate_tags(&many).unwrap_err().contains("max 20"));
+    }
+
+    // ==================== validate_resubmit_interval ====================
+
+    #[test]
+    fn resubmit_interval_bounds_and_policy() {
+        assert!(validate_resubmit_interval(SubmissionPolicy::OnePerAccount, None).is_ok());
+        assert!(validate_resubmit_interval(SubmissionPolicy::Unlimited, Some(60)).is_ok());
+        assert!(validate_resubmit_interval(SubmissionPolicy::Unlimited, Some(MAX_RESUBMIT_INTERVAL_SECS)).is_ok());
+        for bad in [0, -5, MAX_RESUBMIT_INTERVAL_SECS + 1] {
+            let err = validate_resubmit_interval(SubmissionPolicy::Unlimited, Some(bad)).unwrap_err();
+            assert!(err.contains("between 1 and 2592000"), "{}", err);
+        }
+        let err = validate_resubmit_interval(SubmissionPolicy::OnePerAccount, Some(60)).unwrap_err();
+        assert!(err.contains("requires submission_policy 'unlimited'"), "{}", err);
+    }
+
+    #[test]
+    fn retention_days_bounds() {
+        assert!(validate_retention_days(None).is_ok());
+        assert!(validate_retention_days(Some(1)).is_ok());
+        assert!(validate_retention_days(Some(MAX_RETENTION_DAYS)).is_ok());
+        for bad in [0, -1, MAX_RETENTION_DAYS + 1] {
+            let err = validate_retention_days(Some(bad)).unwrap_err();
+            assert!(err.contains("between 1 and 36500"), "{}", err);
+        }
+    }
+
+    // ==================== csv_field ====================
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("alice.testnet"), "alice.testnet");
+        assert_eq!(csv_field("4543303102ab"), "4543303102ab");
+        assert_eq!(csv_field(r#"{"q1":"ab","q2":"cd"}"#), r#""{""q1"":""ab"",""q2"":""cd""}""#);
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    // ==================== validate_denied_submitters ====================
+
+    #[test]
+    fn denied_submitters_validated() {
+        assert!(validate_denied_submitters(&[]).is_ok());
+        assert!(validate_denied_submitters(&tags(&["mallory.testnet", "eve.near"])).is_ok());
+        let err = validate_denied_submitters(&tags(&["mallory.testnet", "Bad Account"])).unwrap_err();
+        assert!(err.starts_with("denied_submitters"), "{}", err);
+        let err = validate_denied_submitters(&tags(&["eve.near", "eve.near"])).unwrap_err();
+        assert_eq!(err, "denied_submitters: duplicate account 'eve.near'");
+        let many: Vec<String> = (0..=MAX_DENIED_SUBMITTERS).map(|i| format!("user{}.near", i)).collect();
+        assert!(validate_denied_submitters(&many).unwrap_err().contains("max 1000"));
+    }
+
+    // ==================== merge_questions ====================
+
+    #[test]
+    fn merge_questions_overrides_in_place() {
+        let demographics = serde_json::json!([{ "id": "age", "label": "Age" }, { "id": "country", "label": "Country" }]);
+        let nps = serde_json::json!([{ "id": "nps", "label": "Recommend?" }]);
+        let own = serde_json::json!([{ "id": "age", "label": "Age bracket" }, { "id": "why" }]);
+        let merged = merge_questions(&[&demographics, &nps, &own]).unwrap();
+        let ids: Vec<&str> = merged.as_array().unwrap().iter().map(|q| q["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["age", "country", "nps", "why"]);
+        assert_eq!(merged[0]["label"], "Age bracket");
+    }
+
+    #[test]
+    fn merge_questions_rejects_non_array() {
+        assert!(merge_questions(&[&serde_json::json!({})]).unwrap_err().contains("JSON array"));
+    }
+
+    #[test]
+    fn find_extends_cycle_walks_lineage() {
+        let [a, b, c, d] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        // Diamond: shared ancestors are not cycles
+        let diamond = HashMap::from([(a, vec![b, c]), (b, vec![d]), (c, vec![d]), (d, vec![])]);
+        assert_eq!(find_extends_cycle(&[a, d], &diamond), None);
+        // Unknown IDs are leaves
+        assert_eq!(find_extends_cycle(&[a], &HashMap::from([(a, vec![b])])), None);
+
+        let cyclic = HashMap::from([(a, vec![b]), (b, vec![c]), (c, vec![a])]);
+        assert!(find_extends_cycle(&[a], &cyclic).is_some());
+        let self_loop = HashMap::from([(d, vec![d])]);
+        assert_eq!(find_extends_cycle(&[a, d], &self_loop), Some(d));
+    }
+
     // ==================== validate_near_account_id ====================
 
     #[test]
@@ -660,12 +4865,20 @@ mod tests {
 
     // ==================== extract_client_ip ====================
 
+    fn trust_all() -> [cidr::IpCidr; 2] {
+        cidr::IpCidr::any()
+    }
+
+    fn ip(s: &str) -> std::net::IpAddr {
+        s.parse().unwrap()
+    }
+
     #[test]
     fn extract_ip_direct_connection() {
         let headers = axum::http::HeaderMap::new();
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, false),
+            extract_client_ip(&headers, addr, &[]),
             "10.0.0.1".parse::<std::net::IpAddr>().unwrap()
         );
     }
@@ -676,7 +4889,7 @@ mod tests {
         headers.insert("X-Forwarded-For", "1.2.3.4, 5.6.7.8".parse().unwrap());
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, true),
+            extract_client_ip(&headers, addr, &trust_all()),
             "1.2.3.4".parse::<std::net::IpAddr>().unwrap()
         );
     }
@@ -687,7 +4900,7 @@ mod tests {
         headers.insert("X-Forwarded-For", "1.2.3.4".parse().unwrap());
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, false),
+            extract_client_ip(&headers, addr, &[]),
             "10.0.0.1".parse::<std::net::IpAddr>().unwrap()
         );
     }
@@ -701,7 +4914,7 @@ mod tests {
         );
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, true),
+            extract_client_ip(&headers, addr, &trust_all()),
             "1.1.1.1".parse::<std::net::IpAddr>().unwrap()
         );
     }
@@ -712,18 +4925,49 @@ mod tests {
         headers.insert("X-Forwarded-For", "not-an-ip, 1.2.3.4".parse().unwrap());
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, true),
+            extract_client_ip(&headers, addr, &trust_all()),
             "10.0.0.1".parse::<std::net::IpAddr>().unwrap()
         );
     }
 
+    #[test]
+    fn extract_ip_skips_trusted_hops_from_the_right() {
+        let proxies = cidr::parse_list("10.0.0.0/8").unwrap();
+        let mut headers = axum::http::HeaderMap::new();
+        // Client-supplied 9.9.9.9 is ignored: 1.2.3.4 is the first untrusted hop
+        headers.insert("X-Forwarded-For", "9.9.9.9, 1.2.3.4, 10.0.0.7".parse().unwrap());
+        let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        assert_eq!(extract_client_ip(&headers, addr, &proxies), ip("1.2.3.4"));
+    }
+
+    #[test]
+    fn extract_ip_untrusted_peer_ignores_headers() {
+        let proxies = cidr::parse_list("10.0.0.0/8").unwrap();
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("X-Forwarded-For", "1.2.3.4".parse().unwrap());
+        headers.insert("X-Real-IP", "5.6.7.8".parse().unwrap());
+        let addr: SocketAddr = "203.0.113.9:1234".parse().unwrap();
+        assert_eq!(extract_client_ip(&headers, addr, &proxies), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn extract_ip_real_ip_from_trusted_peer() {
+        let proxies = cidr::parse_list("10.0.0.1").unwrap();
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("X-Real-IP", "5.6.7.8".parse().unwrap());
+        let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        assert_eq!(extract_client_ip(&headers, addr, &proxies), ip("5.6.7.8"));
+        headers.insert("X-Real-IP", "garbage".parse().unwrap());
+        assert_eq!(extract_client_ip(&headers, addr, &proxies), ip("10.0.0.1"));
+    }
+
     #[test]
     fn extract_ip_xff_single_trusted() {
         let mut headers = axum::http::HeaderMap::new();
         headers.insert("X-Forwarded-For", "1.2.3.4".parse().unwrap());
         let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
         assert_eq!(
-            extract_client_ip(&headers, addr, true),
+            extract_client_ip(&headers, addr, &trust_all()),
             "1.2.3.4".parse::<std::net::IpAddr>().unwrap()
         );
     }
@@ -1,8 +1,12 @@
 //! Database HTTP API for near-forms — binary entrypoint.
 
-use db_api::{build_app, validate_near_account_id, AppState, RateLimiter};
+use db_api::{
+    build_app, config::{Config, SeedForm}, delete_expired_submissions, delete_orphaned_submissions, validate_questions, AppState,
+    relay_submission_events, FormCache, RateLimiter, SubmissionEvents, MIGRATOR,
+};
 use sqlx::{postgres::PgPoolOptions, PgPool};
-use std::{env, net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, time::Duration};
+use tokio::sync::watch;
 use tracing::info;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 use uuid::Uuid;
@@ -13,58 +17,71 @@ const QUESTIONS_JSON: &str = include_str!("../seed/questions.json");
 /// Fixed form ID — must match `FORM_ID` in `wasi-near-forms-ark/src/main.rs`.
 const FORM_ID: &str = "daf14a0c-20f7-4199-a07b-c6456d53ef2d";
 
-/// Initialize database and seed hardcoded form
-async fn init_database(pool: &PgPool) -> Result<(), Box<dyn std::error::Error>> {
-    sqlx::migrate!("./migrations")
-        .run(pool)
-        .await?;
+/// Run migrations, then seed the hardcoded form when `FORM_CREATOR_ID` is set
+async fn init_database(pool: &PgPool, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    MIGRATOR.run(pool).await?;
 
+    match &config.seed_form {
+        Some(seed) => seed_form(pool, seed, config).await,
+        None => {
+            info!("FORM_CREATOR_ID not set: not seeding form {}; forms come from POST /v1/forms", FORM_ID);
+            Ok(())
+        }
+    }
+}
+
+/// Upsert the hardcoded form `FORM_ID` from `seed/questions.json` and the `FORM_*` variables
+async fn seed_form(pool: &PgPool, seed: &SeedForm, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
     let form_id = Uuid::parse_str(FORM_ID)?;
-    let creator_id = env::var("FORM_CREATOR_ID")
-        .expect("FORM_CREATOR_ID environment variable not set");
-    validate_near_account_id(&creator_id, "FORM_CREATOR_ID")
-        .map_err(|e| format!("Invalid FORM_CREATOR_ID: {}", e))?;
-    let title = env::var("FORM_TITLE").unwrap_or_else(|_| {
-        tracing::warn!("FORM_TITLE not set, using default 'My Form'");
-        "My Form".to_string()
-    });
+    let creator_id = &seed.creator_id;
+    let title = &seed.title;
+    let strict_answers = seed.strict_answers;
 
     let questions: serde_json::Value = serde_json::from_str(QUESTIONS_JSON)
         .map_err(|e| format!("Invalid questions.json: {}", e))?;
+    validate_questions(&questions, &config.question_limits)
+        .map_err(|e| format!("Invalid questions.json: {}", e))?;
 
-    // Check if form exists with a different creator before upserting
-    let existing_creator: Option<String> = sqlx::query_scalar(
-        "SELECT creator_id FROM forms WHERE id = $1"
+    // Check if form exists with a different creator (or questions version) before upserting
+    let existing: Option<(String, i32)> = sqlx::query_as(
+        "SELECT creator_id, version FROM forms WHERE id = $1"
     )
     .bind(form_id)
     .fetch_optional(pool)
     .await?;
 
-    // Use PostgreSQL xmax trick: xmax=0 means freshly inserted, xmax>0 means updated existing row
-    let was_inserted: bool = sqlx::query_scalar(
-        "INSERT INTO forms (id, creator_id, title, questions, created_at) VALUES ($1, $2, $3, $4, NOW())
-         ON CONFLICT (id) DO UPDATE SET creator_id = EXCLUDED.creator_id, title = EXCLUDED.title, questions = EXCLUDED.questions
-         RETURNING (xmax = 0)"
+    // Use PostgreSQL xmax trick: xmax=0 means freshly inserted, xmax>0 means updated existing row.
+    // Changed questions bump `forms.version` (trigger), keeping the old revision for
+    // submissions that answered it.
+    let (was_inserted, version): (bool, i32) = sqlx::query_as(
+        "INSERT INTO forms (id, creator_id, title, questions, strict_answers, created_at) VALUES ($1, $2, $3, $4, $5, NOW())
+         ON CONFLICT (id) DO UPDATE SET creator_id = EXCLUDED.creator_id, title = EXCLUDED.title, questions = EXCLUDED.questions,
+             strict_answers = EXCLUDED.strict_answers
+         RETURNING (xmax = 0), version"
     )
     .bind(form_id)
-    .bind(&creator_id)
-    .bind(&title)
+    .bind(creator_id)
+    .bind(title)
     .bind(&questions)
+    .bind(strict_answers)
     .fetch_one(pool)
     .await?;
 
     if was_inserted {
-        info!("Seeded new form {} with creator={}, title={}", form_id, creator_id, title);
+        info!("Seeded new form {} with creator={}, title={}, strict_answers={}", form_id, creator_id, title, strict_answers);
     } else {
-        if let Some(ref old) = existing_creator {
-            if old != &creator_id {
+        if let Some((ref old, old_version)) = existing {
+            if old != creator_id {
                 tracing::warn!(
                     "Form {} creator changed from '{}' to '{}' — verify FORM_CREATOR_ID is correct",
                     form_id, old, creator_id
                 );
             }
+            if old_version != version {
+                info!("Form {} questions changed: now at version {} (earlier versions kept for their submissions)", form_id, version);
+            }
         }
-        info!("Updated existing form {} with creator={}, title={}", form_id, creator_id, title);
+        info!("Updated existing form {} with creator={}, title={}, strict_answers={}", form_id, creator_id, title, strict_answers);
     }
 
     Ok(())
@@ -77,83 +94,95 @@ async fn main() {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(fmt::layer())
-        .init();
-
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
-    let api_port = env::var("API_PORT")
-        .unwrap_or_else(|_| "4001".to_string())
-        .parse::<u16>()
-        .expect("API_PORT must be a valid port number");
-    let api_secret =
-        env::var("API_SECRET").expect("API_SECRET environment variable not set");
-    if api_secret.len() < 32 {
-        panic!("API_SECRET must be at least 32 characters (got {})", api_secret.len());
+    // LOG_FORMAT is read before `Config` because config validation itself logs
+    let registry = tracing_subscriber::registry().with(env_filter);
+    match std::env::var("LOG_FORMAT").ok().as_deref() {
+        None | Some("pretty") => registry.with(fmt::layer()).init(),
+        Some("json") => registry.with(fmt::layer().json()).init(),
+        Some(other) => panic!("LOG_FORMAT must be 'json' or 'pretty' (got '{}')", other),
     }
 
-    let pool_size: u32 = env::var("DATABASE_POOL_SIZE")
-        .unwrap_or_else(|_| "5".to_string())
-        .parse()
-        .expect("DATABASE_POOL_SIZE must be a valid number");
-    if pool_size == 0 {
-        panic!("DATABASE_POOL_SIZE must be > 0");
-    }
+    let config = Config::from_env().unwrap_or_else(|e| panic!("{}", e));
+    info!("Configuration: {}", config);
+
     let pool = PgPoolOptions::new()
-        .max_connections(pool_size)
-        .acquire_timeout(Duration::from_secs(5))
-        .connect(&database_url)
+        .max_connections(config.database_pool_size)
+        .acquire_timeout(config.database_acquire_timeout)
+        .connect(&config.database_url)
         .await
         .expect("Failed to connect to database");
 
-    init_database(&pool)
+    // Optional read replica for GET endpoints; falls back to the primary pool.
+    // Migrations and seeding always run against the primary (below).
+    let read_pool = match &config.database_replica_url {
+        Some(replica_url) => {
+            let replica = PgPoolOptions::new()
+                .max_connections(config.database_pool_size)
+                .acquire_timeout(config.database_acquire_timeout)
+                .connect(replica_url)
+                .await
+                .expect("Failed to connect to read replica (DATABASE_REPLICA_URL)");
+            info!("Read endpoints using replica pool (DATABASE_REPLICA_URL)");
+            replica
+        }
+        None => pool.clone(),
+    };
+
+    init_database(&pool, &config)
         .await
         .expect("Failed to initialize database");
 
-    let rate_limit_rps: u32 = env::var("RATE_LIMIT_RPS")
-        .unwrap_or_else(|_| "10".to_string())
-        .parse()
-        .expect("RATE_LIMIT_RPS must be a valid number");
-    let rate_limit_burst: u32 = env::var("RATE_LIMIT_BURST")
-        .unwrap_or_else(|_| "30".to_string())
-        .parse()
-        .expect("RATE_LIMIT_BURST must be a valid number");
-    if rate_limit_rps == 0 {
-        panic!("RATE_LIMIT_RPS must be > 0");
-    }
-    if rate_limit_burst == 0 {
-        panic!("RATE_LIMIT_BURST must be > 0");
-    }
-    let rate_limiter = RateLimiter::new(rate_limit_rps, rate_limit_burst);
-    let trust_proxy: bool = env::var("RATE_LIMIT_TRUST_PROXY")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse()
-        .expect("RATE_LIMIT_TRUST_PROXY must be 'true' or 'false'");
-    info!(
-        "Rate limiting: {} req/s, burst {}, trust_proxy={}",
-        rate_limit_rps, rate_limit_burst, trust_proxy
-    );
-
     let state = AppState {
         pool,
-        api_secret,
-        rate_limiter,
-        trust_proxy,
+        read_pool,
+        api_secret_hash: config.api_secret_hash,
+        submission_token_key: config.submission_token_key,
+        rate_limiter: RateLimiter::new(config.rate_limit_rps, config.rate_limit_burst),
+        trusted_proxies: config.trusted_proxies.into(),
+        max_concurrent_requests: config.max_concurrent_requests,
+        max_blob_size: config.max_blob_size,
+        question_limits: config.question_limits,
+        max_forms_per_creator: config.max_forms_per_creator,
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(config.form_cache_ttl),
+        max_response_bytes: config.max_response_bytes,
     };
 
-    let cors_origin = env::var("CORS_ALLOWED_ORIGIN")
-        .expect("CORS_ALLOWED_ORIGIN must be set (e.g., http://localhost:3000 for local dev, https://forms.example.com for production)");
-    // Validate early: CORS origin is user-provided and must be a valid HTTP header value.
-    // build_app() would panic at request time if this is invalid — fail at startup instead.
-    cors_origin.parse::<axum::http::HeaderValue>().unwrap_or_else(|_| {
-        panic!("CORS_ALLOWED_ORIGIN is not a valid header value (check for invalid characters)")
-    });
-    info!("CORS restricted to origin: {}", cors_origin);
+    // Flipped to true on shutdown so background tasks stop alongside the server
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let cleanup_task = match config.orphan_cleanup_interval {
+        None => {
+            info!("Orphaned submission cleanup disabled (ORPHAN_CLEANUP_INTERVAL_SECS=0)");
+            None
+        }
+        Some(period) => Some(tokio::spawn(orphan_cleanup_loop(
+            state.pool.clone(),
+            period,
+            shutdown_rx.clone(),
+        ))),
+    };
+
+    let retention_task = match config.retention_cleanup_interval {
+        None => {
+            info!("Submission retention cleanup disabled (RETENTION_CLEANUP_INTERVAL_SECS=0)");
+            None
+        }
+        Some(period) => Some(tokio::spawn(retention_cleanup_loop(
+            state.pool.clone(),
+            period,
+            shutdown_rx.clone(),
+        ))),
+    };
 
-    let app = build_app(state, Some(&cors_origin));
+    let events_task = tokio::spawn(relay_submission_events(
+        state.pool.clone(),
+        state.submission_events.clone(),
+        shutdown_rx,
+    ));
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], api_port));
+    let app = build_app(state, Some(&config.cors_allowed_origin));
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.api_port));
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .expect("Failed to bind to port");
@@ -161,16 +190,72 @@ async fn main() {
     info!("Server running on {}", addr);
 
     axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(shutdown_signal(shutdown_tx))
         .await
         .expect("Server error");
 
+    if let Err(e) = events_task.await {
+        tracing::error!("Submission event relay failed: {}", e);
+    }
+    if let Some(task) = cleanup_task {
+        if let Err(e) = task.await {
+            tracing::error!("Orphan cleanup task failed: {}", e);
+        }
+    }
+    if let Some(task) = retention_task {
+        if let Err(e) = task.await {
+            tracing::error!("Retention cleanup task failed: {}", e);
+        }
+    }
+
     info!("Server shut down gracefully");
 }
 
-async fn shutdown_signal() {
+async fn shutdown_signal(shutdown_tx: watch::Sender<bool>) {
     tokio::signal::ctrl_c()
         .await
         .expect("Failed to install CTRL+C signal handler");
     info!("Received shutdown signal, draining in-flight requests...");
+    let _ = shutdown_tx.send(true);
+}
+
+/// Periodically delete submissions whose form no longer exists, until shutdown.
+/// Failures are logged and retried on the next tick rather than ending the task.
+async fn orphan_cleanup_loop(pool: PgPool, period: Duration, mut shutdown_rx: watch::Receiver<bool>) {
+    let mut ticker = tokio::time::interval(period);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = shutdown_rx.changed() => break,
+        }
+        match delete_orphaned_submissions(&pool).await {
+            Ok(0) => {}
+            Ok(n) => info!("Deleted {} orphaned submission(s)", n),
+            Err(e) => tracing::error!("Orphaned submission cleanup failed: {}", e),
+        }
+    }
+    info!("Orphaned submission cleanup stopped");
+}
+
+/// Periodically delete submissions past their form's `retention_days`, until shutdown.
+/// Failures are logged and retried on the next tick rather than ending the task.
+async fn retention_cleanup_loop(pool: PgPool, period: Duration, mut shutdown_rx: watch::Receiver<bool>) {
+    let mut ticker = tokio::time::interval(period);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = shutdown_rx.changed() => break,
+        }
+        match delete_expired_submissions(&pool).await {
+            Ok(deleted) => {
+                for (form_id, n) in deleted {
+                    info!("Deleted {} expired submission(s) from form {}", n, form_id);
+                }
+            }
+            Err(e) => tracing::error!("Submission retention cleanup failed: {}", e),
+        }
+    }
+    info!("Submission retention cleanup stopped");
 }
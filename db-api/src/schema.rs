@@ -0,0 +1,296 @@
+//! JSON Schema generation for form answers.
+//!
+//! Converts a form's `questions` definition (see `seed/questions.json`) into a JSON Schema
+//! (draft 2020-12) describing the plaintext answers object clients encrypt. Answer shapes
+//! mirror what web-ui submits:
+//!
+//! - `single_select` — one of `options`
+//! - `multi_select` — array of distinct `options`
+//! - `rank` — array of up to `rank_count` `options` (`""` marks an unfilled slot)
+//! - `open_text` — free-form string
+//! - `contact` — `options` entry, optionally followed by `:<detail>` (e.g. `"Yes — Email:a@b.c"`)
+//!
+//! Questions with `show_if` are only required when their condition holds (`allOf` +
+//! `if`/`then`); optional questions are never required.
+//!
+//! [`questions_to_fields`] is the lightweight alternative: just `{ id, type, required }`
+//! per question, for clients that do not need the rendering metadata.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// JSON Schema dialect emitted by `questions_to_json_schema`.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Build a JSON Schema for a form's answers object.
+///
+/// Returns an error naming the offending question if the definition is malformed
+/// (missing `id`/`type`, unknown `type`, or a choice question without `options`).
+pub fn questions_to_json_schema(title: &str, questions: &Value) -> Result<Value, String> {
+    let list = questions
+        .as_array()
+        .ok_or_else(|| "questions must be a JSON array".to_string())?;
+
+    let mut properties = Map::new();
+    let mut required: Vec<Value> = Vec::new();
+    let mut conditionals: Vec<Value> = Vec::new();
+
+    for (i, question) in list.iter().enumerate() {
+        let id = question
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("question #{} is missing an id", i + 1))?;
+        let kind = question
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("question {} is missing a type", id))?;
+
+        let mut property = answer_schema(id, kind, question)?;
+        if let Some(label) = question.get("label").and_then(Value::as_str) {
+            property["description"] = json!(label);
+        }
+        properties.insert(id.to_string(), property);
+
+        let optional = question.get("optional").and_then(Value::as_bool).unwrap_or(false);
+        if optional {
+            continue;
+        }
+        match question.get("show_if").filter(|v| !v.is_null()) {
+            None => required.push(json!(id)),
+            Some(show_if) => conditionals.push(conditional_requirement(id, show_if, list)?),
+        }
+    }
+
+    let mut schema = json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": title,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    if !conditionals.is_empty() {
+        schema["allOf"] = Value::Array(conditionals);
+    }
+    Ok(schema)
+}
+
+/// Schema for a single answer value, by question type.
+fn answer_schema(id: &str, kind: &str, question: &Value) -> Result<Value, String> {
+    let options = || -> Result<Vec<Value>, String> {
+        question
+            .get("options")
+            .and_then(Value::as_array)
+            .filter(|o| !o.is_empty())
+            .cloned()
+            .ok_or_else(|| format!("question {} ({}) has no options", id, kind))
+    };
+
+    match kind {
+        "single_select" => Ok(json!({ "type": "string", "enum": options()? })),
+        "multi_select" => Ok(json!({
+            "type": "array",
+            "items": { "enum": options()? },
+            "uniqueItems": true,
+        })),
+        "rank" => {
+            let mut slots = options()?;
+            slots.push(json!(""));
+            let mut schema = json!({
+                "type": "array",
+                "items": { "enum": slots },
+            });
+            if let Some(count) = question.get("rank_count").and_then(Value::as_u64) {
+                schema["maxItems"] = json!(count);
+            }
+            Ok(schema)
+        }
+        "open_text" => Ok(json!({ "type": "string" })),
+        "contact" => {
+            // Either a bare option, or "<option>:<detail>" for options that collect details
+            let prefixes: Vec<String> = options()?
+                .iter()
+                .filter_map(Value::as_str)
+                .map(regex_escape)
+                .collect();
+            Ok(json!({
+                "type": "string",
+                "pattern": format!("^({})(:.*)?$", prefixes.join("|")),
+            }))
+        }
+        other => Err(format!("question {} has unknown type '{}'", id, other)),
+    }
+}
+
+/// `if`/`then` clause requiring `id` when its `show_if` condition is met.
+fn conditional_requirement(id: &str, show_if: &Value, questions: &[Value]) -> Result<Value, String> {
+    let parent = show_if
+        .get("question_id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("question {} show_if is missing question_id", id))?;
+    let accepted: Vec<Value> = match (show_if.get("value"), show_if.get("values")) {
+        (_, Some(Value::Array(values))) => values.clone(),
+        (Some(value), _) if !value.is_null() => vec![value.clone()],
+        _ => return Err(format!("question {} show_if needs value or values", id)),
+    };
+
+    // Multi-select parents hold arrays, so the condition is "contains any accepted value"
+    let parent_is_array = questions.iter().any(|q| {
+        q.get("id").and_then(Value::as_str) == Some(parent)
+            && q.get("type").and_then(Value::as_str) == Some("multi_select")
+    });
+    let condition = if parent_is_array {
+        json!({ "contains": { "enum": accepted } })
+    } else {
+        json!({ "enum": accepted })
+    };
+
+    Ok(json!({
+        "if": {
+            "properties": { parent: condition },
+            "required": [parent],
+        },
+        "then": { "required": [id] },
+    }))
+}
+
+/// One answerable field of a form, as returned by `GET /v1/forms/:id/fields`.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FormField {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// False for `optional` questions. A `show_if` question is required only when shown.
+    pub required: bool,
+}
+
+/// Project a form's `questions` onto `{ id, type, required }`, in question order.
+///
+/// Fails on the same missing `id`/`type` as `questions_to_json_schema`, but does not
+/// check types or options: the projection passes unknown types through.
+pub fn questions_to_fields(questions: &Value) -> Result<Vec<FormField>, String> {
+    let list = questions
+        .as_array()
+        .ok_or_else(|| "questions must be a JSON array".to_string())?;
+    list.iter()
+        .enumerate()
+        .map(|(i, question)| {
+            let id = question
+                .get("id")
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("question #{} is missing an id", i + 1))?;
+            let kind = question
+                .get("type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("question {} is missing a type", id))?;
+            Ok(FormField {
+                id: id.to_string(),
+                kind: kind.to_string(),
+                required: !question.get("optional").and_then(Value::as_bool).unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// Escape regex metacharacters so an option label can be embedded in a `pattern`.
+fn regex_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_for(questions: Value) -> Value {
+        questions_to_json_schema("Test", &questions).unwrap()
+    }
+
+    #[test]
+    fn choice_questions_get_enums() {
+        let schema = schema_for(json!([
+            { "id": "q1", "label": "Pick", "type": "single_select", "options": ["A", "B"], "optional": false },
+            { "id": "q2", "label": "Many", "type": "multi_select", "options": ["X", "Y"], "optional": true },
+        ]));
+        assert_eq!(schema["properties"]["q1"]["enum"], json!(["A", "B"]));
+        assert_eq!(schema["properties"]["q1"]["description"], "Pick");
+        assert_eq!(schema["properties"]["q2"]["type"], "array");
+        assert_eq!(schema["properties"]["q2"]["items"]["enum"], json!(["X", "Y"]));
+        assert_eq!(schema["required"], json!(["q1"]));
+        assert!(schema.get("allOf").is_none());
+    }
+
+    #[test]
+    fn rank_allows_empty_slots_and_caps_length() {
+        let schema = schema_for(json!([
+            { "id": "q8", "type": "rank", "rank_count": 3, "options": ["A", "B", "C", "D"], "optional": true },
+        ]));
+        assert_eq!(schema["properties"]["q8"]["maxItems"], 3);
+        assert_eq!(schema["properties"]["q8"]["items"]["enum"], json!(["A", "B", "C", "D", ""]));
+    }
+
+    #[test]
+    fn contact_pattern_escapes_options() {
+        let schema = schema_for(json!([
+            { "id": "q25", "type": "contact", "options": ["Yes (Email)", "No"], "optional": true },
+        ]));
+        assert_eq!(schema["properties"]["q25"]["pattern"], "^(Yes \\(Email\\)|No)(:.*)?$");
+    }
+
+    #[test]
+    fn show_if_becomes_conditional_requirement() {
+        let schema = schema_for(json!([
+            { "id": "q1", "type": "multi_select", "options": ["A", "B"], "optional": false },
+            { "id": "q2", "type": "open_text", "optional": false, "show_if": { "question_id": "q1", "value": "A" } },
+            { "id": "q3", "type": "single_select", "options": ["Y", "N"], "optional": false },
+            { "id": "q4", "type": "open_text", "optional": false, "show_if": { "question_id": "q3", "values": ["Y"] } },
+        ]));
+        assert_eq!(schema["required"], json!(["q1", "q3"]));
+        let all_of = schema["allOf"].as_array().unwrap();
+        assert_eq!(all_of[0]["if"]["properties"]["q1"], json!({ "contains": { "enum": ["A"] } }));
+        assert_eq!(all_of[0]["then"]["required"], json!(["q2"]));
+        assert_eq!(all_of[1]["if"]["properties"]["q3"], json!({ "enum": ["Y"] }));
+    }
+
+    #[test]
+    fn rejects_malformed_definitions() {
+        let err = questions_to_json_schema("T", &json!([{ "id": "q1", "type": "slider" }])).unwrap_err();
+        assert!(err.contains("unknown type 'slider'"));
+        let err = questions_to_json_schema("T", &json!([{ "id": "q1", "type": "single_select" }])).unwrap_err();
+        assert!(err.contains("has no options"));
+        assert!(questions_to_json_schema("T", &json!({})).is_err());
+    }
+
+    #[test]
+    fn fields_project_id_type_required() {
+        let fields = questions_to_fields(&json!([
+            { "id": "q1", "label": "Pick", "type": "single_select", "options": ["A"], "optional": false },
+            { "id": "q2", "type": "open_text", "optional": true },
+            { "id": "q3", "type": "contact" },
+        ]))
+        .unwrap();
+        let summary: Vec<(&str, &str, bool)> =
+            fields.iter().map(|f| (f.id.as_str(), f.kind.as_str(), f.required)).collect();
+        assert_eq!(summary, [("q1", "single_select", true), ("q2", "open_text", false), ("q3", "contact", true)]);
+        assert_eq!(
+            serde_json::to_value(&fields[1]).unwrap(),
+            json!({ "id": "q2", "type": "open_text", "required": false })
+        );
+        assert!(questions_to_fields(&json!([{ "type": "open_text" }])).is_err());
+    }
+
+    #[test]
+    fn seed_questions_convert() {
+        let questions: Value = serde_json::from_str(include_str!("../seed/questions.json")).unwrap();
+        let schema = questions_to_json_schema("Seed", &questions).unwrap();
+        assert_eq!(
+            schema["properties"].as_object().unwrap().len(),
+            questions.as_array().unwrap().len()
+        );
+    }
+}
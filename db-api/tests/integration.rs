@@ -5,11 +5,16 @@
 
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
-use db_api::{AppState, RateLimiter, build_app};
+use db_api::{
+    AppState, DEFAULT_FORM_CACHE_TTL_SECS, DEFAULT_MAX_BLOB_SIZE, DEFAULT_MAX_FORMS_PER_CREATOR, DEFAULT_MAX_RESPONSE_BYTES, FormCache, QuestionLimits, RateLimiter, SubmissionEvents, build_app, delete_expired_submissions, delete_orphaned_submissions,
+    derive_submission_token_key, hash_api_secret, relay_submission_events, retry_transaction, MAX_TRANSACTION_ATTEMPTS,
+};
 use http_body_util::BodyExt;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use std::net::SocketAddr;
+use std::time::Duration;
 use tower::ServiceExt;
 use uuid::Uuid;
 
@@ -22,10 +27,19 @@ const TEST_FORM_ID: &str = "daf14a0c-20f7-4199-a07b-c6456d53ef2d";
 
 fn test_app(pool: PgPool) -> axum::Router {
     let state = AppState {
+        read_pool: pool.clone(),
         pool,
-        api_secret: TEST_API_SECRET.to_string(),
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
         rate_limiter: RateLimiter::new(1000, 1000),
-        trust_proxy: false,
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
     };
     build_app(state, None)
 }
@@ -65,6 +79,11 @@ fn valid_ec01_blob() -> String {
     hex
 }
 
+/// `valid_ec01_blob` with 16 random trailing bytes, so it doesn't trip the blob_hash replay check
+fn unique_ec01_blob() -> String {
+    format!("{}{}", valid_ec01_blob(), Uuid::new_v4().simple())
+}
+
 async fn insert_submission(pool: &PgPool, submitter: &str, blob: &str) {
     let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
     sqlx::query(
@@ -111,6 +130,59 @@ async fn health_returns_ok(pool: PgPool) {
     assert_eq!(json["status"], "ok");
 }
 
+fn get_ready() -> Request<Body> {
+    with_connect_info(
+        Request::builder()
+            .uri("/v1/health/ready")
+            .body(Body::empty())
+            .unwrap(),
+    )
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn health_ready_when_migrations_applied(pool: PgPool) {
+    let app = test_app(pool);
+    let resp = app.oneshot(get_ready()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["status"], "ready");
+    assert_eq!(json["expected_version"], json["applied_version"]);
+    assert!(json.get("pending_versions").is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn health_ready_reports_pending_migrations(pool: PgPool) {
+    let latest: i64 = sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+        .bind(latest)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = test_app(pool);
+    let resp = app.oneshot(get_ready()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let json = body_json(resp).await;
+    assert_eq!(json["status"], "migrations_pending");
+    assert_eq!(json["pending_versions"], serde_json::json!([latest]));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn version_reports_build_and_schema(pool: PgPool) {
+    let app = test_app(pool);
+    let req = with_connect_info(Request::builder().uri("/v1/version").body(Body::empty()).unwrap());
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!json["git_sha"].as_str().unwrap().is_empty());
+    assert!(json["migration_version"].is_i64());
+    assert_eq!(json["migration_version"], json["expected_migration_version"]);
+}
+
 // ==================== GET /v1/forms/:id ====================
 
 #[sqlx::test(migrations = "./migrations")]
@@ -130,6 +202,187 @@ async fn get_form_returns_form(pool: PgPool) {
     assert_eq!(json["creator_id"], "alice.testnet");
     assert_eq!(json["title"], "Test Form");
     assert!(json["questions"].is_array());
+    assert_eq!(json["strict_answers"], false);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_form_sets_etag_and_honors_if_none_match(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let etag = resp.headers()["etag"].to_str().unwrap().to_string();
+
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+            .header("If-None-Match", &etag)
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers()["etag"], etag.as_str());
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    assert!(bytes.is_empty());
+
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+            .header("If-None-Match", "\"stale\"")
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_form_schema_returns_json_schema(pool: PgPool) {
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    sqlx::query(
+        "INSERT INTO forms (id, creator_id, title, questions, created_at) \
+         VALUES ($1, 'alice.testnet', 'Schema Form', $2, NOW())",
+    )
+    .bind(form_id)
+    .bind(serde_json::json!([
+        {"id": "q1", "label": "Pick", "type": "single_select", "options": ["A", "B"], "optional": false},
+        {"id": "q2", "label": "Why?", "type": "open_text", "options": null, "optional": true}
+    ]))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = test_app(pool);
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/schema", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["title"], "Schema Form");
+    assert_eq!(json["type"], "object");
+    assert_eq!(json["properties"]["q1"]["enum"], serde_json::json!(["A", "B"]));
+    assert_eq!(json["required"], serde_json::json!(["q1"]));
+
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/schema", Uuid::new_v4()))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_form_fields_projects_questions(pool: PgPool) {
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    sqlx::query(
+        "INSERT INTO forms (id, creator_id, title, questions, created_at) \
+         VALUES ($1, 'alice.testnet', 'Fields Form', $2, NOW())",
+    )
+    .bind(form_id)
+    .bind(serde_json::json!([
+        {"id": "q1", "label": "Pick", "type": "single_select", "options": ["A", "B"], "optional": false},
+        {"id": "q2", "label": "Why?", "type": "open_text", "options": null, "optional": true}
+    ]))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = test_app(pool);
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/fields", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(resp).await,
+        serde_json::json!({ "fields": [
+            { "id": "q1", "type": "single_select", "required": true },
+            { "id": "q2", "type": "open_text", "required": false },
+        ] })
+    );
+
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/fields", Uuid::new_v4()))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_versions_track_question_edits(pool: PgPool) {
+    seed_form(&pool).await;
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    let app = test_app(pool.clone());
+    let get = |uri: String| {
+        let app = app.clone();
+        async move {
+            let req = with_connect_info(Request::builder().uri(uri).body(Body::empty()).unwrap());
+            let resp = app.oneshot(req).await.unwrap();
+            let status = resp.status();
+            (status, body_json(resp).await)
+        }
+    };
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Rewriting identical questions (as every restart's seeding does) is not a new version
+    let edit = |questions: Value| {
+        sqlx::query("UPDATE forms SET questions = $2 WHERE id = $1").bind(form_id).bind(questions).execute(&pool)
+    };
+    edit(serde_json::json!([{"id": "q1", "text": "Favorite color?", "type": "text"}])).await.unwrap();
+    edit(serde_json::json!([{"id": "q2", "text": "Favorite animal?", "type": "text"}])).await.unwrap();
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let (_, json) = get_submissions_json(&app, "?order=asc").await;
+    let versions: Vec<(&str, i64)> = json["submissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| (s["submitter_id"].as_str().unwrap(), s["form_version"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(versions, [("bob.testnet", 1), ("carol.testnet", 2)]);
+
+    let (status, json) = get(format!("/v1/forms/{}/versions", TEST_FORM_ID)).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = json["versions"].as_array().unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0]["version"], 1);
+    assert_eq!(listed[1]["questions"][0]["id"], "q2");
+
+    let (status, json) = get(format!("/v1/forms/{}/versions/1", TEST_FORM_ID)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["questions"][0]["id"], "q1");
+    let (_, json) = get(format!("/v1/forms/{}", TEST_FORM_ID)).await;
+    assert_eq!(json["version"], 2);
+
+    let (status, _) = get(format!("/v1/forms/{}/versions/3", TEST_FORM_ID)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get(format!("/v1/forms/{}/versions/latest", TEST_FORM_ID)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get(format!("/v1/forms/{}/versions", Uuid::new_v4())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -161,6 +414,83 @@ async fn get_form_not_found(pool: PgPool) {
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn get_form_cache_is_invalidated_by_updates(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    let fetch_form = || async {
+        let req = with_connect_info(
+            Request::builder()
+                .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+                .body(Body::empty())
+                .unwrap(),
+        );
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        body_json(resp).await
+    };
+    assert_eq!(fetch_form().await["creator_id"], "alice.testnet");
+
+    // A write behind db-api's back is not seen until the entry expires...
+    sqlx::query("UPDATE forms SET title = 'Renamed' WHERE id = $1")
+        .bind(Uuid::parse_str(TEST_FORM_ID).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(fetch_form().await["title"], "Test Form");
+
+    // ...but db-api's own updates drop it straight away
+    let req = patch_creator(TEST_FORM_ID, "carol.testnet", Some(TEST_API_SECRET));
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+    let json = fetch_form().await;
+    assert_eq!(json["creator_id"], "carol.testnet");
+    assert_eq!(json["title"], "Renamed");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_form_cache_is_not_refilled_from_a_lagging_replica(pool: PgPool) {
+    seed_form(&pool).await;
+    // A "replica" frozen before the write below: same database, but `forms` resolves to a
+    // snapshot in another schema
+    sqlx::query("CREATE SCHEMA replica").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TABLE replica.forms AS SELECT * FROM public.forms").execute(&pool).await.unwrap();
+    let lagging = sqlx::postgres::PgPoolOptions::new()
+        .connect_with((*pool.connect_options()).clone().options([("search_path", "replica,public")]))
+        .await
+        .unwrap();
+    let state = AppState {
+        pool: pool.clone(),
+        read_pool: lagging,
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    let req = patch_creator(TEST_FORM_ID, "carol.testnet", Some(TEST_API_SECRET));
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+    for _ in 0..2 {
+        let req = with_connect_info(
+            Request::builder()
+                .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+                .body(Body::empty())
+                .unwrap(),
+        );
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body_json(resp).await["creator_id"], "carol.testnet");
+    }
+}
+
 // ==================== GET /v1/forms/:id/submissions ====================
 
 #[sqlx::test(migrations = "./migrations")]
@@ -175,6 +505,43 @@ async fn get_submissions_no_api_secret(pool: PgPool) {
     assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn authenticated_responses_are_not_cached(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["cache-control"], "no-store");
+    assert_eq!(resp.headers()["content-type"], "application/json; charset=utf-8");
+
+    // Auth failures carry the same headers
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(resp.headers()["cache-control"], "no-store");
+    assert_eq!(resp.headers()["content-type"], "application/json; charset=utf-8");
+
+    // Public form metadata stays cacheable (ETag revalidation)
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert!(resp.headers().get("cache-control").is_none());
+    assert_eq!(resp.headers()["content-type"], "application/json; charset=utf-8");
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn get_submissions_wrong_api_secret(pool: PgPool) {
     seed_form(&pool).await;
@@ -193,7 +560,7 @@ async fn get_submissions_empty_result(pool: PgPool) {
     seed_form(&pool).await;
     let app = test_app(pool);
     let req = Request::builder()
-        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .uri(format!("/v1/forms/{}/submissions?with_total=true", TEST_FORM_ID))
         .header("API-Secret", TEST_API_SECRET)
         .body(Body::empty())
         .unwrap();
@@ -202,6 +569,7 @@ async fn get_submissions_empty_result(pool: PgPool) {
     let json = body_json(resp).await;
     assert_eq!(json["submissions"].as_array().unwrap().len(), 0);
     assert_eq!(json["total_count"], 0);
+    assert_eq!(json["has_more"], false);
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -234,7 +602,10 @@ async fn get_submissions_default_pagination(pool: PgPool) {
     assert_eq!(resp.status(), StatusCode::OK);
     let json = body_json(resp).await;
     assert_eq!(json["submissions"].as_array().unwrap().len(), 3);
-    assert_eq!(json["total_count"], 3);
+    // total_count is opt-in via ?with_total=true
+    assert!(json.get("total_count").is_none());
+    assert_eq!(json["has_more"], false);
+    assert!(json.get("next_cursor").is_none());
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -248,7 +619,7 @@ async fn get_submissions_custom_offset_limit(pool: PgPool) {
     let app = test_app(pool);
     let req = Request::builder()
         .uri(format!(
-            "/v1/forms/{}/submissions?offset=1&limit=1",
+            "/v1/forms/{}/submissions?offset=1&limit=1&with_total=true",
             TEST_FORM_ID
         ))
         .header("API-Secret", TEST_API_SECRET)
@@ -259,78 +630,457 @@ async fn get_submissions_custom_offset_limit(pool: PgPool) {
     let json = body_json(resp).await;
     assert_eq!(json["submissions"].as_array().unwrap().len(), 1);
     assert_eq!(json["total_count"], 3);
+    assert_eq!(json["has_more"], true);
 }
 
-#[sqlx::test(migrations = "./migrations")]
-async fn get_submissions_limit_clamped(pool: PgPool) {
-    seed_form(&pool).await;
-    let blob = valid_ec01_blob();
-    insert_submission(&pool, "bob.testnet", &blob).await;
+/// Insert `count` submissions one second apart (oldest first) so ordering is deterministic.
+async fn insert_spaced_submissions(pool: &PgPool, count: i32) {
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    for i in 0..count {
+        sqlx::query(
+            "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at) \
+             VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))",
+        )
+        .bind(Uuid::new_v4())
+        .bind(form_id)
+        .bind(format!("user{}.testnet", i))
+        .bind(valid_ec01_blob())
+        .bind(i as f64)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
 
-    let app = test_app(pool);
-    // limit=0 should be clamped to 1
+async fn get_submissions_json(app: &axum::Router, query: &str) -> (StatusCode, Value) {
     let req = Request::builder()
-        .uri(format!(
-            "/v1/forms/{}/submissions?limit=0",
-            TEST_FORM_ID
-        ))
+        .uri(format!("/v1/forms/{}/submissions{}", TEST_FORM_ID, query))
         .header("API-Secret", TEST_API_SECRET)
         .body(Body::empty())
         .unwrap();
-    let resp = app.oneshot(req).await.unwrap();
-    assert_eq!(resp.status(), StatusCode::OK);
-    let json = body_json(resp).await;
-    assert_eq!(json["submissions"].as_array().unwrap().len(), 1);
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
 }
 
 #[sqlx::test(migrations = "./migrations")]
-async fn get_submissions_desc_sort_order(pool: PgPool) {
+async fn get_submissions_cursor_walk(pool: PgPool) {
     seed_form(&pool).await;
-    let blob = valid_ec01_blob();
-    // Insert with slight delay to ensure ordering
-    insert_submission(&pool, "first.testnet", &blob).await;
-    // Use a direct insert with explicit timestamps to guarantee order
-    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
-    sqlx::query(
-        "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at) \
-         VALUES ($1, $2, 'second.testnet', $3, NOW() + interval '1 second')",
-    )
-    .bind(Uuid::new_v4())
-    .bind(form_id)
-    .bind(&blob)
-    .execute(&pool)
-    .await
-    .unwrap();
-
+    insert_spaced_submissions(&pool, 5).await;
     let app = test_app(pool);
-    let req = Request::builder()
-        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
-        .header("API-Secret", TEST_API_SECRET)
-        .body(Body::empty())
-        .unwrap();
-    let resp = app.oneshot(req).await.unwrap();
-    let json = body_json(resp).await;
-    let subs = json["submissions"].as_array().unwrap();
-    assert_eq!(subs.len(), 2);
-    // DESC order: second should come first
-    assert_eq!(subs[0]["submitter_id"], "second.testnet");
-    assert_eq!(subs[1]["submitter_id"], "first.testnet");
-}
 
-// ==================== POST /v1/submissions ====================
+    let (status, first) = get_submissions_json(&app, "?limit=2&with_total=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["total_count"], 5);
+    assert_eq!(first["has_more"], true);
+    assert_eq!(first["submissions"][0]["submitter_id"], "user4.testnet");
 
-fn post_submission(form_id: &str, submitter: &str, blob: &str) -> Request<Body> {
-    let body = serde_json::json!({
-        "form_id": form_id,
-        "submitter_id": submitter,
-        "encrypted_blob": blob,
-    });
-    Request::builder()
-        .method("POST")
-        .uri("/v1/submissions")
-        .header("Content-Type", "application/json")
-        .header("API-Secret", TEST_API_SECRET)
-        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+    let mut seen: Vec<String> = first["submissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["submitter_id"].as_str().unwrap().to_string())
+        .collect();
+    let mut cursor = first["next_cursor"].as_str().unwrap().to_string();
+    loop {
+        let (status, page) =
+            get_submissions_json(&app, &format!("?limit=2&with_total=true&cursor={}", cursor)).await;
+        assert_eq!(status, StatusCode::OK);
+        // Totals are only computed on the first page
+        assert!(page.get("total_count").is_none());
+        for s in page["submissions"].as_array().unwrap() {
+            seen.push(s["submitter_id"].as_str().unwrap().to_string());
+        }
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = next.to_string(),
+            None => {
+                assert_eq!(page["has_more"], false);
+                break;
+            }
+        }
+    }
+    let expected: Vec<String> = (0..5).rev().map(|i| format!("user{}.testnet", i)).collect();
+    assert_eq!(seen, expected);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_rejects_oversized_page(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_spaced_submissions(&pool, 5).await;
+    // Room for two of these small submissions per page
+    let state = AppState {
+        read_pool: pool.clone(),
+        pool,
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: 1000,
+    };
+    let app = test_app_with_state(state);
+
+    let (status, body) = get_submissions_json(&app, "").await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    let error = body["error"].as_str().unwrap();
+    assert!(error.contains("5 submissions"), "{}", error);
+    assert!(error.contains("limit=2"), "{}", error);
+
+    let (status, page) = get_submissions_json(&app, "?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["submissions"].as_array().unwrap().len(), 2);
+    assert_eq!(page["has_more"], true);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_ascending_cursor_walk(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_spaced_submissions(&pool, 5).await;
+    let app = test_app(pool);
+
+    let mut seen = Vec::new();
+    let mut query = "?order=asc&limit=2".to_string();
+    loop {
+        let (status, page) = get_submissions_json(&app, &query).await;
+        assert_eq!(status, StatusCode::OK);
+        for s in page["submissions"].as_array().unwrap() {
+            seen.push(s["submitter_id"].as_str().unwrap().to_string());
+        }
+        match page["next_cursor"].as_str() {
+            Some(next) => query = format!("?order=asc&limit=2&cursor={}", next),
+            None => break,
+        }
+    }
+    let expected: Vec<String> = (0..5).map(|i| format!("user{}.testnet", i)).collect();
+    assert_eq!(seen, expected);
+
+    let (_, page) = get_submissions_json(&app, "?order=asc&offset=3").await;
+    assert_eq!(page["submissions"][0]["submitter_id"], "user3.testnet");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_since(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_spaced_submissions(&pool, 5).await;
+    let app = test_app(pool);
+
+    let (_, all) = get_submissions_json(&app, "?order=asc").await;
+    let since = all["submissions"][1]["submitted_at"].as_str().unwrap().to_string();
+
+    // Strictly after: user1's own submission is excluded
+    let (status, page) =
+        get_submissions_json(&app, &format!("?order=asc&with_total=true&since={}", since)).await;
+    assert_eq!(status, StatusCode::OK);
+    let seen: Vec<&str> = page["submissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["submitter_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(seen, ["user2.testnet", "user3.testnet", "user4.testnet"]);
+    assert_eq!(page["total_count"], 3);
+
+    let (_, page) = get_submissions_json(&app, &format!("?limit=1&since={}", since)).await;
+    assert_eq!(page["submissions"][0]["submitter_id"], "user4.testnet");
+    assert_eq!(page["has_more"], true);
+
+    let (_, page) = get_submissions_json(&app, &format!("?offset=10&with_total=true&since={}", since)).await;
+    assert_eq!(page["total_count"], 3);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_invalid_since(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, json) = get_submissions_json(&app, "?since=yesterday").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "since must be an RFC 3339 timestamp");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_invalid_order(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, json) = get_submissions_json(&app, "?order=submitted_at;DROP%20TABLE%20forms").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "order must be 'asc' or 'desc'");
+    let (status, _) = get_submissions_json(&app, "?order=ASC").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_invalid_cursor(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, _) = get_submissions_json(&app, "?cursor=garbage").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let cursor = format!("1:{}", Uuid::new_v4());
+    let (status, json) = get_submissions_json(&app, &format!("?cursor={}&offset=5", cursor)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("cannot be combined"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_total_past_end(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_spaced_submissions(&pool, 2).await;
+    let app = test_app(pool);
+    let (status, json) = get_submissions_json(&app, "?offset=10&with_total=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["submissions"].as_array().unwrap().len(), 0);
+    assert_eq!(json["total_count"], 2);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_limit_clamped(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    insert_submission(&pool, "bob.testnet", &blob).await;
+
+    let app = test_app(pool);
+    // limit=0 should be clamped to 1
+    let req = Request::builder()
+        .uri(format!(
+            "/v1/forms/{}/submissions?limit=0",
+            TEST_FORM_ID
+        ))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["submissions"].as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submissions_desc_sort_order(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    // Insert with slight delay to ensure ordering
+    insert_submission(&pool, "first.testnet", &blob).await;
+    // Use a direct insert with explicit timestamps to guarantee order
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    sqlx::query(
+        "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at) \
+         VALUES ($1, $2, 'second.testnet', $3, NOW() + interval '1 second')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(form_id)
+    .bind(&blob)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = test_app(pool);
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    let json = body_json(resp).await;
+    let subs = json["submissions"].as_array().unwrap();
+    assert_eq!(subs.len(), 2);
+    // DESC order: second should come first
+    assert_eq!(subs[0]["submitter_id"], "second.testnet");
+    assert_eq!(subs[1]["submitter_id"], "first.testnet");
+}
+
+// ==================== GET /v1/forms/:id/submissions/:submitter_id ====================
+
+fn get_one_submission(form_id: &str, submitter: &str) -> Request<Body> {
+    Request::builder()
+        .uri(format!("/v1/forms/{}/submissions/{}", form_id, submitter))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submission_by_submitter(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    insert_submission(&pool, "bob.testnet", &blob).await;
+    insert_submission(&pool, "carol.testnet", &blob).await;
+    let app = test_app(pool);
+
+    let resp = app.clone().oneshot(get_one_submission(TEST_FORM_ID, "bob.testnet")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["submitter_id"], "bob.testnet");
+    assert_eq!(json["encrypted_blob"], blob);
+    assert!(json["submitted_at"].as_str().unwrap().ends_with('Z'));
+
+    let resp = app.clone().oneshot(get_one_submission(TEST_FORM_ID, "dave.testnet")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = app.clone().oneshot(get_one_submission(TEST_FORM_ID, "BAD!")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions/bob.testnet", TEST_FORM_ID))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== GET /v1/submitters/:submitter_id/submissions ====================
+
+async fn get_submitter_json(app: &axum::Router, submitter: &str, query: &str) -> (StatusCode, Value) {
+    let req = Request::builder()
+        .uri(format!("/v1/submitters/{}/submissions{}", submitter, query))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn get_submitter_submissions_spans_forms(pool: PgPool) {
+    seed_form(&pool).await;
+    let other_form = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO forms (id, creator_id, title, questions, created_at) \
+         VALUES ($1, 'alice.testnet', 'Other Form', '[]', NOW())",
+    )
+    .bind(other_form)
+    .execute(&pool)
+    .await
+    .unwrap();
+    for (form_id, submitter, secs) in [
+        (Uuid::parse_str(TEST_FORM_ID).unwrap(), "bob.testnet", 0.0),
+        (other_form, "bob.testnet", 1.0),
+        (other_form, "carol.testnet", 2.0),
+    ] {
+        sqlx::query(
+            "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at) \
+             VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))",
+        )
+        .bind(Uuid::new_v4())
+        .bind(form_id)
+        .bind(submitter)
+        .bind(valid_ec01_blob())
+        .bind(secs)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let app = test_app(pool);
+
+    let (status, first) = get_submitter_json(&app, "bob.testnet", "?limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["submissions"][0]["form_id"], other_form.to_string());
+    assert!(first["submissions"][0].get("encrypted_blob").is_none());
+    assert_eq!(first["has_more"], true);
+
+    let cursor = first["next_cursor"].as_str().unwrap();
+    let (_, second) = get_submitter_json(&app, "bob.testnet", &format!("?limit=1&cursor={}", cursor)).await;
+    assert_eq!(second["submissions"][0]["form_id"], TEST_FORM_ID);
+    assert_eq!(second["has_more"], false);
+    assert!(second.get("next_cursor").is_none());
+
+    let (_, none) = get_submitter_json(&app, "dave.testnet", "").await;
+    assert_eq!(none["submissions"].as_array().unwrap().len(), 0);
+
+    let (status, _) = get_submitter_json(&app, "BAD!", "").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_submitter_json(&app, "bob.testnet", "?cursor=garbage").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let req = Request::builder()
+        .uri("/v1/submitters/bob.testnet/submissions")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== GET /v1/forms/:id/submissions/:submitter_id/exists ====================
+
+fn exists_request(submitter: &str, auth: (&str, &str)) -> Request<Body> {
+    with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/submissions/{}/exists", TEST_FORM_ID, submitter))
+            .header(auth.0, auth.1)
+            .body(Body::empty())
+            .unwrap(),
+    )
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_exists_with_api_secret(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_submission(&pool, "bob.testnet", &valid_ec01_blob()).await;
+    let app = test_app(pool);
+
+    for (submitter, expected) in [("bob.testnet", true), ("carol.testnet", false)] {
+        let resp = app.clone().oneshot(exists_request(submitter, ("API-Secret", TEST_API_SECRET))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = body_json(resp).await;
+        assert_eq!(json, serde_json::json!({ "submitted": expected }));
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_exists_requires_bound_token(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_submission(&pool, "bob.testnet", &valid_ec01_blob()).await;
+    let app = test_app(pool);
+
+    let (_, bound) = mint_token(&app, serde_json::json!({ "form_id": TEST_FORM_ID, "submitter_id": "bob.testnet" })).await;
+    let bound = bound["token"].as_str().unwrap();
+    let resp = app.clone().oneshot(exists_request("bob.testnet", ("Submission-Token", bound))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["submitted"], true);
+
+    // A token bound to bob cannot probe other accounts
+    let resp = app.clone().oneshot(exists_request("carol.testnet", ("Submission-Token", bound))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Unbound tokens cannot probe at all
+    let (_, unbound) = mint_token(&app, serde_json::json!({ "form_id": TEST_FORM_ID })).await;
+    let resp = app
+        .clone()
+        .oneshot(exists_request("bob.testnet", ("Submission-Token", unbound["token"].as_str().unwrap())))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(with_connect_info(
+            Request::builder()
+                .uri(format!("/v1/forms/{}/submissions/bob.testnet/exists", TEST_FORM_ID))
+                .body(Body::empty())
+                .unwrap(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== POST /v1/submissions ====================
+
+fn post_submission(form_id: &str, submitter: &str, blob: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "form_id": form_id,
+        "submitter_id": submitter,
+        "encrypted_blob": blob,
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/submissions")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
         .unwrap()
 }
 
@@ -345,6 +1095,72 @@ async fn create_submission_happy_path(pool: PgPool) {
     assert!(json["id"].as_str().is_some());
     // Verify it's a valid UUID
     Uuid::parse_str(json["id"].as_str().unwrap()).unwrap();
+    // The receipt hash is of the stored (normalized) blob
+    let expected_hash = hex::encode(Sha256::digest(valid_ec01_blob().as_bytes()));
+    assert_eq!(json["blob_hash"], expected_hash);
+}
+
+/// End-to-end through the HTTP API only: create, read back, then the 409 and 413
+/// rejections, which must leave the stored submission untouched.
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_round_trip(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let blob = valid_ec01_blob();
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let id = body_json(resp).await["id"].as_str().unwrap().to_string();
+
+    let (status, json) = get_submissions_json(&app, "").await;
+    assert_eq!(status, StatusCode::OK);
+    let submissions = json["submissions"].as_array().unwrap();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0]["id"], id);
+    assert_eq!(submissions[0]["submitter_id"], "bob.testnet");
+    assert_eq!(submissions[0]["encrypted_blob"], blob);
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let oversized = format!("{}{}", blob, "00".repeat(DEFAULT_MAX_BLOB_SIZE));
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &oversized)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(
+        body_json(resp).await["error"],
+        format!(
+            "encrypted_blob too long: {} characters (max: {} characters for {} bytes, 2 hex characters per byte)",
+            oversized.len(),
+            DEFAULT_MAX_BLOB_SIZE * 2,
+            DEFAULT_MAX_BLOB_SIZE
+        )
+    );
+
+    let (_, json) = get_submissions_json(&app, "").await;
+    let submissions = json["submissions"].as_array().unwrap();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0]["encrypted_blob"], blob);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn per_question_blob_round_trip(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (q1, q2) = (unique_ec01_blob(), unique_ec01_blob());
+    // Keys out of order and uppercase hex: stored sorted and lowercased
+    let blob = format!(r#"{{"q2":"{}","q1":"{}"}}"#, q2.to_uppercase(), q1);
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let (_, json) = get_submissions_json(&app, "").await;
+    let stored = json["submissions"][0]["encrypted_blob"].as_str().unwrap();
+    assert_eq!(stored, format!(r#"{{"q1":"{}","q2":"{}"}}"#, q1, q2));
+
+    let bad_field = format!(r#"{{"q1":"{}","q2":"00"}}"#, q1);
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &bad_field)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("field 'q2'"));
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -448,6 +1264,37 @@ async fn create_submission_blob_too_large(pool: PgPool) {
     assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn create_submission_respects_configured_blob_size(pool: PgPool) {
+    seed_form(&pool).await;
+    let state = AppState {
+        read_pool: pool.clone(),
+        pool,
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: 1024,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    // 1 KB + 1 byte: under the body limit, so the handler's message is returned
+    let mut blob = valid_ec01_blob();
+    while blob.len() <= 2 * 1024 {
+        blob.push_str("aa");
+    }
+    let resp = app.oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let json = body_json(resp).await;
+    assert!(json["error"].as_str().unwrap().contains("1024 bytes"));
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn create_submission_odd_hex(pool: PgPool) {
     seed_form(&pool).await;
@@ -475,12 +1322,50 @@ async fn create_submission_blob_too_short(pool: PgPool) {
 }
 
 #[sqlx::test(migrations = "./migrations")]
-async fn create_submission_non_hex_chars(pool: PgPool) {
+async fn create_submission_rejects_empty_blob(pool: PgPool) {
     seed_form(&pool).await;
     let app = test_app(pool);
-    let mut blob = valid_ec01_blob();
-    // Replace last 2 chars with non-hex
-    blob.replace_range(blob.len() - 2.., "zz");
+    for blob in ["", "  \n"] {
+        let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", blob)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(resp).await["error"], "encrypted_blob must not be empty");
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_submission_malformed_body_uses_error_envelope(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let post = |content_type: &str, body: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/submissions")
+            .header("Content-Type", content_type)
+            .header("API-Secret", TEST_API_SECRET)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let missing_field = serde_json::json!({ "form_id": TEST_FORM_ID, "submitter_id": "bob.testnet" }).to_string();
+    for (content_type, body, status, message) in [
+        ("application/json", missing_field.as_str(), StatusCode::BAD_REQUEST, "missing field `encrypted_blob`"),
+        ("application/json", "{\"form_id\":", StatusCode::BAD_REQUEST, "EOF while parsing"),
+        ("text/plain", "{}", StatusCode::UNSUPPORTED_MEDIA_TYPE, "Content-Type"),
+    ] {
+        let resp = app.clone().oneshot(post(content_type, body)).await.unwrap();
+        assert_eq!(resp.status(), status, "{}", body);
+        let json = body_json(resp).await;
+        assert!(json["error"].as_str().unwrap().contains(message), "{}", json);
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_submission_non_hex_chars(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let mut blob = valid_ec01_blob();
+    // Replace last 2 chars with non-hex
+    blob.replace_range(blob.len() - 2.., "zz");
     let req = post_submission(TEST_FORM_ID, "bob.testnet", &blob);
     let resp = app.oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
@@ -516,6 +1401,24 @@ async fn create_submission_duplicate_conflict(pool: PgPool) {
     assert!(json["error"].as_str().unwrap().contains("already submitted"));
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn create_submission_replayed_blob_conflict(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    let app = test_app(pool.clone());
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Same ciphertext under a different account (and in uppercase hex) is a replay
+    let req = post_submission(TEST_FORM_ID, "carol.testnet", &blob.to_uppercase());
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let json = body_json(resp).await;
+    assert!(json["error"].as_str().unwrap().contains("already been received"));
+    assert_eq!(count_submissions(&pool).await, 1);
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn create_submission_hex_normalized_to_lowercase(pool: PgPool) {
     seed_form(&pool).await;
@@ -536,33 +1439,1855 @@ async fn create_submission_hex_normalized_to_lowercase(pool: PgPool) {
     assert_eq!(stored.0, stored.0.to_ascii_lowercase());
 }
 
-// ==================== E2E: submit then read ====================
+// ==================== PUT /v1/submissions ====================
+
+fn sha256_hex(data: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data.as_bytes()))
+}
+
+fn put_submission(submitter: &str, blob: &str, previous_hash: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "form_id": TEST_FORM_ID,
+        "submitter_id": submitter,
+        "encrypted_blob": blob,
+        "previous_blob_hash": previous_hash,
+    });
+    Request::builder()
+        .method("PUT")
+        .uri("/v1/submissions")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
 
 #[sqlx::test(migrations = "./migrations")]
-async fn e2e_submit_then_read(pool: PgPool) {
+async fn update_submission_replaces_blob_idempotently(pool: PgPool) {
+    seed_form(&pool).await;
+    let old_blob = valid_ec01_blob();
+    insert_submission(&pool, "bob.testnet", &old_blob).await;
+    let app = test_app(pool.clone());
+    let new_blob = unique_ec01_blob();
+
+    for _ in 0..2 {
+        let resp = app.clone().oneshot(put_submission("bob.testnet", &new_blob, &sha256_hex(&old_blob))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let (stored, hash): (String, Option<String>) = sqlx::query_as(
+        "SELECT encrypted_blob, blob_hash FROM submissions WHERE submitter_id = 'bob.testnet'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(stored, new_blob);
+    assert_eq!(hash, Some(sha256_hex(&new_blob)));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn update_submission_conflicts_and_not_found(pool: PgPool) {
+    seed_form(&pool).await;
+    let stored = valid_ec01_blob();
+    insert_submission(&pool, "bob.testnet", &stored).await;
+    let app = test_app(pool.clone());
+
+    // Stale compare-and-swap guard
+    let resp = app.clone().oneshot(put_submission("bob.testnet", &unique_ec01_blob(), &sha256_hex("other"))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("changed since it was read"));
+
+    // Replaying another submission's ciphertext
+    let taken = unique_ec01_blob();
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &taken)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(put_submission("bob.testnet", &taken, &sha256_hex(&stored))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("already been received"));
+
+    let resp = app.oneshot(put_submission("dave.testnet", &unique_ec01_blob(), &sha256_hex(&stored))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ==================== POST /v1/submissions/batch ====================
+
+fn post_batch(items: Value, query: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/v1/submissions/batch{}", query))
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&items).unwrap()))
+        .unwrap()
+}
+
+fn batch_item(form_id: &str, submitter: &str, blob: &str) -> Value {
+    serde_json::json!({
+        "form_id": form_id,
+        "submitter_id": submitter,
+        "encrypted_blob": blob,
+    })
+}
+
+async fn count_submissions(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM submissions")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_mixed_results(pool: PgPool) {
     seed_form(&pool).await;
     let blob = valid_ec01_blob();
+    insert_submission(&pool, "carol.testnet", &blob).await;
 
-    // Submit via POST
     let app = test_app(pool.clone());
-    let req = post_submission(TEST_FORM_ID, "bob.testnet", &blob);
+    let items = serde_json::json!([
+        batch_item(TEST_FORM_ID, "bob.testnet", &blob),
+        batch_item(TEST_FORM_ID, "carol.testnet", &blob),
+        batch_item(TEST_FORM_ID, "dave.testnet", "zz"),
+        batch_item(&Uuid::new_v4().to_string(), "erin.testnet", &blob),
+    ]);
+    let resp = app.oneshot(post_batch(items, "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["inserted"], 1);
+    assert_eq!(json["failed"], 3);
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results[0]["status"], 201);
+    Uuid::parse_str(results[0]["id"].as_str().unwrap()).unwrap();
+    assert_eq!(results[1]["status"], 409);
+    assert_eq!(results[2]["status"], 400);
+    assert_eq!(results[3]["status"], 404);
+    assert_eq!(count_submissions(&pool).await, 2);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_duplicate_within_batch(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    let app = test_app(pool.clone());
+    let items = serde_json::json!([
+        batch_item(TEST_FORM_ID, "bob.testnet", &blob),
+        batch_item(TEST_FORM_ID, "bob.testnet", &blob),
+    ]);
+    let resp = app.oneshot(post_batch(items, "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["inserted"], 1);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(count_submissions(&pool).await, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_replayed_blob_across_submitters(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    let app = test_app(pool.clone());
+    let items = serde_json::json!([
+        batch_item(TEST_FORM_ID, "bob.testnet", &blob),
+        batch_item(TEST_FORM_ID, "carol.testnet", &blob),
+    ]);
+    let resp = app.oneshot(post_batch(items, "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["inserted"], 1);
+    assert_eq!(json["results"][1]["status"], 409);
+    assert!(json["results"][1]["error"].as_str().unwrap().contains("already been received"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_atomic_rolls_back(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+    let app = test_app(pool.clone());
+    let items = serde_json::json!([
+        batch_item(TEST_FORM_ID, "bob.testnet", &blob),
+        batch_item(TEST_FORM_ID, "carol.testnet", "not-hex"),
+    ]);
+    let resp = app.oneshot(post_batch(items, "?atomic=true")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let json = body_json(resp).await;
+    assert_eq!(json["inserted"], 0);
+    assert!(json["results"][0]["id"].is_null());
+    assert_eq!(count_submissions(&pool).await, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_rejects_empty_and_oversized(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let resp = app.clone().oneshot(post_batch(serde_json::json!([]), "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let blob = valid_ec01_blob();
+    let items: Vec<Value> = (0..=db_api::MAX_BATCH_SIZE)
+        .map(|i| batch_item(TEST_FORM_ID, &format!("user{}.testnet", i), &blob))
+        .collect();
+    let resp = app.oneshot(post_batch(Value::Array(items), "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_create_no_api_secret(pool: PgPool) {
+    let app = test_app(pool);
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/submissions/batch")
+        .header("Content-Type", "application/json")
+        .body(Body::from("[]"))
+        .unwrap();
     let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== Submission tokens ====================
+
+async fn mint_token(app: &axum::Router, body: Value) -> (StatusCode, Value) {
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/submission-tokens")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+fn post_submission_with_token(form_id: &str, submitter: &str, blob: &str, token: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "form_id": form_id,
+        "submitter_id": submitter,
+        "encrypted_blob": blob,
+    });
+    with_connect_info(
+        Request::builder()
+            .method("POST")
+            .uri("/v1/submissions")
+            .header("Content-Type", "application/json")
+            .header("Submission-Token", token)
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap(),
+    )
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_token_allows_direct_submit(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    let (status, json) = mint_token(&app, serde_json::json!({ "form_id": TEST_FORM_ID })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(json["expires_at"].as_str().unwrap().ends_with('Z'));
+    let token = json["token"].as_str().unwrap();
+
+    let req = post_submission_with_token(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob(), token);
+    let resp = app.clone().oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(count_submissions(&pool).await, 1);
 
-    // Read via GET
-    let app = test_app(pool);
+    // Reads still require the API secret
     let req = Request::builder()
         .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
-        .header("API-Secret", TEST_API_SECRET)
+        .header("Submission-Token", token)
         .body(Body::empty())
         .unwrap();
     let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_token_scope_is_enforced(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    let (_, json) = mint_token(
+        &app,
+        serde_json::json!({ "form_id": TEST_FORM_ID, "submitter_id": "bob.testnet" }),
+    )
+    .await;
+    let token = json["token"].as_str().unwrap();
+
+    let req = post_submission_with_token(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob(), token);
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let other_form = Uuid::new_v4().to_string();
+    let req = post_submission_with_token(&other_form, "bob.testnet", &unique_ec01_blob(), token);
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let forged = format!("{}0", token);
+    let req = post_submission_with_token(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob(), &forged);
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(count_submissions(&pool).await, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_token_mint_validation(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, _) = mint_token(&app, serde_json::json!({ "form_id": TEST_FORM_ID, "ttl_secs": 0 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = mint_token(&app, serde_json::json!({ "form_id": TEST_FORM_ID, "ttl_secs": 999_999 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = mint_token(&app, serde_json::json!({ "form_id": Uuid::new_v4().to_string() })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Minting itself requires the API secret
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/submission-tokens")
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::json!({ "form_id": TEST_FORM_ID }).to_string()))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== POST /v1/forms, GET /v1/creators/:id/forms ====================
+
+fn post_form(body: Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/forms")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+fn list_forms(creator_id: &str, query: &str) -> Request<Body> {
+    Request::builder()
+        .uri(format!("/v1/creators/{}/forms{}", creator_id, query))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn create_tagged_form(app: &axum::Router, title: &str, tags: &[&str]) -> Value {
+    let resp = app
+        .clone()
+        .oneshot(post_form(serde_json::json!({
+            "creator_id": "dana.testnet",
+            "title": title,
+            "questions": [{"id": "q1", "text": "Why?", "type": "text"}],
+            "tags": tags,
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    body_json(resp).await
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_returns_tags(pool: PgPool) {
+    let app = test_app(pool);
+    let json = create_tagged_form(&app, "Onboarding", &["research", "q3"]).await;
+    assert_eq!(json["creator_id"], "dana.testnet");
+    assert_eq!(json["tags"], serde_json::json!(["research", "q3"]));
+    assert_eq!(json["strict_answers"], false);
+
+    let resp = app
+        .oneshot(with_connect_info(
+            Request::builder()
+                .uri(format!("/v1/forms/{}", json["id"].as_str().unwrap()))
+                .body(Body::empty())
+                .unwrap(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["tags"], serde_json::json!(["research", "q3"]));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_rejects_invalid_tags(pool: PgPool) {
+    let app = test_app(pool);
+    let resp = app
+        .oneshot(post_form(serde_json::json!({
+            "creator_id": "dana.testnet",
+            "title": "Dup",
+            "questions": [],
+            "tags": ["a", "a"],
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("duplicate tag"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn new_forms_get_a_random_derivation_salt(pool: PgPool) {
+    let app = test_app(pool.clone());
+    let first = create_tagged_form(&app, "First", &[]).await;
+    let second = create_tagged_form(&app, "Second", &[]).await;
+    // Not even the creator's own form responses carry the salt
+    assert!(first.get("derivation_salt").is_none());
+
+    let salt_request = |id: &str, secret: Option<&str>| {
+        let mut builder = Request::builder().uri(format!("/v1/forms/{}/derivation-salt", id));
+        if let Some(secret) = secret {
+            builder = builder.header("API-Secret", secret);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+    let id = first["id"].as_str().unwrap();
+
+    // The public form never carries the salt; only API-secret holders can read it
+    let public = with_connect_info(Request::builder().uri(format!("/v1/forms/{}", id)).body(Body::empty()).unwrap());
+    let resp = app.clone().oneshot(public).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_json(resp).await.get("derivation_salt").is_none());
+    let resp = app.clone().oneshot(salt_request(id, None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = app.clone().oneshot(salt_request(id, Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let salt = body_json(resp).await["derivation_salt"].clone();
+    assert!(db_api::is_derivation_salt(salt.as_str().unwrap()), "{}", salt);
+    let resp = app.clone().oneshot(salt_request(second["id"].as_str().unwrap(), Some(TEST_API_SECRET))).await.unwrap();
+    assert_ne!(body_json(resp).await["derivation_salt"], salt);
+
+    // Forms from before the column keep the original derivation: a null salt
+    sqlx::query("UPDATE forms SET derivation_salt = NULL WHERE id = $1")
+        .bind(Uuid::parse_str(id).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let resp = app.oneshot(salt_request(id, Some(TEST_API_SECRET))).await.unwrap();
+    assert!(body_json(resp).await["derivation_salt"].is_null());
+}
+
+fn put_public_key(form_id: &str, public_key: &str) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/v1/forms/{}/public-key", form_id))
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&serde_json::json!({ "public_key": public_key })).unwrap()))
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_public_key_registered_and_served(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let get_key = |form_id: &str| {
+        with_connect_info(
+            Request::builder().uri(format!("/v1/forms/{}/public-key", form_id)).body(Body::empty()).unwrap(),
+        )
+    };
+
+    let resp = app.clone().oneshot(get_key(TEST_FORM_ID)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_json(resp).await["error"], "Form public key not registered yet");
+
+    let key = "02257731F1D53B68B0C8E8602250746131B1B037556343B4F666C9AC753E5CC4EA";
+    let resp = app.clone().oneshot(put_public_key(TEST_FORM_ID, key)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["public_key"], key.to_ascii_lowercase());
+
+    let resp = app.clone().oneshot(get_key(TEST_FORM_ID)).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let json = body_json(resp).await;
-    let subs = json["submissions"].as_array().unwrap();
-    assert_eq!(subs.len(), 1);
-    assert_eq!(subs[0]["submitter_id"], "bob.testnet");
-    assert_eq!(subs[0]["encrypted_blob"], blob);
+    assert_eq!(json["form_id"], TEST_FORM_ID);
+    assert_eq!(json["public_key"], key.to_ascii_lowercase());
+    let resp = app
+        .clone()
+        .oneshot(with_connect_info(
+            Request::builder().uri(format!("/v1/forms/{}", TEST_FORM_ID)).body(Body::empty()).unwrap(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(body_json(resp).await["public_key"], key.to_ascii_lowercase());
+
+    for bad in ["04aa", &format!("04{}", "a".repeat(64)), &format!("02{}", "g".repeat(64))] {
+        let resp = app.clone().oneshot(put_public_key(TEST_FORM_ID, bad)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", bad);
+    }
+    let resp = app.clone().oneshot(put_public_key(&Uuid::new_v4().to_string(), key)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let resp = app.clone().oneshot(get_key(&Uuid::new_v4().to_string())).await.unwrap();
+    assert_eq!(body_json(resp).await["error"], "Form not found");
+
+    let mut unauthenticated = put_public_key(TEST_FORM_ID, key);
+    unauthenticated.headers_mut().remove("API-Secret");
+    assert_eq!(app.oneshot(unauthenticated).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn list_creator_forms_filters_by_tag(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    create_tagged_form(&app, "First", &["research"]).await;
+    create_tagged_form(&app, "Second", &["research", "internal"]).await;
+    create_tagged_form(&app, "Third", &[]).await;
+
+    let resp = app.clone().oneshot(list_forms("dana.testnet", "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let titles: Vec<&str> = json["forms"].as_array().unwrap().iter().map(|f| f["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, ["Third", "Second", "First"]);
+
+    let resp = app.clone().oneshot(list_forms("dana.testnet", "?tag=research")).await.unwrap();
+    let json = body_json(resp).await;
+    let titles: Vec<&str> = json["forms"].as_array().unwrap().iter().map(|f| f["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, ["Second", "First"]);
+
+    let resp = app.clone().oneshot(list_forms("dana.testnet", "?tag=Research")).await.unwrap();
+    assert_eq!(body_json(resp).await["forms"], serde_json::json!([]));
+
+    // Other creators' forms (the seeded alice.testnet form) are never listed
+    let resp = app.oneshot(list_forms("alice.testnet", "?tag=research")).await.unwrap();
+    assert_eq!(body_json(resp).await["forms"], serde_json::json!([]));
+}
+
+async fn create_form_json(app: &axum::Router, body: Value) -> (StatusCode, Value) {
+    let resp = app.clone().oneshot(post_form(body)).await.unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_enforces_per_creator_quota(pool: PgPool) {
+    let state = AppState {
+        read_pool: pool.clone(),
+        pool,
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(2),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+    let form = |creator: &str| serde_json::json!({ "creator_id": creator, "title": "Quota", "questions": [] });
+
+    for _ in 0..2 {
+        let (status, _) = create_form_json(&app, form("dana.testnet")).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    let (status, json) = create_form_json(&app, form("dana.testnet")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(json["error"], "creator_id already owns the maximum of 2 forms");
+
+    // The cap is per creator
+    let (status, _) = create_form_json(&app, form("erin.testnet")).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_extends_templates(pool: PgPool) {
+    let app = test_app(pool);
+    let (status, demographics) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Demographics",
+        "is_template": true,
+        "questions": [{"id": "age", "label": "Age"}, {"id": "country", "label": "Country"}],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(demographics["is_template"], true);
+    let (_, nps) = create_form_json(&app, serde_json::json!({
+        "creator_id": "erin.testnet",
+        "title": "NPS",
+        "is_template": true,
+        "questions": [{"id": "nps", "label": "Recommend?"}],
+    })).await;
+
+    let (status, form) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Onboarding",
+        "extends": [demographics["id"], nps["id"]],
+        "questions": [{"id": "age", "label": "Age bracket"}, {"id": "why", "label": "Why?"}],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(form["is_template"], false);
+    let ids: Vec<&str> = form["questions"].as_array().unwrap().iter().map(|q| q["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["age", "country", "nps", "why"]);
+    assert_eq!(form["questions"][0]["label"], "Age bracket");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_rejects_bad_extends(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let missing = Uuid::new_v4().to_string();
+    let cases = [
+        (serde_json::json!([TEST_FORM_ID]), "is not a template"),
+        (serde_json::json!([missing]), "not found"),
+        (serde_json::json!(["nope"]), "invalid template ID"),
+        (serde_json::json!([missing, missing]), "duplicate template ID"),
+    ];
+    for (extends, expected) in cases {
+        let (status, json) = create_form_json(&app, serde_json::json!({
+            "creator_id": "dana.testnet",
+            "title": "Bad",
+            "extends": extends,
+            "questions": [],
+        })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", expected);
+        assert!(json["error"].as_str().unwrap().contains(expected), "{}: {}", expected, json);
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_form_rejects_template_cycle(pool: PgPool) {
+    let app = test_app(pool.clone());
+    let (_, base) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Base",
+        "is_template": true,
+        "questions": [{"id": "age", "label": "Age"}],
+    })).await;
+    let (status, derived) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Derived",
+        "is_template": true,
+        "extends": [base["id"]],
+        "questions": [{"id": "nps", "label": "Recommend?"}],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let base_id = Uuid::parse_str(base["id"].as_str().unwrap()).unwrap();
+    let derived_id = Uuid::parse_str(derived["id"].as_str().unwrap()).unwrap();
+    let lineage: Vec<Uuid> = sqlx::query_scalar("SELECT extends FROM forms WHERE id = $1")
+        .bind(derived_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(lineage, [base_id]);
+
+    // Only a write outside create_form can close the loop
+    sqlx::query("UPDATE forms SET extends = $1 WHERE id = $2")
+        .bind(vec![derived_id])
+        .bind(base_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, json) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Loop",
+        "extends": [derived["id"]],
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("is part of a cycle"), "{}", json);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unlimited_policy_accepts_repeat_submissions(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, form) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Open feedback",
+        "submission_policy": "unlimited",
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(form["submission_policy"], "unlimited");
+    let form_id = form["id"].as_str().unwrap();
+
+    for _ in 0..2 {
+        let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+    // Replays are still rejected under either policy
+    let blob = unique_ec01_blob();
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &blob)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("already been received"));
+
+    // The seeded form keeps the default one-per-account policy
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert!(body_json(resp).await["error"].as_str().unwrap().contains("already submitted"));
+
+    let resp = app.clone().oneshot(get_one_submission(form_id, "bob.testnet")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["encrypted_blob"], blob);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn min_resubmit_interval_throttles_repeat_submissions(pool: PgPool) {
+    let app = test_app(pool.clone());
+    let (status, json) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Throttled",
+        "min_resubmit_interval_secs": 60,
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "min_resubmit_interval_secs requires submission_policy 'unlimited'");
+
+    let (status, form) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Throttled",
+        "submission_policy": "unlimited",
+        "min_resubmit_interval_secs": 3600,
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(form["min_resubmit_interval_secs"], 3600);
+    let form_id = form["id"].as_str().unwrap();
+
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let error = body_json(resp).await["error"].as_str().unwrap().to_string();
+    assert!(error.starts_with("Submitted too recently: this form accepts one submission per account every 3600 seconds"), "{}", error);
+
+    // Other accounts and drafts are not throttled
+    let resp = app.clone().oneshot(post_submission(form_id, "carol.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let draft = Request::builder()
+        .method("POST")
+        .uri("/v1/submissions")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&serde_json::json!({
+            "form_id": form_id,
+            "submitter_id": "bob.testnet",
+            "encrypted_blob": unique_ec01_blob(),
+            "is_draft": true,
+        })).unwrap()))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(draft).await.unwrap().status(), StatusCode::CREATED);
+
+    // Once the interval has passed, the account may submit again
+    sqlx::query("UPDATE submissions SET submitted_at = submitted_at - INTERVAL '2 hours' WHERE submitter_id = 'bob.testnet' AND NOT is_draft")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+/// Give the seeded form a one-hour min_resubmit_interval_secs.
+async fn throttle_seeded_form(pool: &PgPool) {
+    sqlx::query("UPDATE forms SET submission_policy = 'unlimited', min_resubmit_interval_secs = 3600 WHERE id = $1")
+        .bind(Uuid::parse_str(TEST_FORM_ID).unwrap())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_respects_min_resubmit_interval(pool: PgPool) {
+    seed_form(&pool).await;
+    throttle_seeded_form(&pool).await;
+    insert_submission(&pool, "bob.testnet", &unique_ec01_blob()).await;
+    let app = test_app(pool.clone());
+
+    let items = serde_json::json!([
+        batch_item(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob()),
+        batch_item(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob()),
+        batch_item(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob()),
+    ]);
+    let resp = app.clone().oneshot(post_batch(items, "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["inserted"], 1);
+    let statuses: Vec<u64> = json["results"].as_array().unwrap().iter().map(|r| r["status"].as_u64().unwrap()).collect();
+    assert_eq!(statuses, [429, 201, 429]);
+    assert!(json["results"][0]["error"].as_str().unwrap().starts_with("Submitted too recently"), "{}", json);
+    assert_eq!(count_submissions(&pool).await, 2);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn update_submission_respects_min_resubmit_interval(pool: PgPool) {
+    seed_form(&pool).await;
+    throttle_seeded_form(&pool).await;
+    let old_blob = unique_ec01_blob();
+    insert_submission(&pool, "bob.testnet", &old_blob).await;
+    let app = test_app(pool.clone());
+
+    let new_blob = unique_ec01_blob();
+    let resp = app.clone().oneshot(put_submission("bob.testnet", &new_blob, &sha256_hex(&old_blob))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let error = body_json(resp).await["error"].as_str().unwrap().to_string();
+    assert!(error.starts_with("Submitted too recently: this form accepts one submission per account every 3600 seconds"), "{}", error);
+
+    sqlx::query("UPDATE submissions SET submitted_at = submitted_at - INTERVAL '2 hours'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let resp = app.clone().oneshot(put_submission("bob.testnet", &new_blob, &sha256_hex(&old_blob))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Repeating a completed update stays a no-op inside the interval
+    sqlx::query("UPDATE submissions SET submitted_at = NOW()")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let resp = app.clone().oneshot(put_submission("bob.testnet", &new_blob, &sha256_hex(&old_blob))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+fn put_denied_submitters(form_id: &str, accounts: Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/v1/forms/{}/denied-submitters", form_id))
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&serde_json::json!({ "denied_submitters": accounts })).unwrap()))
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn denied_submitters_are_refused(pool: PgPool) {
+    let app = test_app(pool.clone());
+    let (status, form) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Moderated",
+        "denied_submitters": ["mallory.testnet"],
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(form["denied_submitters"], serde_json::json!(["mallory.testnet"]));
+    let form_id = form["id"].as_str().unwrap();
+
+    // The public form endpoint does not reveal who is blocked
+    let req = with_connect_info(Request::builder().uri(format!("/v1/forms/{}", form_id)).body(Body::empty()).unwrap());
+    let public = body_json(app.clone().oneshot(req).await.unwrap()).await;
+    assert!(public.get("denied_submitters").is_none(), "{}", public);
+
+    let resp = app.clone().oneshot(post_submission(form_id, "mallory.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_json(resp).await["error"], "This account is not allowed to submit to this form.");
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let items = serde_json::json!([
+        batch_item(form_id, "mallory.testnet", &unique_ec01_blob()),
+        batch_item(form_id, "carol.testnet", &unique_ec01_blob()),
+    ]);
+    let json = body_json(app.clone().oneshot(post_batch(items, "")).await.unwrap()).await;
+    assert_eq!(json["results"][0]["status"], 403);
+    assert_eq!(json["results"][1]["status"], 201);
+
+    // Replacing the list lifts the block
+    let resp = app.clone().oneshot(put_denied_submitters(form_id, serde_json::json!(["Not Valid"]))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.clone().oneshot(put_denied_submitters(&Uuid::new_v4().to_string(), serde_json::json!([]))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let resp = app.clone().oneshot(put_denied_submitters(form_id, serde_json::json!([]))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_json(resp).await.get("denied_submitters").is_none());
+    let resp = app.clone().oneshot(post_submission(form_id, "mallory.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+fn patch_form_state(form_id: &str, action: &str) -> Request<Body> {
+    Request::builder()
+        .method("PATCH")
+        .uri(format!("/v1/forms/{}/{}", form_id, action))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn paused_form_refuses_submissions_until_resumed(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_submission(&pool, "alice.testnet", &unique_ec01_blob()).await;
+    let app = test_app(pool);
+
+    let resp = app.clone().oneshot(patch_form_state(TEST_FORM_ID, "pause")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["paused"], true);
+
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::LOCKED);
+    assert_eq!(body_json(resp).await["error"], "This form is paused and is not accepting submissions right now.");
+    let json = body_json(app.clone().oneshot(post_batch(serde_json::json!([
+        batch_item(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob()),
+    ]), "")).await.unwrap()).await;
+    assert_eq!(json["results"][0]["status"], 423);
+
+    // Drafts and reads still work
+    let resp = app.clone().oneshot(post_draft("bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(get_one_submission(TEST_FORM_ID, "alice.testnet")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Pausing twice is harmless; resuming reopens the form
+    let resp = app.clone().oneshot(patch_form_state(TEST_FORM_ID, "pause")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app.clone().oneshot(patch_form_state(TEST_FORM_ID, "resume")).await.unwrap();
+    assert_eq!(body_json(resp).await["paused"], false);
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = app.clone().oneshot(patch_form_state(&Uuid::new_v4().to_string(), "pause")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn encryption_scheme_rejects_other_layouts(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let (status, form) = create_form_json(&app, serde_json::json!({
+        "creator_id": "dana.testnet",
+        "title": "Whole-blob only",
+        "encryption_scheme": "ec01",
+        "questions": [],
+    })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(form["encryption_scheme"], "ec01");
+    let form_id = form["id"].as_str().unwrap();
+    let per_question = format!(r#"{{"q1":"{}"}}"#, unique_ec01_blob());
+
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &per_question)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_json(resp).await["error"], "This form requires encryption_scheme 'ec01'");
+    let resp = app.clone().oneshot(post_submission(form_id, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let items = serde_json::json!([
+        batch_item(form_id, "carol.testnet", &per_question),
+        batch_item(form_id, "dave.testnet", &unique_ec01_blob()),
+    ]);
+    let json = body_json(app.clone().oneshot(post_batch(items, "")).await.unwrap()).await;
+    assert_eq!(json["results"][0]["status"], 400);
+    assert_eq!(json["results"][0]["error"], "This form requires encryption_scheme 'ec01'");
+    assert_eq!(json["results"][1]["status"], 201);
+
+    // The seeded form keeps the default, which accepts either layout
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &per_question)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = app.clone().oneshot(post_submission(&Uuid::new_v4().to_string(), "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+fn post_draft(submitter: &str, blob: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "form_id": TEST_FORM_ID,
+        "submitter_id": submitter,
+        "encrypted_blob": blob,
+        "is_draft": true,
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/submissions")
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+// ==================== Form archives ====================
+
+fn get_archive(form_id: &str) -> Request<Body> {
+    Request::builder()
+        .uri(format!("/v1/forms/{}/archive", form_id))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn import_archive(app: &axum::Router, query: &str, archive: &[u8]) -> (StatusCode, Value) {
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/v1/forms/import{}", query))
+        .header("Content-Type", "application/json")
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::from(archive.to_vec()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_archive_export_and_import(pool: PgPool) {
+    seed_form(&pool).await;
+    for submitter in ["alice.testnet", "bob.testnet", "carol.testnet"] {
+        insert_submission(&pool, submitter, &unique_ec01_blob()).await;
+    }
+    // insert_submission leaves blob_hash unset; hash like the migration backfill does
+    sqlx::query("UPDATE submissions SET blob_hash = encode(sha256(convert_to(encrypted_blob, 'UTF8')), 'hex')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let app = test_app(pool.clone());
+
+    let resp = app.clone().oneshot(get_archive(TEST_FORM_ID)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers()["content-type"].to_str().unwrap().starts_with("application/json"));
+    let archive = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: Value = serde_json::from_slice(&archive).unwrap();
+    assert_eq!(json["format"], "near-forms-archive");
+    assert_eq!(json["version"], 2);
+    assert_eq!(json["form"]["id"], TEST_FORM_ID);
+    assert_eq!(json["submissions"].as_array().unwrap().len(), 3);
+    assert_eq!(json["checksum"].as_str().unwrap().len(), 64);
+
+    // The form is still here
+    let (status, body) = import_archive(&app, "", &archive).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"].as_str().unwrap().contains("already exists"), "{}", body);
+
+    // A copy in the same database would replay the stored blobs
+    let (status, body) = import_archive(&app, "?new_id=true", &archive).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"].as_str().unwrap().contains("replay"), "{}", body);
+    let forms: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM forms").fetch_one(&pool).await.unwrap();
+    assert_eq!(forms, 1, "a failed import must not leave a form behind");
+
+    // Restore after the form is lost
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    sqlx::query("DELETE FROM submissions WHERE form_id = $1").bind(form_id).execute(&pool).await.unwrap();
+    sqlx::query("DELETE FROM forms WHERE id = $1").bind(form_id).execute(&pool).await.unwrap();
+    let (status, body) = import_archive(&app, "", &archive).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["form"]["id"], TEST_FORM_ID);
+    assert_eq!(body["imported_submissions"], 3);
+
+    let resp = app.clone().oneshot(get_archive(TEST_FORM_ID)).await.unwrap();
+    let again = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(again, archive, "re-export of an imported form is byte-identical");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_archive_keeps_question_history(pool: PgPool) {
+    seed_form(&pool).await;
+    let form_id = Uuid::parse_str(TEST_FORM_ID).unwrap();
+    let app = test_app(pool.clone());
+
+    // bob answers version 1; carol answers version 2, after an edit
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    sqlx::query("UPDATE forms SET questions = $2 WHERE id = $1")
+        .bind(form_id)
+        .bind(serde_json::json!([{"id": "color", "text": "Favorite color?", "type": "text"}]))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let submission_versions = |json: Value| -> Vec<(String, i64)> {
+        json["submissions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| (s["submitter_id"].as_str().unwrap().to_string(), s["form_version"].as_i64().unwrap()))
+            .collect()
+    };
+
+    let resp = app.clone().oneshot(get_archive(TEST_FORM_ID)).await.unwrap();
+    let archive = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: Value = serde_json::from_slice(&archive).unwrap();
+    assert_eq!(json["form"]["version"], 2);
+    assert_eq!(json["form"]["versions"].as_array().unwrap().len(), 2);
+    assert_eq!(json["form"]["versions"][0]["questions"][0]["id"], "q1");
+    let expected = vec![("bob.testnet".to_string(), 1), ("carol.testnet".to_string(), 2)];
+    assert_eq!(submission_versions(json), expected);
+
+    sqlx::query("DELETE FROM submissions WHERE form_id = $1").bind(form_id).execute(&pool).await.unwrap();
+    sqlx::query("DELETE FROM forms WHERE id = $1").bind(form_id).execute(&pool).await.unwrap();
+    let (status, body) = import_archive(&app, "", &archive).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["form"]["version"], 2);
+
+    // Old answers are still read against the questions they answered
+    let (_, json) = get_submissions_json(&app, "?order=asc").await;
+    assert_eq!(submission_versions(json), expected);
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/versions/1", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["questions"][0]["id"], "q1");
+
+    let resp = app.clone().oneshot(get_archive(TEST_FORM_ID)).await.unwrap();
+    let again = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(again, archive, "re-export of an imported form is byte-identical");
+
+    // The history must end at the form's current questions
+    let mut truncated: Value = serde_json::from_slice(&archive).unwrap();
+    truncated["form"]["versions"].as_array_mut().unwrap().pop();
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(&truncated["form"]).unwrap());
+    for submission in truncated["submissions"].as_array().unwrap() {
+        hasher.update(serde_json::to_vec(submission).unwrap());
+    }
+    truncated["checksum"] = hex::encode(hasher.finalize()).into();
+    let (status, body) = import_archive(&app, "?new_id=true", &serde_json::to_vec(&truncated).unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("current version"), "{}", body);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_archive_import_rejects_damaged_archives(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_submission(&pool, "alice.testnet", &unique_ec01_blob()).await;
+    let app = test_app(pool);
+    let resp = app.clone().oneshot(get_archive(TEST_FORM_ID)).await.unwrap();
+    let archive: Value = serde_json::from_slice(&resp.into_body().collect().await.unwrap().to_bytes()).unwrap();
+
+    let mut tampered = archive.clone();
+    tampered["submissions"][0]["submitter_id"] = "mallory.testnet".into();
+    let (status, body) = import_archive(&app, "?new_id=true", &serde_json::to_vec(&tampered).unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Archive checksum does not match its contents");
+
+    let mut future = archive.clone();
+    future["version"] = 3.into();
+    let (status, body) = import_archive(&app, "?new_id=true", &serde_json::to_vec(&future).unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().starts_with("Unsupported archive version 3"), "{}", body);
+
+    let resp = app.clone().oneshot(get_archive(&Uuid::new_v4().to_string())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let req = Request::builder().uri(format!("/v1/forms/{}/archive", TEST_FORM_ID)).body(Body::empty()).unwrap();
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submissions_csv_streams_encrypted_rows(pool: PgPool) {
+    seed_form(&pool).await;
+    let single = unique_ec01_blob();
+    let per_question = format!(r#"{{"q1":"{}"}}"#, unique_ec01_blob());
+    insert_submission(&pool, "alice.testnet", &single).await;
+    insert_submission(&pool, "bob.testnet", &per_question).await;
+    sqlx::query("UPDATE submissions SET submitted_at = submitted_at - INTERVAL '1 minute' WHERE submitter_id = 'alice.testnet'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO submissions (form_id, submitter_id, encrypted_blob, is_draft) VALUES ($1, 'carol.testnet', $2, true)")
+        .bind(Uuid::parse_str(TEST_FORM_ID).unwrap())
+        .bind(unique_ec01_blob())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let app = test_app(pool);
+
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions.csv", TEST_FORM_ID))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "text/csv; charset=utf-8");
+    assert!(resp.headers()["content-disposition"].to_str().unwrap().starts_with("attachment;"));
+    let csv = String::from_utf8(resp.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap();
+
+    let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+    assert_eq!(lines.len(), 3, "drafts are excluded: {}", csv);
+    assert_eq!(lines[0], "submitter_id,encrypted_blob,submitted_at");
+    assert!(lines[1].starts_with(&format!("alice.testnet,{},", single)), "{}", lines[1]);
+    let quoted = format!("\"{}\"", per_question.replace('"', "\"\""));
+    assert!(lines[2].starts_with(&format!("bob.testnet,{},", quoted)), "{}", lines[2]);
+    assert!(lines[2].ends_with('Z'));
+
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions.csv", Uuid::new_v4()))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+    let req = Request::builder().uri(format!("/v1/forms/{}/submissions.csv", TEST_FORM_ID)).body(Body::empty()).unwrap();
+    assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== GET /v1/forms/:id/submissions/stream ====================
+
+fn get_stream(form_id: &str) -> Request<Body> {
+    Request::builder()
+        .uri(format!("/v1/forms/{}/submissions/stream", form_id))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submission_stream_pushes_new_submissions(pool: PgPool) {
+    seed_form(&pool).await;
+    let other_form = Uuid::new_v4();
+    sqlx::query("INSERT INTO forms (id, creator_id, title, questions) VALUES ($1, 'bob.testnet', 'Other', '[]')")
+        .bind(other_form)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let events = SubmissionEvents::new();
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let relay = tokio::spawn(relay_submission_events(pool.clone(), events.clone(), shutdown_rx));
+    let state = AppState {
+        read_pool: pool.clone(),
+        pool: pool.clone(),
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: events,
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    let resp = app.clone().oneshot(get_stream(TEST_FORM_ID)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "text/event-stream");
+    let mut body = resp.into_body();
+
+    // The relay may still be connecting, so keep submitting until an event comes through.
+    // Each round also writes to another form first, which the stream must filter out.
+    let mut frame = None;
+    for round in 0..50 {
+        sqlx::query("INSERT INTO submissions (form_id, submitter_id, encrypted_blob) VALUES ($1, 'bob.testnet', $2)")
+            .bind(other_form)
+            .bind(unique_ec01_blob())
+            .execute(&pool)
+            .await
+            .unwrap();
+        insert_submission(&pool, &format!("user{}.testnet", round), &unique_ec01_blob()).await;
+        if let Ok(next) = tokio::time::timeout(std::time::Duration::from_millis(200), body.frame()).await {
+            frame = Some(next.unwrap().unwrap().into_data().unwrap());
+            break;
+        }
+    }
+    let text = String::from_utf8(frame.expect("no event within 50 submissions").to_vec()).unwrap();
+    assert!(text.lines().any(|l| l == "event: submission"), "{}", text);
+    let data = text.lines().find_map(|l| l.strip_prefix("data: ")).unwrap();
+    let event: Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["form_id"], TEST_FORM_ID);
+    assert!(event["submitter_id"].as_str().unwrap().starts_with("user"));
+    assert!(event.get("encrypted_blob").is_none());
+    assert!(text.lines().any(|l| l == format!("id: {}", event["id"].as_str().unwrap())), "{}", text);
+
+    // Shutdown ends open streams so graceful shutdown can finish
+    shutdown_tx.send(true).unwrap();
+    relay.await.unwrap();
+    loop {
+        let next = tokio::time::timeout(std::time::Duration::from_secs(5), body.frame()).await.expect("stream did not end");
+        if next.is_none() {
+            break;
+        }
+    }
+
+    let resp = app.clone().oneshot(get_stream(&Uuid::new_v4().to_string())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let req = Request::builder().uri(format!("/v1/forms/{}/submissions/stream", TEST_FORM_ID)).body(Body::empty()).unwrap();
+    assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn draft_submissions_lifecycle(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+
+    // Saving twice replaces the one draft in place
+    let resp = app.clone().oneshot(post_draft("bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let draft_id = body_json(resp).await["id"].clone();
+    let resp = app.clone().oneshot(post_draft("bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(body_json(resp).await["id"], draft_id);
+
+    // Hidden from reads and "has submitted" unless asked for
+    let (_, page) = get_submissions_json(&app, "?with_total=true").await;
+    assert_eq!(page["total_count"], 0);
+    let (_, page) = get_submissions_json(&app, "?with_total=true&include_drafts=true").await;
+    assert_eq!(page["total_count"], 1);
+    assert_eq!(page["submissions"][0]["is_draft"], true);
+    let resp = app.clone().oneshot(exists_request("bob.testnet", ("API-Secret", TEST_API_SECRET))).await.unwrap();
+    assert_eq!(body_json(resp).await["submitted"], false);
+
+    // The final submission is allowed despite the draft, and replaces it
+    let resp = app.clone().oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let (_, page) = get_submissions_json(&app, "?with_total=true&include_drafts=true").await;
+    assert_eq!(page["total_count"], 1);
+    assert_eq!(page["submissions"][0]["is_draft"], false);
+
+    // Nothing left to draft on a one-per-account form
+    let resp = app.clone().oneshot(post_draft("bob.testnet", &unique_ec01_blob())).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert!(body_json(resp).await["error"].as_str().unwrap().starts_with("You have already submitted this form"));
+
+    let json = body_json(app.clone().oneshot(post_batch(serde_json::json!([{
+        "form_id": TEST_FORM_ID,
+        "submitter_id": "carol.testnet",
+        "encrypted_blob": unique_ec01_blob(),
+        "is_draft": true,
+    }]), "")).await.unwrap()).await;
+    assert_eq!(json["results"][0]["status"], 400);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn list_creator_forms_requires_auth(pool: PgPool) {
+    let app = test_app(pool);
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/creators/dana.testnet/forms")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ==================== PATCH /v1/forms/:id/creator ====================
+
+fn patch_creator(form_id: &str, new_creator: &str, secret: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("PATCH")
+        .uri(format!("/v1/forms/{}/creator", form_id))
+        .header("Content-Type", "application/json");
+    if let Some(secret) = secret {
+        builder = builder.header("API-Secret", secret);
+    }
+    let body = serde_json::json!({ "new_creator_id": new_creator });
+    builder.body(Body::from(serde_json::to_vec(&body).unwrap())).unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn update_creator_transfers_ownership(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    let req = patch_creator(TEST_FORM_ID, "carol.testnet", Some(TEST_API_SECRET));
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["creator_id"], "carol.testnet");
+    assert_eq!(json["id"], TEST_FORM_ID);
+
+    let stored: String = sqlx::query_scalar("SELECT creator_id FROM forms WHERE id = $1")
+        .bind(Uuid::parse_str(TEST_FORM_ID).unwrap())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, "carol.testnet");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn update_creator_rejects_bad_input(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+
+    let resp = app.clone().oneshot(patch_creator(TEST_FORM_ID, "carol.testnet", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let req = patch_creator(TEST_FORM_ID, "Not A Valid Account!", Some(TEST_API_SECRET));
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = patch_creator(&Uuid::new_v4().to_string(), "carol.testnet", Some(TEST_API_SECRET));
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ==================== /v1/forms/:id/owners ====================
+
+fn owner_request(method: &str, path: &str, secret: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().method(method).uri(format!("/v1/forms/{}/owners{}", TEST_FORM_ID, path));
+    if let Some(secret) = secret {
+        builder = builder.header("API-Secret", secret);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn owner_ids(resp: axum::response::Response) -> Vec<String> {
+    body_json(resp).await["owners"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| o["account_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_owners_add_remove_and_follow_creator(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let send = |req| app.clone().oneshot(req);
+
+    // The creator is the first owner
+    let resp = send(owner_request("GET", "", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["owners"][0]["account_id"], "alice.testnet");
+    assert_eq!(json["owners"][0]["is_creator"], true);
+
+    // Adding is idempotent
+    for _ in 0..2 {
+        let resp = send(owner_request("PUT", "/bob.testnet", Some(TEST_API_SECRET))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(owner_ids(resp).await, ["alice.testnet", "bob.testnet"]);
+    }
+
+    let resp = send(owner_request("DELETE", "/alice.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // A transfer replaces the creator's ownership and keeps co-owners
+    let resp = send(patch_creator(TEST_FORM_ID, "carol.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = send(owner_request("GET", "", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(owner_ids(resp).await, ["carol.testnet", "bob.testnet"]);
+
+    let resp = send(owner_request("DELETE", "/bob.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(owner_ids(resp).await, ["carol.testnet"]);
+    let resp = send(owner_request("DELETE", "/bob.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn form_owners_reject_bad_input(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let send = |req| app.clone().oneshot(req);
+
+    let resp = send(owner_request("GET", "", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = send(owner_request("PUT", "/bob.testnet", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = send(owner_request("PUT", "/Not%20Valid", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = Request::builder()
+        .method("PUT")
+        .uri(format!("/v1/forms/{}/owners/bob.testnet", Uuid::new_v4()))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+
+    for i in 1..db_api::MAX_FORM_OWNERS {
+        let resp = send(owner_request("PUT", &format!("/owner{}.testnet", i), Some(TEST_API_SECRET))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    let resp = send(owner_request("PUT", "/one-too-many.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+fn visible_questions_request(account_id: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/v1/forms/{}/owners/{}/visible-questions", TEST_FORM_ID, account_id))
+        .header("API-Secret", TEST_API_SECRET)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn owner_visible_questions_set_and_cleared(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool);
+    let send = |req| app.clone().oneshot(req);
+
+    let resp = send(owner_request("PUT", "/bob.testnet", Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = send(visible_questions_request("bob.testnet", serde_json::json!({ "visible_questions": ["q1", "q3"] }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert!(json["owners"][0].get("visible_questions").is_none());
+    assert_eq!(json["owners"][1]["visible_questions"], serde_json::json!(["q1", "q3"]));
+
+    let resp = send(visible_questions_request("bob.testnet", serde_json::json!({ "visible_questions": ["q1", "q1"] }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = send(visible_questions_request("bob.testnet", serde_json::json!({ "visible_questions": [""] }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = send(visible_questions_request("alice.testnet", serde_json::json!({ "visible_questions": ["q1"] }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let resp = send(visible_questions_request("carol.testnet", serde_json::json!({ "visible_questions": ["q1"] }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = send(visible_questions_request("bob.testnet", serde_json::json!({ "visible_questions": null }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_json(resp).await["owners"][1].get("visible_questions").is_none());
+}
+
+// ==================== E2E: submit then read ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn e2e_submit_then_read(pool: PgPool) {
+    seed_form(&pool).await;
+    let blob = valid_ec01_blob();
+
+    // Submit via POST
+    let app = test_app(pool.clone());
+    let req = post_submission(TEST_FORM_ID, "bob.testnet", &blob);
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Read via GET
+    let app = test_app(pool);
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let subs = json["submissions"].as_array().unwrap();
+    assert_eq!(subs.len(), 1);
+    assert_eq!(subs[0]["submitter_id"], "bob.testnet");
+    assert_eq!(subs[0]["encrypted_blob"], blob);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn submissions_expose_server_timestamps_in_utc(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    let resp = app
+        .oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &valid_ec01_blob()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let app = test_app(pool);
+    let req = Request::builder()
+        .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+        .header("API-Secret", TEST_API_SECRET)
+        .body(Body::empty())
+        .unwrap();
+    let json = body_json(app.oneshot(req).await.unwrap()).await;
+    let sub = &json["submissions"][0];
+    let submitted_at = sub["submitted_at"].as_str().unwrap();
+    let received_at = sub["received_at"].as_str().unwrap();
+    assert!(submitted_at.ends_with('Z'));
+    assert!(received_at.ends_with('Z'));
+    // Both come from the same NOW() in the insert transaction
+    assert_eq!(submitted_at, received_at);
+}
+
+// ==================== Read replica routing ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn read_handlers_use_read_pool_and_writes_use_primary(pool: PgPool) {
+    seed_form(&pool).await;
+    // A read pool that can never connect: reads must fail, writes must still succeed
+    let unreachable = sqlx::postgres::PgPoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_millis(200))
+        .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+        .unwrap();
+    let state = AppState {
+        pool,
+        read_pool: unreachable,
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        // Uncached: a miss that will be cached is read from the primary instead
+        form_cache: FormCache::new(None),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    let resp = app
+        .clone()
+        .oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &valid_ec01_blob()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}", TEST_FORM_ID))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    // The pool never yields a connection, so the acquire times out
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn exhausted_pool_returns_503_with_retry_after(pool: PgPool) {
+    seed_form(&pool).await;
+    // One connection, already checked out: every handler's acquire must time out
+    let exhausted = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .connect_with((*pool.connect_options()).clone())
+        .await
+        .unwrap();
+    let _held = exhausted.acquire().await.unwrap();
+    let state = AppState {
+        pool: exhausted.clone(),
+        read_pool: exhausted.clone(),
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(None),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    let submit = post_submission(TEST_FORM_ID, "bob.testnet", &valid_ec01_blob());
+    let read = with_connect_info(
+        Request::builder()
+            .uri(format!("/v1/forms/{}/submissions", TEST_FORM_ID))
+            .header("API-Secret", TEST_API_SECRET)
+            .body(Body::empty())
+            .unwrap(),
+    );
+    for req in [submit, read] {
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()["retry-after"], "1");
+        let body = body_json(resp).await;
+        assert_eq!(body["error"], "Database is busy, please retry shortly");
+    }
+}
+
+// ==================== Concurrency limit ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn concurrency_limit_sheds_excess_requests(pool: PgPool) {
+    seed_form(&pool).await;
+    let state = AppState {
+        read_pool: pool.clone(),
+        pool: pool.clone(),
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
+        rate_limiter: RateLimiter::new(1000, 1000),
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 1,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+    };
+    let app = test_app_with_state(state);
+
+    // Hold a table lock so the first insert blocks while holding the only permit
+    let mut lock_tx = pool.begin().await.unwrap();
+    sqlx::query("LOCK TABLE submissions IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *lock_tx)
+        .await
+        .unwrap();
+
+    let blocked = tokio::spawn(
+        app.clone()
+            .oneshot(post_submission(TEST_FORM_ID, "bob.testnet", &valid_ec01_blob())),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let resp = app
+        .clone()
+        .oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &valid_ec01_blob()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let json = body_json(resp).await;
+    assert!(json["error"].as_str().unwrap().contains("busy"));
+
+    lock_tx.rollback().await.unwrap();
+    let resp = blocked.await.unwrap().unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Permit released: requests go through again
+    let resp = app
+        .oneshot(post_submission(TEST_FORM_ID, "carol.testnet", &unique_ec01_blob()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+// ==================== Transaction retry ====================
+
+/// Raise an error with the given SQLSTATE from inside a transaction.
+async fn fail_with_sqlstate(pool: &PgPool, code: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!("DO $$ BEGIN RAISE EXCEPTION 'forced' USING ERRCODE = '{}'; END $$", code))
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn retry_transaction_retries_serialization_failures(pool: PgPool) {
+    let attempts = std::sync::atomic::AtomicU32::new(0);
+    let result = retry_transaction(|| {
+        let n = attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        let pool = &pool;
+        async move {
+            match n {
+                1 => fail_with_sqlstate(pool, "40001").await.map(|_| 0),
+                2 => fail_with_sqlstate(pool, "40P01").await.map(|_| 0),
+                _ => Ok(n),
+            }
+        }
+    })
+    .await;
+    assert_eq!(result.unwrap(), 3);
+
+    // Persistent conflicts give up after the attempt limit
+    attempts.store(0, std::sync::atomic::Ordering::SeqCst);
+    let result = retry_transaction(|| {
+        attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        fail_with_sqlstate(&pool, "40001")
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), MAX_TRANSACTION_ATTEMPTS);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn retry_transaction_propagates_other_errors_immediately(pool: PgPool) {
+    let attempts = std::sync::atomic::AtomicU32::new(0);
+    let result = retry_transaction(|| {
+        attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        fail_with_sqlstate(&pool, "23505")
+    })
+    .await;
+    let err = result.unwrap_err();
+    assert_eq!(err.as_database_error().unwrap().code().as_deref(), Some("23505"));
+    assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+// ==================== Orphaned submission cleanup ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn delete_orphaned_submissions_removes_only_orphans(pool: PgPool) {
+    seed_form(&pool).await;
+    insert_submission(&pool, "bob.testnet", &valid_ec01_blob()).await;
+
+    // Simulate a form force-deleted outside the API (FK bypassed)
+    sqlx::query("ALTER TABLE submissions DROP CONSTRAINT submissions_form_id_fkey")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO submissions (id, form_id, submitter_id, encrypted_blob, submitted_at)
+         VALUES ($1, $2, 'carol.testnet', $3, NOW())"
+    )
+    .bind(Uuid::new_v4())
+    .bind(Uuid::new_v4())
+    .bind(valid_ec01_blob())
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert_eq!(count_submissions(&pool).await, 2);
+
+    assert_eq!(delete_orphaned_submissions(&pool).await.unwrap(), 1);
+    assert_eq!(count_submissions(&pool).await, 1);
+    assert_eq!(delete_orphaned_submissions(&pool).await.unwrap(), 0);
+}
+
+// ==================== POST /v1/forms/validate ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_questions_endpoint_lists_errors(pool: PgPool) {
+    let app = test_app(pool);
+    let validate = |body: serde_json::Value, secret: Option<&str>| {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/v1/forms/validate")
+            .header("content-type", "application/json");
+        if let Some(secret) = secret {
+            builder = builder.header("API-Secret", secret);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    };
+
+    let resp = app.clone().oneshot(validate(serde_json::json!({ "questions": [] }), None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = app.clone().oneshot(validate(serde_json::json!({ "questions": [{ "id": "q1", "label": "Name" }] }), Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await, serde_json::json!({ "valid": true }));
+
+    let questions = serde_json::json!([
+        { "id": "q1", "label": "Name" },
+        { "id": "q2", "label": "Colour", "renamed_from": "q0" },
+        { "id": "q3", "label": "Size", "renamed_from": ["q1"] },
+    ]);
+    let resp = app.clone().oneshot(validate(serde_json::json!({ "questions": questions }), Some(TEST_API_SECRET))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["valid"], false);
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["index"], 1);
+    assert_eq!(errors[1]["index"], 2);
+    assert_eq!(errors[1]["message"], "question q3 renamed_from q1 is still a question ID");
+
+    let resp = app.clone().oneshot(validate(serde_json::json!({ "questions": {} }), Some(TEST_API_SECRET))).await.unwrap();
+    let json = body_json(resp).await;
+    assert_eq!(json["errors"], serde_json::json!([{ "message": "questions must be a JSON array" }]));
+}
+
+// ==================== Submission retention ====================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn delete_expired_submissions_honors_form_retention(pool: PgPool) {
+    seed_form(&pool).await;
+    let app = test_app(pool.clone());
+    insert_submission(&pool, "bob.testnet", &unique_ec01_blob()).await;
+    insert_submission(&pool, "carol.testnet", &unique_ec01_blob()).await;
+    sqlx::query("UPDATE submissions SET submitted_at = NOW() - INTERVAL '10 days' WHERE submitter_id = 'bob.testnet'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Without a retention period nothing expires
+    assert!(delete_expired_submissions(&pool).await.unwrap().is_empty());
+
+    let retention = |body: serde_json::Value| {
+        Request::builder()
+            .method("PUT")
+            .uri(format!("/v1/forms/{}/retention", TEST_FORM_ID))
+            .header("API-Secret", TEST_API_SECRET)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+    let resp = app.clone().oneshot(retention(serde_json::json!({ "retention_days": 0 }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.clone().oneshot(retention(serde_json::json!({ "retention_days": 7 }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["retention_days"], 7);
+
+    let deleted = delete_expired_submissions(&pool).await.unwrap();
+    assert_eq!(deleted, [(Uuid::parse_str(TEST_FORM_ID).unwrap(), 1)]);
+    assert_eq!(count_submissions(&pool).await, 1);
+    assert!(delete_expired_submissions(&pool).await.unwrap().is_empty());
+
+    let resp = app.clone().oneshot(retention(serde_json::json!({ "retention_days": null }))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_json(resp).await.get("retention_days").is_none());
 }
 
 // ==================== Rate Limiting ====================
@@ -570,10 +3295,19 @@ async fn e2e_submit_then_read(pool: PgPool) {
 #[sqlx::test(migrations = "./migrations")]
 async fn rate_limit_rejects_after_burst(pool: PgPool) {
     let state = AppState {
+        read_pool: pool.clone(),
         pool,
-        api_secret: TEST_API_SECRET.to_string(),
+        api_secret_hash: hash_api_secret(TEST_API_SECRET),
+        submission_token_key: derive_submission_token_key(TEST_API_SECRET),
         rate_limiter: RateLimiter::new(0, 3), // zero refill, burst of 3
-        trust_proxy: false,
+        trusted_proxies: Default::default(),
+        max_concurrent_requests: 64,
+        max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+        question_limits: QuestionLimits::default(),
+        max_forms_per_creator: Some(DEFAULT_MAX_FORMS_PER_CREATOR),
+        submission_events: SubmissionEvents::new(),
+        form_cache: FormCache::new(Some(Duration::from_secs(DEFAULT_FORM_CACHE_TTL_SECS))),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
     };
 
     for i in 0..4 {
@@ -0,0 +1,100 @@
+//! Test-only allocator that measures peak heap use of a closure on the current thread.
+//!
+//! Used to compare submit-time blob validation against decoding the whole blob. Counts
+//! are per thread, so tests running in parallel do not disturb each other's numbers.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct PeakTracking;
+
+#[global_allocator]
+static ALLOCATOR: PeakTracking = PeakTracking;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record(grow: usize, shrink: usize) {
+    // try_with: the allocator also runs while thread-locals are being torn down
+    let _ = TRACKING.try_with(|tracking| {
+        if tracking.get() {
+            let current = CURRENT.with(|c| {
+                c.set((c.get() + grow).saturating_sub(shrink));
+                c.get()
+            });
+            PEAK.with(|p| p.set(p.get().max(current)));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for PeakTracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Run `f` and return its result with the peak bytes it held on the heap at once.
+/// Memory allocated before the call is not counted.
+pub fn peak_bytes<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    CURRENT.with(|c| c.set(0));
+    PEAK.with(|p| p.set(0));
+    TRACKING.with(|t| t.set(true));
+    let result = f();
+    TRACKING.with(|t| t.set(false));
+    (result, PEAK.with(Cell::get))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::{decoded_size, validate_ec01_hex, DEFAULT_MAX_BLOB_SIZE};
+
+    /// A valid EC01 blob at the default size cap.
+    fn max_size_blob_hex() -> String {
+        let secret = libsecp256k1::SecretKey::parse_slice(&[1u8; 32]).unwrap();
+        let mut blob = b"EC01".to_vec();
+        blob.extend_from_slice(&libsecp256k1::PublicKey::from_secret_key(&secret).serialize_compressed());
+        blob.resize(DEFAULT_MAX_BLOB_SIZE, 0);
+        hex::encode(blob)
+    }
+
+    #[test]
+    fn submit_validation_does_not_decode_the_blob() {
+        let hex_blob = max_size_blob_hex();
+
+        let (decoded, full_decode) = peak_bytes(|| hex::decode(&hex_blob).unwrap().len());
+        let (validated, header_only) = peak_bytes(|| validate_ec01_hex(&hex_blob, DEFAULT_MAX_BLOB_SIZE).unwrap());
+
+        assert_eq!(decoded, validated);
+        assert!(full_decode >= DEFAULT_MAX_BLOB_SIZE, "{}", full_decode);
+        assert!(header_only < 1024, "{}", header_only);
+    }
+
+    #[test]
+    fn size_estimate_does_not_decode_the_blob() {
+        let hex_blob = max_size_blob_hex();
+        let (size, peak) = peak_bytes(|| decoded_size(&hex_blob).unwrap());
+        assert_eq!(size, DEFAULT_MAX_BLOB_SIZE);
+        assert!(peak < 1024, "{}", peak);
+    }
+}
@@ -0,0 +1,210 @@
+//! Submission-rate anomaly flags for ReadResponses.
+//!
+//! Computed from the `submitted_at` timestamps already fetched for the page, so the
+//! signal costs no extra storage or round trips. Two patterns are flagged: many
+//! submissions in the same clock minute (bursts), and submissions arriving at near-equal
+//! intervals (scripted). Both are hints for the creator, not verdicts.
+//!
+//! [`clock_skew`] checks the same timestamps against the TEE's clock instead, to surface a
+//! db-api clock that has drifted (which would silently misorder every later analysis).
+
+use crate::types::{ClockSkew, SubmissionAnomalies, SubmissionBurst};
+use std::collections::BTreeMap;
+
+/// Submissions within one clock minute at or above which the minute is reported as a burst.
+pub const BURST_PER_MINUTE: usize = 10;
+
+/// Fewest submissions needed before interval regularity is judged.
+pub const REGULAR_MIN_SUBMISSIONS: usize = 10;
+
+/// Largest gap standard deviation, relative to the mean gap, still considered "regular".
+/// Human submissions are nowhere near this even; a timer loop is.
+const REGULAR_MAX_RELATIVE_JITTER: f64 = 0.05;
+
+/// Flag bursts and suspiciously regular intervals in a page's `submitted_at` values.
+///
+/// Timestamps are the db-api RFC 3339 UTC form (`2026-01-02T03:04:05.123456Z`); values
+/// that do not parse are left out of the interval check. Returns `None` when nothing
+/// is flagged, so ordinary pages carry no extra output.
+pub fn detect(submitted_at: &[&str]) -> Option<SubmissionAnomalies> {
+    let mut per_minute: BTreeMap<&str, usize> = BTreeMap::new();
+    for ts in submitted_at {
+        if let Some(minute) = ts.get(..16) {
+            *per_minute.entry(minute).or_default() += 1;
+        }
+    }
+    let peak_per_minute = per_minute.values().copied().max().unwrap_or(0);
+    let bursts: Vec<SubmissionBurst> = per_minute
+        .into_iter()
+        .filter(|(_, count)| *count >= BURST_PER_MINUTE)
+        .map(|(minute, count)| SubmissionBurst {
+            minute: format!("{}:00Z", minute),
+            count,
+        })
+        .collect();
+
+    let regular_interval_ms = regular_interval_ms(submitted_at);
+
+    if bursts.is_empty() && regular_interval_ms.is_none() {
+        return None;
+    }
+    Some(SubmissionAnomalies {
+        peak_per_minute,
+        bursts,
+        regular_interval_ms,
+    })
+}
+
+/// How far a `submitted_at` may lead the TEE clock (or trail the form's `created_at`)
+/// before it is reported: ordinary NTP drift between two hosts is well under this.
+pub const CLOCK_SKEW_TOLERANCE_SECS: i64 = 300;
+
+/// Flag `submitted_at` values db-api's clock could only have produced while wrong: later
+/// than `now_micros` (the TEE's clock), or earlier than the form's own `created_at`.
+///
+/// Both timestamps come from db-api, so only the future check catches a clock that is
+/// wrong all the time; the `created_at` check catches one that jumped back. Values that do
+/// not parse are ignored. Returns `None` when nothing is flagged.
+pub fn clock_skew(submitted_at: &[&str], now_micros: i64, form_created_at: Option<&str>) -> Option<ClockSkew> {
+    let tolerance = CLOCK_SKEW_TOLERANCE_SECS * 1_000_000;
+    let created = form_created_at.and_then(parse_utc_micros);
+    let mut skew = ClockSkew { future_dated: 0, max_ahead_secs: 0, before_form_created: 0 };
+    for micros in submitted_at.iter().filter_map(|ts| parse_utc_micros(ts)) {
+        let ahead = micros - now_micros;
+        if ahead > tolerance {
+            skew.future_dated += 1;
+            skew.max_ahead_secs = skew.max_ahead_secs.max((ahead / 1_000_000) as u64);
+        }
+        if created.is_some_and(|created| micros < created - tolerance) {
+            skew.before_form_created += 1;
+        }
+    }
+    (skew.future_dated > 0 || skew.before_form_created > 0).then_some(skew)
+}
+
+/// Mean gap in milliseconds when the gaps between consecutive submissions are near-equal.
+fn regular_interval_ms(submitted_at: &[&str]) -> Option<u64> {
+    let mut micros: Vec<i64> = submitted_at.iter().filter_map(|ts| parse_utc_micros(ts)).collect();
+    if micros.len() < REGULAR_MIN_SUBMISSIONS {
+        return None;
+    }
+    micros.sort_unstable();
+    let gaps: Vec<f64> = micros.windows(2).map(|w| (w[1] - w[0]) as f64).collect();
+    let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+    if mean <= 0.0 {
+        // All at the same instant: that is a burst, not an interval
+        return None;
+    }
+    let variance = gaps.iter().map(|g| (g - mean).powi(2)).sum::<f64>() / gaps.len() as f64;
+    if variance.sqrt() / mean > REGULAR_MAX_RELATIVE_JITTER {
+        return None;
+    }
+    Some((mean / 1000.0).round() as u64)
+}
+
+/// Parse `YYYY-MM-DDTHH:MM:SS[.ffffff]Z` into microseconds since the Unix epoch.
+pub fn parse_utc_micros(ts: &str) -> Option<i64> {
+    let ts = ts.strip_suffix('Z')?;
+    let (date, time) = ts.split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (date_parts.next()?.ok()?, date_parts.next()?.ok()?, date_parts.next()?.ok()?);
+    let (hms, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time_parts = hms.splitn(3, ':').map(str::parse::<i64>);
+    let (hour, minute, second) = (time_parts.next()?.ok()?, time_parts.next()?.ok()?, time_parts.next()?.ok()?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let fraction_micros = if fraction.is_empty() {
+        0
+    } else {
+        let digits: String = fraction.chars().chain(std::iter::repeat('0')).take(6).collect();
+        digits.parse::<i64>().ok()?
+    };
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar (Howard Hinnant's algorithm)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    Some(secs * 1_000_000 + fraction_micros)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_seconds(start: i64, step: i64, n: usize) -> Vec<String> {
+        (0..n as i64)
+            .map(|i| {
+                let s = start + i * step;
+                format!("2026-01-01T{:02}:{:02}:{:02}.000000Z", s / 3600, (s / 60) % 60, s % 60)
+            })
+            .collect()
+    }
+
+    fn refs(v: &[String]) -> Vec<&str> {
+        v.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn parse_utc_micros_known_values() {
+        assert_eq!(parse_utc_micros("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_utc_micros("2026-01-01T00:00:00.000001Z"), Some(1_767_225_600_000_001));
+        assert_eq!(parse_utc_micros("2024-02-29T12:00:00.5Z"), Some(1_709_208_000_500_000));
+        assert_eq!(parse_utc_micros("2026-13-01T00:00:00Z"), None);
+        assert_eq!(parse_utc_micros("not a time"), None);
+    }
+
+    #[test]
+    fn ordinary_page_has_no_anomalies() {
+        let times = ["2026-01-01T09:00:00Z", "2026-01-01T09:07:13Z", "2026-01-01T11:42:01Z"];
+        assert!(detect(&times).is_none());
+    }
+
+    #[test]
+    fn burst_within_one_minute() {
+        let mut times = at_seconds(0, 3, BURST_PER_MINUTE);
+        times.push("2026-01-01T05:00:00.000000Z".to_string());
+        let anomalies = detect(&refs(&times)).unwrap();
+        assert_eq!(anomalies.peak_per_minute, BURST_PER_MINUTE);
+        assert_eq!(anomalies.bursts.len(), 1);
+        assert_eq!(anomalies.bursts[0].minute, "2026-01-01T00:00:00Z");
+        assert_eq!(anomalies.bursts[0].count, BURST_PER_MINUTE);
+    }
+
+    #[test]
+    fn regular_intervals_flagged() {
+        let times = at_seconds(0, 90, REGULAR_MIN_SUBMISSIONS);
+        let anomalies = detect(&refs(&times)).unwrap();
+        assert!(anomalies.bursts.is_empty());
+        assert_eq!(anomalies.regular_interval_ms, Some(90_000));
+    }
+
+    #[test]
+    fn irregular_or_few_intervals_not_flagged() {
+        assert!(detect(&refs(&at_seconds(0, 90, REGULAR_MIN_SUBMISSIONS - 1))).is_none());
+        let mut times = at_seconds(0, 90, REGULAR_MIN_SUBMISSIONS);
+        times[4] = "2026-01-01T00:05:17.000000Z".to_string();
+        assert!(detect(&refs(&times)).is_none());
+    }
+
+    #[test]
+    fn clock_skew_flags_future_and_pre_creation_timestamps() {
+        let now = parse_utc_micros("2026-01-01T12:00:00Z").unwrap();
+        let created = Some("2026-01-01T00:00:00.000000Z");
+        let fine = ["2026-01-01T11:59:00Z", "2026-01-01T12:04:00Z", "2025-12-31T23:58:00Z"];
+        assert!(clock_skew(&fine, now, created).is_none());
+
+        let skewed = ["2026-01-01T13:00:00Z", "2026-01-01T12:10:00Z", "2025-12-01T00:00:00Z", "garbage"];
+        let skew = clock_skew(&skewed, now, created).unwrap();
+        assert_eq!(skew.future_dated, 2);
+        assert_eq!(skew.max_ahead_secs, 3600);
+        assert_eq!(skew.before_form_created, 1);
+        assert!(clock_skew(&skewed[2..], now, None).is_none());
+    }
+}
@@ -0,0 +1,501 @@
+//! Parsing and shaping of decrypted answers for ReadResponses (ordering, size checks,
+//! renamed question IDs, question-level visibility, answer filters, per-submitter
+//! deduplication).
+//!
+//! Relies on serde_json's `preserve_order` feature: without it `Map` is a `BTreeMap` and
+//! keys always serialize alphabetically (`q10` before `q2`), whatever order we insert them.
+
+use crate::error::FormsError;
+use crate::types::{AnswerFilter, DuplicateSubmitter, Response};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+
+/// Default cap on a decrypted answers payload (200 KB, the default ciphertext cap).
+/// Overridable via `MAX_PLAINTEXT_SIZE_BYTES`.
+pub const DEFAULT_MAX_PLAINTEXT_SIZE: usize = 200 * 1024;
+
+/// Default maximum nesting depth of answers JSON. Overridable via `MAX_ANSWERS_DEPTH`.
+pub const DEFAULT_MAX_ANSWERS_DEPTH: usize = 16;
+
+/// Default maximum number of object keys in answers JSON, counted across all nesting levels.
+/// Overridable via `MAX_ANSWERS_KEYS`.
+pub const DEFAULT_MAX_ANSWERS_KEYS: usize = 1000;
+
+/// Structural limits on answers JSON, checked by [`check_json_shape`] before parsing.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonLimits {
+    pub max_depth: usize,
+    pub max_keys: usize,
+}
+
+impl Default for JsonLimits {
+    fn default() -> Self {
+        JsonLimits {
+            max_depth: DEFAULT_MAX_ANSWERS_DEPTH,
+            max_keys: DEFAULT_MAX_ANSWERS_KEYS,
+        }
+    }
+}
+
+/// Reject answers JSON nested deeper than `limits.max_depth` or holding more than
+/// `limits.max_keys` object keys, without building a `Value`.
+///
+/// A single pass over the bytes that only tracks strings, brackets and colons: a payload
+/// under the size cap can still be thousands of levels deep, and that should fail here
+/// rather than in the recursive parser or in stats/CSV rendering later. Malformed JSON is
+/// not diagnosed; the parse that follows reports it.
+pub fn check_json_shape(json: &[u8], limits: &JsonLimits) -> Result<(), FormsError> {
+    let mut depth = 0usize;
+    let mut keys = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in json {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > limits.max_depth {
+                    return Err(FormsError::InvalidInput(format!(
+                        "Answers nested too deeply (max depth: {})",
+                        limits.max_depth
+                    )));
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b':' => {
+                keys += 1;
+                if keys > limits.max_keys {
+                    return Err(FormsError::InvalidInput(format!(
+                        "Answers have too many keys (max: {})",
+                        limits.max_keys
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Reject decrypted plaintext larger than `max_len` bytes.
+///
+/// Today EC01 plaintext is never larger than its (already size-capped) ciphertext, but
+/// once answers are compressed a small blob could expand arbitrarily; checking the
+/// decrypted size keeps ReadResponses memory bounded either way.
+pub fn check_plaintext_size(len: usize, max_len: usize) -> Result<(), FormsError> {
+    if len > max_len {
+        return Err(FormsError::InvalidInput(format!(
+            "Decrypted answers too large: {} bytes (max: {} bytes)",
+            len, max_len
+        )));
+    }
+    Ok(())
+}
+
+/// Parse decrypted plaintext as answers JSON, reporting bad UTF-8 separately from bad JSON.
+/// Plaintext over `max_len` bytes, or outside `limits`, is rejected before parsing.
+///
+/// ChaCha20-Poly1305 authenticates the ciphertext, so a wrong key fails decryption outright;
+/// plaintext that decrypts but is not UTF-8 was produced that way by the encrypting client
+/// (wrong input buffer, binary data, corruption before encryption). Calling that out saves
+/// chasing a JSON syntax error that isn't one.
+///
+/// With `lenient`, JSON that strict parsing rejects gets a second try through
+/// [`recover_json`]; the flag returned says whether it was needed. Size, shape and UTF-8
+/// failures are never recovered.
+pub fn parse_answers(
+    plaintext: &[u8],
+    max_len: usize,
+    limits: &JsonLimits,
+    lenient: bool,
+) -> Result<(Value, bool), FormsError> {
+    check_plaintext_size(plaintext.len(), max_len)?;
+    check_json_shape(plaintext, limits)?;
+    let text = std::str::from_utf8(plaintext).map_err(|e| {
+        FormsError::InvalidInput(format!(
+            "Decrypted answers are not valid UTF-8 (corrupt or non-text plaintext, invalid byte at offset {})",
+            e.valid_up_to()
+        ))
+    })?;
+    match serde_json::from_str(text) {
+        Ok(answers) => Ok((answers, false)),
+        Err(e) => match lenient.then(|| recover_json(text)).flatten() {
+            Some(answers) => Ok((answers, true)),
+            None => Err(FormsError::InvalidInput(format!("Invalid JSON in decrypted answers: {}", e))),
+        },
+    }
+}
+
+/// Second, more permissive parse for JSON that strict parsing rejected: strips a leading
+/// byte order mark, drops trailing commas before `}` or `]`, and takes the first complete
+/// value, ignoring whatever follows it (padding, a truncated duplicate). `None` when there
+/// is still nothing to salvage.
+///
+/// Only for reading back stored submissions, where the alternative is losing the data;
+/// SubmitForm keeps rejecting such input outright.
+pub fn recover_json(text: &str) -> Option<Value> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut cleaned = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && matches!(text[i + 1..].trim_start().chars().next(), Some('}' | ']')) {
+            continue;
+        }
+        cleaned.push(c);
+    }
+    serde_json::Deserializer::from_str(&cleaned).into_iter::<Value>().next()?.ok()
+}
+
+/// Reorder an answers object so keys follow the form's question definition order.
+///
+/// Keys that are not question IDs are kept, after all known keys, in their original order.
+/// Non-object values (malformed submissions) are returned unchanged.
+pub fn order_answers(answers: Value, questions: &Value) -> Value {
+    let Value::Object(mut remaining) = answers else {
+        return answers;
+    };
+    let mut ordered = Map::with_capacity(remaining.len());
+    for id in questions
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|q| q.get("id")?.as_str())
+    {
+        if let Some(value) = remaining.shift_remove(id) {
+            ordered.insert(id.to_string(), value);
+        }
+    }
+    ordered.extend(remaining);
+    Value::Object(ordered)
+}
+
+/// Earlier question IDs of the form's current `questions`, old ID -> current ID.
+///
+/// A question lists the IDs it had in earlier revisions in `renamed_from` (db-api checks
+/// each old ID is claimed once and is not a current ID). Every revision keeps its own
+/// questions in `form_versions`, so the current one describes every rename up to now.
+pub fn renamed_question_ids(questions: &Value) -> HashMap<String, String> {
+    let mut renames = HashMap::new();
+    for question in questions.as_array().into_iter().flatten() {
+        let Some(id) = question.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        let old_ids = question.get("renamed_from").and_then(Value::as_array).into_iter().flatten();
+        for old_id in old_ids.filter_map(Value::as_str) {
+            renames.insert(old_id.to_string(), id.to_string());
+        }
+    }
+    renames
+}
+
+/// File answers given under an earlier question ID (see [`renamed_question_ids`]) under the
+/// current one, keeping key order. An old key whose current ID is also present is left as
+/// it is rather than overwriting that answer. Non-object values are returned unchanged.
+pub fn rename_answer_keys(answers: Value, renames: &HashMap<String, String>) -> Value {
+    let Value::Object(map) = answers else {
+        return answers;
+    };
+    if renames.is_empty() {
+        return Value::Object(map);
+    }
+    let mut renamed = Map::with_capacity(map.len());
+    for (key, value) in &map {
+        let key = match renames.get(key) {
+            Some(current) if !map.contains_key(current) => current.clone(),
+            _ => key.clone(),
+        };
+        renamed.insert(key, value.clone());
+    }
+    Value::Object(renamed)
+}
+
+/// Question IDs whose answers a reader may see: the caller's `requested` filter narrowed
+/// to what their owner record `permitted`. None on both sides means every question.
+pub fn visible_question_ids(requested: Option<&[String]>, permitted: Option<&[String]>) -> Option<BTreeSet<String>> {
+    match (requested, permitted) {
+        (None, None) => None,
+        (Some(ids), None) | (None, Some(ids)) => Some(ids.iter().cloned().collect()),
+        (Some(requested), Some(permitted)) => Some(
+            requested.iter().filter(|id| permitted.contains(id)).cloned().collect(),
+        ),
+    }
+}
+
+/// Drop every answer whose key is not in `visible`. Apply after [`rename_answer_keys`] so
+/// answers given under an earlier ID are matched by their current one. Non-object values
+/// are returned unchanged.
+pub fn retain_visible_answers(answers: Value, visible: &BTreeSet<String>) -> Value {
+    let Value::Object(mut map) = answers else {
+        return answers;
+    };
+    map.retain(|key, _| visible.contains(key));
+    Value::Object(map)
+}
+
+/// Whether `answers` (keys already renamed to current IDs) satisfies `filter`: the answer
+/// equals `filter.value`, or is an array containing it. An unanswered question never matches.
+pub fn matches_filter(answers: &Value, filter: &AnswerFilter) -> bool {
+    match answers.get(&filter.question_id) {
+        Some(Value::Array(items)) if !filter.value.is_array() => items.contains(&filter.value),
+        Some(answer) => *answer == filter.value,
+        None => false,
+    }
+}
+
+/// Keep only the latest response per `submitter_id`, reporting every submitter that had more.
+///
+/// db-api's `(form_id, submitter_id)` unique constraint should make this a no-op; it is an
+/// integrity signal for rows that slipped past it. Latest is by `submitted_at`, which db-api
+/// always formats as UTC RFC 3339 with fixed precision, so string order is time order (ties
+/// keep the earlier entry). Surviving responses keep their original relative order.
+pub fn dedupe_by_submitter(responses: Vec<Response>) -> (Vec<Response>, Vec<DuplicateSubmitter>) {
+    // submitter_id -> (index of latest response, number seen)
+    let mut latest: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (i, response) in responses.iter().enumerate() {
+        let entry = latest.entry(response.submitter_id.as_str()).or_insert((i, 0));
+        entry.1 += 1;
+        if response.submitted_at > responses[entry.0].submitted_at {
+            entry.0 = i;
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateSubmitter> = latest
+        .values()
+        .filter(|(_, count)| *count > 1)
+        .map(|&(i, count)| DuplicateSubmitter {
+            submitter_id: responses[i].submitter_id.clone(),
+            submission_count: count,
+            kept_submitted_at: responses[i].submitted_at.clone(),
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.submitter_id.cmp(&b.submitter_id));
+
+    let keep: Vec<bool> = responses
+        .iter()
+        .enumerate()
+        .map(|(i, r)| latest[r.submitter_id.as_str()].0 == i)
+        .collect();
+    let kept = responses
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(response, keep)| keep.then_some(response))
+        .collect();
+    (kept, duplicates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(submitter: &str, submitted_at: &str) -> Response {
+        Response {
+            submitter_id: submitter.to_string(),
+            answers: json!({ "at": submitted_at }),
+            submitted_at: submitted_at.to_string(),
+            key_version: "current".to_string(),
+            is_draft: false,
+            form_version: None,
+            recovered: false,
+            encrypted_blob: None,
+        }
+    }
+
+    #[test]
+    fn dedupe_keeps_latest_per_submitter() {
+        let (kept, duplicates) = dedupe_by_submitter(vec![
+            response("bob.near", "2026-01-02T00:00:00.000000Z"),
+            response("amy.near", "2026-01-01T00:00:00.000000Z"),
+            response("bob.near", "2026-01-03T00:00:00.000000Z"),
+            response("bob.near", "2026-01-01T00:00:00.000000Z"),
+        ]);
+        let kept: Vec<(&str, &str)> = kept.iter().map(|r| (r.submitter_id.as_str(), r.submitted_at.as_str())).collect();
+        assert_eq!(kept, [("amy.near", "2026-01-01T00:00:00.000000Z"), ("bob.near", "2026-01-03T00:00:00.000000Z")]);
+        assert_eq!(duplicates, [DuplicateSubmitter {
+            submitter_id: "bob.near".to_string(),
+            submission_count: 3,
+            kept_submitted_at: "2026-01-03T00:00:00.000000Z".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn dedupe_without_duplicates_is_identity() {
+        let (kept, duplicates) = dedupe_by_submitter(vec![
+            response("bob.near", "2026-01-02T00:00:00.000000Z"),
+            response("amy.near", "2026-01-01T00:00:00.000000Z"),
+        ]);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].submitter_id, "bob.near");
+        assert!(duplicates.is_empty());
+    }
+
+    fn keys(value: &Value) -> Vec<&str> {
+        value.as_object().unwrap().keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn parse_answers_distinguishes_utf8_from_json_errors() {
+        assert_eq!(parse_answers(br#"{"q1":"a"}"#, DEFAULT_MAX_PLAINTEXT_SIZE, &JsonLimits::default(), false).unwrap().0, json!({ "q1": "a" }));
+
+        let err = parse_answers(&[b'{', 0xff, 0xfe, b'}'], DEFAULT_MAX_PLAINTEXT_SIZE, &JsonLimits::default(), false).unwrap_err().to_string();
+        assert!(err.contains("not valid UTF-8"), "{}", err);
+        assert!(err.contains("offset 1"), "{}", err);
+
+        let err = parse_answers(b"{\"q1\":", DEFAULT_MAX_PLAINTEXT_SIZE, &JsonLimits::default(), false).unwrap_err().to_string();
+        assert!(err.starts_with("Invalid JSON in decrypted answers"), "{}", err);
+    }
+
+    #[test]
+    fn lenient_parse_recovers_bom_trailing_commas_and_trailing_bytes() {
+        let limits = JsonLimits::default();
+        let parse = |plaintext: &[u8]| parse_answers(plaintext, DEFAULT_MAX_PLAINTEXT_SIZE, &limits, true);
+        assert_eq!(parse(br#"{"q1":"a"}"#).unwrap(), (json!({ "q1": "a" }), false));
+        assert_eq!(parse("\u{feff}{\"q1\":\"a\"}".as_bytes()).unwrap(), (json!({ "q1": "a" }), true));
+        assert_eq!(parse(br#"{"q1":["a","b",],"q2":"x, ]",}"#).unwrap(), (json!({ "q1": ["a", "b"], "q2": "x, ]" }), true));
+        assert_eq!(parse(b"{\"q1\":1}\0\0{\"q1\"").unwrap(), (json!({ "q1": 1 }), true));
+
+        let err = parse(b"{\"q1\":").unwrap_err().to_string();
+        assert!(err.starts_with("Invalid JSON in decrypted answers"), "{}", err);
+        // Strict unless asked
+        assert!(parse_answers(br#"{"q1":1,}"#, DEFAULT_MAX_PLAINTEXT_SIZE, &limits, false).is_err());
+    }
+
+    #[test]
+    fn oversized_plaintext_is_rejected_before_parsing() {
+        let plaintext = br#"{"q1":"abcdef"}"#;
+        assert!(parse_answers(plaintext, plaintext.len(), &JsonLimits::default(), false).is_ok());
+        let err = parse_answers(plaintext, plaintext.len() - 1, &JsonLimits::default(), false).unwrap_err().to_string();
+        assert!(err.starts_with("Decrypted answers too large"), "{}", err);
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected_before_parsing() {
+        let limits = JsonLimits { max_depth: 3, max_keys: 10 };
+        assert!(check_json_shape(br#"{"q1":[[1]]}"#, &limits).is_ok());
+        let err = check_json_shape(br#"{"q1":[[[1]]]}"#, &limits).unwrap_err().to_string();
+        assert_eq!(err, "Answers nested too deeply (max depth: 3)");
+
+        // Far past serde_json's own recursion limit; must fail on depth, not in the parser
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let err = parse_answers(deep.as_bytes(), DEFAULT_MAX_PLAINTEXT_SIZE, &JsonLimits::default(), false).unwrap_err();
+        assert!(err.to_string().starts_with("Answers nested too deeply"), "{}", err);
+    }
+
+    #[test]
+    fn key_count_spans_nested_objects() {
+        let limits = JsonLimits { max_depth: 10, max_keys: 3 };
+        assert!(check_json_shape(br#"{"q1":{"a":1,"b":2}}"#, &limits).is_ok());
+        let err = check_json_shape(br#"{"q1":{"a":1,"b":2},"q2":3}"#, &limits).unwrap_err().to_string();
+        assert_eq!(err, "Answers have too many keys (max: 3)");
+    }
+
+    #[test]
+    fn brackets_and_colons_in_strings_are_ignored() {
+        let limits = JsonLimits { max_depth: 1, max_keys: 1 };
+        assert!(check_json_shape(br#"{"q1":"[[{:}]] \" {{:"}"#, &limits).is_ok());
+    }
+
+    #[test]
+    fn follows_question_order() {
+        let questions = json!([{ "id": "q2" }, { "id": "q10" }, { "id": "q1" }]);
+        let answers: Value = serde_json::from_str(r#"{"q1":"a","q10":"b","q2":"c"}"#).unwrap();
+        let ordered = order_answers(answers, &questions);
+        assert_eq!(keys(&ordered), ["q2", "q10", "q1"]);
+        assert_eq!(ordered["q10"], "b");
+    }
+
+    #[test]
+    fn unknown_keys_go_last_in_original_order() {
+        let questions = json!([{ "id": "q1" }, { "id": "q2" }]);
+        let answers: Value = serde_json::from_str(r#"{"zz":1,"q2":2,"extra":3,"q1":4}"#).unwrap();
+        assert_eq!(keys(&order_answers(answers, &questions)), ["q1", "q2", "zz", "extra"]);
+    }
+
+    #[test]
+    fn renamed_ids_map_to_current_ids_in_place() {
+        let questions = json!([
+            { "id": "q1" },
+            { "id": "q2b", "renamed_from": ["q2", "q2a"] },
+            { "id": "q3", "renamed_from": "not-a-list" },
+        ]);
+        let renames = renamed_question_ids(&questions);
+        assert_eq!(renames.len(), 2);
+        assert_eq!(renames["q2a"], "q2b");
+
+        // Answered against version 1 (q2), then version 2 (q2a)
+        let v1: Value = serde_json::from_str(r#"{"q1":"a","q2":"b","zz":1}"#).unwrap();
+        let renamed = rename_answer_keys(v1, &renames);
+        assert_eq!(keys(&renamed), ["q1", "q2b", "zz"]);
+        assert_eq!(renamed["q2b"], "b");
+        assert_eq!(rename_answer_keys(json!({ "q2a": "c" }), &renames), json!({ "q2b": "c" }));
+
+        // Never overwrites an answer already under the current ID
+        let both: Value = serde_json::from_str(r#"{"q2":"old","q2b":"new"}"#).unwrap();
+        assert_eq!(keys(&rename_answer_keys(both, &renames)), ["q2", "q2b"]);
+    }
+
+    #[test]
+    fn visible_questions_intersect_request_and_permission() {
+        let ids = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(visible_question_ids(None, None), None);
+        let requested = ids(&["q1", "q2"]);
+        let permitted = ids(&["q2", "q3"]);
+        assert_eq!(visible_question_ids(Some(&requested), None).unwrap().len(), 2);
+        assert_eq!(visible_question_ids(None, Some(&permitted)).unwrap().len(), 2);
+        let both = visible_question_ids(Some(&requested), Some(&permitted)).unwrap();
+        assert_eq!(both.into_iter().collect::<Vec<_>>(), ["q2"]);
+
+        // Applied after renaming, so an answer under an old ID is matched by its current one
+        let renames = renamed_question_ids(&json!([{ "id": "q2", "renamed_from": ["q2old"] }]));
+        let answers = rename_answer_keys(json!({ "q1": "a", "q2old": "b", "q3": "c" }), &renames);
+        let visible = BTreeSet::from(["q2".to_string()]);
+        assert_eq!(retain_visible_answers(answers, &visible), json!({ "q2": "b" }));
+    }
+
+    #[test]
+    fn filter_matches_value_or_array_member() {
+        let filter = |question_id: &str, value: Value| AnswerFilter { question_id: question_id.to_string(), value };
+        let answers = json!({ "q1": "yes", "q2": ["a", "c"], "q3": 4 });
+        assert!(matches_filter(&answers, &filter("q1", json!("yes"))));
+        assert!(!matches_filter(&answers, &filter("q1", json!("no"))));
+        assert!(matches_filter(&answers, &filter("q2", json!("c"))));
+        assert!(matches_filter(&answers, &filter("q2", json!(["a", "c"]))));
+        assert!(!matches_filter(&answers, &filter("q2", json!("b"))));
+        assert!(matches_filter(&answers, &filter("q3", json!(4))));
+        assert!(!matches_filter(&answers, &filter("q3", json!("4"))));
+        assert!(!matches_filter(&answers, &filter("q9", json!(null))));
+    }
+
+    #[test]
+    fn unanswered_questions_are_not_added() {
+        let questions = json!([{ "id": "q1" }, { "id": "q2" }]);
+        let ordered = order_answers(json!({ "q2": "x" }), &questions);
+        assert_eq!(ordered, json!({ "q2": "x" }));
+    }
+
+    #[test]
+    fn non_object_and_missing_questions_pass_through() {
+        assert_eq!(order_answers(json!(["a"]), &json!([{ "id": "q1" }])), json!(["a"]));
+        let answers: Value = serde_json::from_str(r#"{"b":1,"a":2}"#).unwrap();
+        assert_eq!(keys(&order_answers(answers, &Value::Null)), ["b", "a"]);
+    }
+}
@@ -0,0 +1,206 @@
+//! Layouts of the ciphertext stored in `submissions.encrypted_blob`.
+//!
+//! A submission is either one hex EC01 blob over the whole answers object, or a JSON
+//! object mapping each question ID to a hex EC01 blob of that answer's JSON value. The
+//! per-question layout lets a handler decrypt only the answers it needs; each field is an
+//! ordinary EC01 blob, so `crypto::decrypt_blob` is unchanged.
+
+use crate::answers::{self, JsonLimits};
+use crate::crypto;
+use crate::error::FormsError;
+use libsecp256k1::{PublicKey, SecretKey};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// True when `blob` uses the per-question layout (a JSON object rather than hex).
+pub fn is_per_question(blob: &str) -> bool {
+    blob.starts_with('{')
+}
+
+/// Ciphertext of a stored submission, hex-decoded.
+pub enum StoredBlob {
+    Whole(Vec<u8>),
+    PerQuestion(BTreeMap<String, Vec<u8>>),
+}
+
+/// Decrypted counterpart of [`StoredBlob`]: the answers object, or each answer's JSON.
+pub enum Plaintext {
+    Whole(Vec<u8>),
+    PerQuestion(BTreeMap<String, Vec<u8>>),
+}
+
+impl StoredBlob {
+    /// Decode a blob read back from db-api (already validated on insert), so a failure
+    /// means the stored data is corrupt: [`FormsError::DecryptFailed`].
+    pub fn parse(blob: &str) -> Result<Self, FormsError> {
+        if !is_per_question(blob) {
+            return hex::decode(blob)
+                .map(StoredBlob::Whole)
+                .map_err(|e| FormsError::DecryptFailed(format!("Invalid hex ciphertext: {}", e)));
+        }
+        let fields: BTreeMap<String, String> = serde_json::from_str(blob)
+            .map_err(|e| FormsError::DecryptFailed(format!("Invalid per-question ciphertext: {}", e)))?;
+        fields
+            .into_iter()
+            .map(|(id, hex_blob)| {
+                let bytes = hex::decode(&hex_blob)
+                    .map_err(|e| FormsError::DecryptFailed(format!("Invalid hex ciphertext for {}: {}", id, e)))?;
+                Ok((id, bytes))
+            })
+            .collect::<Result<_, FormsError>>()
+            .map(StoredBlob::PerQuestion)
+    }
+
+    /// Drop per-question fields whose ID fails `keep`, so they are never decrypted. A
+    /// whole-object blob has to be decrypted in full; filter its answers afterwards.
+    pub fn retain_fields(&mut self, keep: impl Fn(&str) -> bool) {
+        if let StoredBlob::PerQuestion(fields) = self {
+            fields.retain(|id, _| keep(id));
+        }
+    }
+
+    /// Decrypt with whichever form key works (see `crypto::decrypt_blob_any`).
+    ///
+    /// For the per-question layout, returns the oldest key version any field needed, so a
+    /// half-migrated submission is still reported as needing re-encryption.
+    pub fn decrypt<'a>(
+        &self,
+        form_privkeys: &[(&'a str, SecretKey)],
+    ) -> Result<(&'a str, Plaintext), FormsError> {
+        match self {
+            StoredBlob::Whole(bytes) => {
+                let (version, plaintext) = crypto::decrypt_blob_any(form_privkeys, bytes)?;
+                Ok((version, Plaintext::Whole(plaintext)))
+            }
+            StoredBlob::PerQuestion(fields) => {
+                let mut oldest = 0;
+                let mut plaintexts = BTreeMap::new();
+                for (id, bytes) in fields {
+                    let (version, plaintext) = crypto::decrypt_blob_any(form_privkeys, bytes)
+                        .map_err(|e| FormsError::DecryptFailed(format!("{}: {}", id, e)))?;
+                    let index = form_privkeys.iter().position(|(v, _)| *v == version).unwrap_or(0);
+                    oldest = oldest.max(index);
+                    plaintexts.insert(id.clone(), plaintext);
+                }
+                let version = form_privkeys.get(oldest).map(|(v, _)| *v).ok_or("no keys configured")?;
+                Ok((version, Plaintext::PerQuestion(plaintexts)))
+            }
+        }
+    }
+}
+
+impl Plaintext {
+    /// Parse into an answers object. `max_len` caps the combined plaintext size; each
+    /// per-question answer is held to `limits` on its own. With `lenient`, JSON that strict
+    /// parsing rejects goes through `answers::recover_json`; the flag returned says whether
+    /// any part needed it.
+    pub fn into_answers(self, max_len: usize, limits: &JsonLimits, lenient: bool) -> Result<(Value, bool), FormsError> {
+        match self {
+            Plaintext::Whole(plaintext) => answers::parse_answers(&plaintext, max_len, limits, lenient),
+            Plaintext::PerQuestion(fields) => {
+                let total = fields.values().fold(0usize, |n, p| n.saturating_add(p.len()));
+                answers::check_plaintext_size(total, max_len)?;
+                let mut map = Map::with_capacity(fields.len());
+                let mut recovered = false;
+                for (id, plaintext) in fields {
+                    answers::check_json_shape(&plaintext, limits)?;
+                    let value = match serde_json::from_slice(&plaintext) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            let salvaged = std::str::from_utf8(&plaintext)
+                                .ok()
+                                .filter(|_| lenient)
+                                .and_then(answers::recover_json);
+                            recovered |= salvaged.is_some();
+                            salvaged.ok_or_else(|| {
+                                FormsError::InvalidInput(format!("Invalid JSON in decrypted answer {}: {}", id, e))
+                            })?
+                        }
+                    };
+                    map.insert(id, value);
+                }
+                Ok((Value::Object(map), recovered))
+            }
+        }
+    }
+
+    /// Encrypt to `form_pubkey`, keeping the layout, and return the string db-api stores.
+    pub fn encrypt(&self, form_pubkey: &PublicKey) -> Result<String, FormsError> {
+        match self {
+            Plaintext::Whole(plaintext) => Ok(hex::encode(crypto::encrypt_blob(form_pubkey, plaintext)?)),
+            Plaintext::PerQuestion(fields) => {
+                let encrypted = fields
+                    .iter()
+                    .map(|(id, plaintext)| Ok((id.as_str(), hex::encode(crypto::encrypt_blob(form_pubkey, plaintext)?))))
+                    .collect::<Result<BTreeMap<_, _>, FormsError>>()?;
+                Ok(serde_json::to_string(&encrypted)?)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> Vec<(&'static str, SecretKey)> {
+        vec![
+            ("current", SecretKey::parse(&[1u8; 32]).unwrap()),
+            ("previous", SecretKey::parse(&[2u8; 32]).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn per_question_round_trip() {
+        let keys = keys();
+        let pubkey = PublicKey::from_secret_key(&keys[0].1);
+        let plaintext = Plaintext::PerQuestion(BTreeMap::from([
+            ("q1".to_string(), br#""blue""#.to_vec()),
+            ("q2".to_string(), b"[1,2]".to_vec()),
+        ]));
+        let stored = plaintext.encrypt(&pubkey).unwrap();
+        assert!(is_per_question(&stored));
+
+        let (version, decrypted) = StoredBlob::parse(&stored).unwrap().decrypt(&keys).unwrap();
+        assert_eq!(version, "current");
+        assert_eq!(decrypted.into_answers(1024, &JsonLimits::default(), false).unwrap().0, serde_json::json!({ "q1": "blue", "q2": [1, 2] }));
+    }
+
+    #[test]
+    fn per_question_reports_oldest_key_version() {
+        let keys = keys();
+        let current = PublicKey::from_secret_key(&keys[0].1);
+        let previous = PublicKey::from_secret_key(&keys[1].1);
+        let stored = format!(
+            r#"{{"q1":"{}","q2":"{}"}}"#,
+            hex::encode(crypto::encrypt_blob(&current, b"1").unwrap()),
+            hex::encode(crypto::encrypt_blob(&previous, b"2").unwrap()),
+        );
+        let (version, _) = StoredBlob::parse(&stored).unwrap().decrypt(&keys).unwrap();
+        assert_eq!(version, "previous");
+    }
+
+    #[test]
+    fn retain_fields_skips_hidden_answers() {
+        let keys = keys();
+        let pubkey = PublicKey::from_secret_key(&keys[0].1);
+        let stored = format!(
+            r#"{{"q1":"{}","q2":"{}"}}"#,
+            hex::encode(crypto::encrypt_blob(&pubkey, b"1").unwrap()),
+            "00", // never decrypted, so never fails
+        );
+        let mut blob = StoredBlob::parse(&stored).unwrap();
+        blob.retain_fields(|id| id == "q1");
+        let (_, decrypted) = blob.decrypt(&keys).unwrap();
+        assert_eq!(decrypted.into_answers(1024, &JsonLimits::default(), false).unwrap().0, serde_json::json!({ "q1": 1 }));
+    }
+
+    #[test]
+    fn per_question_size_cap_covers_all_fields() {
+        let plaintext = Plaintext::PerQuestion(BTreeMap::from([
+            ("q1".to_string(), b"\"aaaa\"".to_vec()),
+            ("q2".to_string(), b"\"bbbb\"".to_vec()),
+        ]));
+        assert!(plaintext.into_answers(8, &JsonLimits::default(), false).unwrap_err().to_string().starts_with("Decrypted answers too large"));
+    }
+}
@@ -6,6 +6,7 @@
 //! - libsecp256k1 (not secp256k1 which has C bindings)
 //! - ECDH + ChaCha20-Poly1305 for encryption (EC01 format)
 
+use crate::error::FormsError;
 use chacha20poly1305::{
     aead::{Aead, KeyInit},
     ChaCha20Poly1305, Nonce,
@@ -19,7 +20,15 @@ use sha2::{Digest, Sha256};
 const ECDH_MAGIC: &[u8; 4] = b"EC01";
 
 /// Domain separation prefix for key derivation
-const DERIVATION_PREFIX: &[u8] = b"near-forms:v1:";
+pub const DERIVATION_PREFIX: &str = "near-forms:v1:";
+
+/// Name of the whole encryption contract GetMasterPublicKey advertises: additive form key
+/// derivation under [`DERIVATION_PREFIX`], then EC01 (ECDH + HKDF + ChaCha20-Poly1305)
+/// to the compressed form public key. A breaking change to either gets a new name.
+pub const SCHEME: &str = "near-forms:v1:ecdh";
+
+/// Longest tenant label accepted by [`validate_tenant`]
+pub const MAX_TENANT_LEN: usize = 64;
 
 /// secp256k1 curve order (big-endian)
 const CURVE_ORDER: [u8; 32] = [
@@ -68,7 +77,8 @@ fn reduce_mod_order(bytes: &[u8; 32]) -> [u8; 32] {
 
 /// Parse a hex-encoded private key.
 /// Trims whitespace to handle trailing newlines from Docker secrets, k8s ConfigMaps, or copy-paste.
-pub fn parse_private_key(hex_str: &str) -> Result<SecretKey, Box<dyn std::error::Error>> {
+/// Keys only come from the TEE environment, so failures are [`FormsError::Config`].
+pub fn parse_private_key(hex_str: &str) -> Result<SecretKey, FormsError> {
     let trimmed = hex_str.trim();
     if trimmed.len() != 64 {
         eprintln!(
@@ -76,46 +86,100 @@ pub fn parse_private_key(hex_str: &str) -> Result<SecretKey, Box<dyn std::error:
             trimmed.len(),
             hex_str.len()
         );
-        return Err(format!(
+        return Err(FormsError::Config(format!(
             "Private key must be exactly 64 hex characters (32 bytes), got {}",
             trimmed.len()
-        ).into());
+        )));
     }
-    let bytes = hex::decode(trimmed)?;
-    let privkey = SecretKey::parse_slice(&bytes)
-        .map_err(|e| format!("Invalid private key: {}", e))?;
+    // Neither message includes the decoder's detail: it would quote part of the key
+    let bytes = hex::decode(trimmed).map_err(|_| {
+        FormsError::Config("Private key is not valid hex (expected only 0-9 and a-f)".to_string())
+    })?;
+    let privkey = SecretKey::parse_slice(&bytes).map_err(|_| {
+        FormsError::Config(
+            "Private key is not a valid secp256k1 scalar (must be nonzero and below the curve order)".to_string(),
+        )
+    })?;
     Ok(privkey)
 }
 
+/// Check a tenant label for form key derivation: 1 to [`MAX_TENANT_LEN`] ASCII letters,
+/// digits, `.`, `_` or `-`. Excluding `:` keeps `tenant:form_id` unambiguous.
+pub fn validate_tenant(tenant: &str) -> Result<(), FormsError> {
+    let valid = !tenant.is_empty()
+        && tenant.len() <= MAX_TENANT_LEN
+        && tenant.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+    if !valid {
+        return Err(FormsError::Config(format!(
+            "FORM_KEY_TENANT must be 1 to {} characters of letters, digits, '.', '_' or '-'",
+            MAX_TENANT_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Everything hashed ahead of the form ID: [`DERIVATION_PREFIX`], then `tenant + ":"`
+/// when a tenant is configured.
+pub fn derivation_prefix(tenant: Option<&str>) -> String {
+    match tenant {
+        Some(tenant) => format!("{}{}:", DERIVATION_PREFIX, tenant),
+        None => DERIVATION_PREFIX.to_string(),
+    }
+}
+
+/// Compute the additive derivation tweak for a form: `SHA256(prefix + form_id) mod n`, or
+/// `SHA256(prefix + tenant + ":" + form_id) mod n` for a tenant-isolated deployment, with
+/// `":" + salt` appended when the form has a `derivation_salt`. Form IDs are UUIDs, so the
+/// trailing `:` cannot be confused with part of the ID. Without a tenant or salt the tweak
+/// is exactly the original one, so existing keys are unchanged.
+/// Shared by `derive_form_privkey` and `derive_form_pubkey` so both sides stay in lockstep.
+fn form_tweak(form_id: &str, tenant: Option<&str>, salt: Option<&str>) -> SecretKey {
+    let mut input = derivation_prefix(tenant).into_bytes();
+    input.extend_from_slice(form_id.as_bytes());
+    if let Some(salt) = salt {
+        input.push(b':');
+        input.extend_from_slice(salt.as_bytes());
+    }
+    hash_to_tweak(&input, |data| Sha256::digest(data).into())
+}
+
+/// Hash `input` to a nonzero scalar: `hash(input) mod n`, and in the ~2^-256 case where
+/// that is zero, `hash(input + ":" + counter) mod n` for counter 1, 2, ... until nonzero.
+///
+/// Reducing mod n (rather than rejecting values >= n, as `SecretKey::parse_slice` alone
+/// would) matches TypeScript's `bytesToBigInt(hash) % CURVE_ORDER` for every input. A zero tweak would make derivation a no-op, so it is rehashed instead;
+/// web-ui's `deriveFormPublicKey()` uses the same counter suffix. No real input reaches
+/// the loop (`hash` is a parameter so tests can), so every existing key is unchanged.
+fn hash_to_tweak(input: &[u8], hash: impl Fn(&[u8]) -> [u8; 32]) -> SecretKey {
+    let mut digest = hash(input);
+    let mut counter = 0u32;
+    loop {
+        if let Ok(tweak) = SecretKey::parse_slice(&reduce_mod_order(&digest)) {
+            return tweak;
+        }
+        counter += 1;
+        let mut retry = input.to_vec();
+        retry.extend_from_slice(format!(":{}", counter).as_bytes());
+        digest = hash(&retry);
+    }
+}
+
 /// Derive a form-specific private key from master private key
 ///
 /// Uses additive key derivation:
-///   form_privkey = master_privkey + SHA256(prefix + form_id)
+///   form_privkey = master_privkey + SHA256(prefix + [tenant + ":"] + form_id + [":" + salt])
 ///
 /// This must match the public key derivation in web-ui's `deriveFormPublicKey()`.
+/// Changing `tenant` changes every derived key: submissions encrypted under the old
+/// tenant no longer decrypt. `salt` is the form's `derivation_salt` from db-api (None for
+/// forms created before the column existed).
 pub fn derive_form_privkey(
     master_privkey: &SecretKey,
     form_id: &str,
-) -> Result<SecretKey, Box<dyn std::error::Error>> {
-    // Create deterministic tweak from form_id
-    let mut hasher = Sha256::new();
-    hasher.update(DERIVATION_PREFIX);
-    hasher.update(form_id.as_bytes());
-    let tweak_bytes: [u8; 32] = hasher.finalize().into();
-
-    // Reduce mod curve order to match TypeScript's `bytesToBigInt(hash) % CURVE_ORDER`.
-    // This differs from near-email which passes raw SHA256 output to SecretKey::parse_slice
-    // (which rejects values >= curve order). Our approach ensures Rust and TypeScript derive
-    // identical keys for all inputs, not just the ~(1 - 2^-128) that happen to be in range.
-    //
-    // Note: if the reduced tweak is zero (~2^-256 probability), both Rust
-    // (SecretKey::parse_slice rejects zero) and TypeScript (explicit zero check)
-    // would return an error. The probability is astronomically small.
-    let reduced = reduce_mod_order(&tweak_bytes);
-
-    // Convert tweak to SecretKey (which is a scalar)
-    let tweak = SecretKey::parse_slice(&reduced)
-        .map_err(|e| format!("Failed to create tweak: {}", e))?;
+    tenant: Option<&str>,
+    salt: Option<&str>,
+) -> Result<SecretKey, FormsError> {
+    let tweak = form_tweak(form_id, tenant, salt);
 
     // Add tweak to private key (scalar addition)
     let mut user_privkey = *master_privkey;
@@ -125,6 +189,28 @@ pub fn derive_form_privkey(
     Ok(user_privkey)
 }
 
+/// Derive a form-specific public key from the master public key
+///
+/// Public counterpart of `derive_form_privkey`:
+///   form_pubkey = master_pubkey + SHA256(prefix + [tenant + ":"] + form_id + [":" + salt])·G
+///
+/// Lets clients encrypt to the form key without implementing the derivation themselves.
+pub fn derive_form_pubkey(
+    master_pubkey: &PublicKey,
+    form_id: &str,
+    tenant: Option<&str>,
+    salt: Option<&str>,
+) -> Result<PublicKey, FormsError> {
+    let tweak = form_tweak(form_id, tenant, salt);
+
+    // Add tweak·G to the public key (point addition)
+    let mut form_pubkey = *master_pubkey;
+    form_pubkey.tweak_add_assign(&tweak)
+        .map_err(|e| format!("Failed to derive public key: {}", e))?;
+
+    Ok(form_pubkey)
+}
+
 /// Decrypt form submission data using EC01 format
 ///
 /// Format:
@@ -132,14 +218,38 @@ pub fn derive_form_privkey(
 /// - Ephemeral public key: 33 bytes (compressed)
 /// - Nonce: 12 bytes
 /// - ChaCha20-Poly1305 ciphertext + tag: remaining bytes
+///
+/// Every failure is [`FormsError::DecryptFailed`] carrying the detail.
 pub fn decrypt_blob(
     form_privkey: &SecretKey,
     encrypted: &[u8],
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+) -> Result<Vec<u8>, FormsError> {
     if encrypted.len() <= 4 || &encrypted[0..4] != ECDH_MAGIC {
-        return Err("Invalid encryption format: expected EC01 magic bytes".into());
+        return Err(FormsError::DecryptFailed("Invalid encryption format: expected EC01 magic bytes".to_string()));
+    }
+    decrypt_ecdh(form_privkey, encrypted).map_err(FormsError::DecryptFailed)
+}
+
+/// Decrypt with the first of several form keys that succeeds (newest first).
+///
+/// Used during master key rotation: each entry is `(key_version, form_privkey)`.
+/// Returns the version that decrypted the blob, or the last error if none did.
+pub fn decrypt_blob_any<'a>(
+    form_privkeys: &[(&'a str, SecretKey)],
+    encrypted: &[u8],
+) -> Result<(&'a str, Vec<u8>), FormsError> {
+    let mut last_err: Option<FormsError> = None;
+    for (version, form_privkey) in form_privkeys {
+        match decrypt_blob(form_privkey, encrypted) {
+            Ok(plaintext) => return Ok((version, plaintext)),
+            Err(e) => last_err = Some(e),
+        }
     }
-    decrypt_ecdh(form_privkey, encrypted)
+    Err(FormsError::DecryptFailed(format!(
+        "no key version could decrypt ({} tried): {}",
+        form_privkeys.len(),
+        last_err.map(|e| e.to_string()).unwrap_or_else(|| "no keys configured".to_string())
+    )))
 }
 
 /// Decrypt data using ECDH + ChaCha20-Poly1305 (EC01 format)
@@ -148,23 +258,34 @@ pub fn decrypt_blob(
 fn decrypt_ecdh(
     user_privkey: &SecretKey,
     encrypted: &[u8],
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+) -> Result<Vec<u8>, String> {
     const HEADER_SIZE: usize = 4;       // EC01
     const PUBKEY_SIZE: usize = 33;      // compressed pubkey
     const NONCE_SIZE: usize = 12;
     const TAG_SIZE: usize = 16;         // Poly1305 tag
-    const MIN_SIZE: usize = HEADER_SIZE + PUBKEY_SIZE + NONCE_SIZE + TAG_SIZE;
+    const PREFIX_SIZE: usize = HEADER_SIZE + PUBKEY_SIZE + NONCE_SIZE;
 
-    if encrypted.len() < MIN_SIZE {
+    if encrypted.len() < PREFIX_SIZE {
         return Err(format!(
             "EC01 data too short: {} bytes, need at least {}",
-            encrypted.len(), MIN_SIZE
-        ).into());
+            encrypted.len(), PREFIX_SIZE + TAG_SIZE
+        ));
+    }
+    // Checked separately so a truncated body reports a clear error instead of an opaque
+    // AEAD failure from ChaCha20-Poly1305. Lengths are public, so no constant-time concern.
+    let body_len = encrypted.len() - PREFIX_SIZE;
+    if body_len < TAG_SIZE {
+        return Err(format!(
+            "EC01 ciphertext shorter than authentication tag: {} bytes, need at least {}",
+            body_len, TAG_SIZE
+        ));
     }
 
-    // Parse ephemeral public key
-    let ephemeral_pubkey_bytes = &encrypted[HEADER_SIZE..HEADER_SIZE + PUBKEY_SIZE];
-    let mut shared_point = PublicKey::parse_slice(ephemeral_pubkey_bytes, None)
+    // Parse ephemeral public key; only the compressed (0x02/0x03) encoding is accepted
+    let ephemeral_pubkey_bytes: &[u8; PUBKEY_SIZE] = encrypted[HEADER_SIZE..HEADER_SIZE + PUBKEY_SIZE]
+        .try_into()
+        .map_err(|_| "EC01 ephemeral pubkey slice has the wrong length")?;
+    let mut shared_point = PublicKey::parse_compressed(ephemeral_pubkey_bytes)
         .map_err(|e| format!("Invalid ephemeral pubkey: {}", e))?;
 
     // ECDH: shared_point = ephemeral_pubkey * user_privkey
@@ -203,19 +324,23 @@ fn decrypt_ecdh(
 }
 
 /// Parse a hex-encoded compressed secp256k1 public key (66 hex chars = 33 bytes).
-/// Trims whitespace for robustness.
-pub fn parse_public_key(hex_str: &str) -> Result<PublicKey, Box<dyn std::error::Error>> {
+/// Trims whitespace for robustness. Keys come from callers, so failures are
+/// [`FormsError::InvalidInput`].
+pub fn parse_public_key(hex_str: &str) -> Result<PublicKey, FormsError> {
     let trimmed = hex_str.trim();
     if trimmed.len() != 66 {
-        return Err(format!(
+        return Err(FormsError::InvalidInput(format!(
             "Expected 66-char compressed public key hex, got {} chars",
             trimmed.len()
-        )
-        .into());
+        )));
     }
-    let bytes = hex::decode(trimmed)?;
-    let pubkey = PublicKey::parse_compressed(&bytes.try_into().map_err(|_| "unexpected length")?)
-        .map_err(|e| format!("Invalid public key: {}", e))?;
+    let bytes = hex::decode(trimmed)
+        .map_err(|e| FormsError::InvalidInput(format!("Invalid public key hex: {}", e)))?;
+    let bytes: [u8; 33] = bytes
+        .try_into()
+        .map_err(|_| FormsError::InvalidInput("Invalid public key: unexpected length".to_string()))?;
+    let pubkey = PublicKey::parse_compressed(&bytes)
+        .map_err(|e| FormsError::InvalidInput(format!("Invalid public key: {}", e)))?;
     Ok(pubkey)
 }
 
@@ -228,7 +353,7 @@ pub fn parse_public_key(hex_str: &str) -> Result<PublicKey, Box<dyn std::error::
 pub fn encrypt_blob(
     target_pubkey: &PublicKey,
     plaintext: &[u8],
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+) -> Result<Vec<u8>, FormsError> {
     const MAX_PLAINTEXT: usize = 8 * 1024 * 1024; // 8 MB
     if plaintext.len() > MAX_PLAINTEXT {
         return Err(format!(
@@ -284,6 +409,68 @@ pub fn encrypt_blob(
     Ok(output)
 }
 
+/// Known-answer vector for [`self_test`] (also checked by the tests): master key 1,
+/// the seeded FORM_ID, no tenant. Generated outside this crate, so it pins compatibility
+/// with other EC01 implementations, not just self-consistency.
+const SELF_TEST_MASTER_KEY: [u8; 32] = {
+    let mut key = [0u8; 32];
+    key[31] = 1;
+    key
+};
+const SELF_TEST_FORM_ID: &str = "daf14a0c-20f7-4199-a07b-c6456d53ef2d";
+const SELF_TEST_PLAINTEXT: &[u8] = br#"{"q1":"Alice","q2":["a","c"],"q3":42}"#;
+const SELF_TEST_FORM_PRIVKEY: &str = "9dd31d0bb45adf39d1f8518c9ba2e9e8c7399def3a233b6631c075e19a3ad542";
+/// Ephemeral key 0x11 * 32, nonce 00..0b
+const SELF_TEST_CIPHERTEXT: &str = "45433031034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa000102030405060708090a0be72a3b736bf7c38f7be46ff74813aa9aaeef5501ca4e26ed4640ba2ff32c6cf5b272a7a13ce16996c715ca756786cbaebfeb7cf72a";
+
+/// Check key derivation and EC01 against a fixed vector, so a broken build or mismatched
+/// dependency fails loudly instead of storing submissions nobody can decrypt.
+///
+/// Checks that form key derivation is deterministic and matches the known answer (private
+/// and public side), that the known ciphertext decrypts, and that a fresh encryption
+/// round-trips and is randomized. Uses only the fixed test key, never a configured one.
+pub fn self_test() -> Result<(), FormsError> {
+    let failed = |check: &str| FormsError::Internal(format!("crypto self-test failed: {}", check));
+
+    let master = SecretKey::parse(&SELF_TEST_MASTER_KEY).map_err(|_| failed("fixed master key rejected"))?;
+    let form_privkey = derive_form_privkey(&master, SELF_TEST_FORM_ID, None, None)
+        .map_err(|e| failed(&format!("form key derivation errored ({})", e)))?;
+    let again = derive_form_privkey(&master, SELF_TEST_FORM_ID, None, None)
+        .map_err(|e| failed(&format!("form key derivation errored ({})", e)))?;
+    if form_privkey.serialize() != again.serialize() {
+        return Err(failed("form key derivation is not deterministic"));
+    }
+    if hex::encode(form_privkey.serialize()) != SELF_TEST_FORM_PRIVKEY {
+        return Err(failed("form key derivation does not match the known answer"));
+    }
+    let form_pubkey = PublicKey::from_secret_key(&form_privkey);
+    let derived_pubkey = derive_form_pubkey(&PublicKey::from_secret_key(&master), SELF_TEST_FORM_ID, None, None)
+        .map_err(|e| failed(&format!("form public key derivation errored ({})", e)))?;
+    if derived_pubkey != form_pubkey {
+        return Err(failed("form public key derivation does not match the private key"));
+    }
+
+    let known = hex::decode(SELF_TEST_CIPHERTEXT).map_err(|_| failed("fixed ciphertext is not hex"))?;
+    match decrypt_blob(&form_privkey, &known) {
+        Ok(plaintext) if plaintext == SELF_TEST_PLAINTEXT => {}
+        Ok(_) => return Err(failed("known ciphertext decrypted to the wrong plaintext")),
+        Err(e) => return Err(failed(&format!("known ciphertext did not decrypt ({})", e))),
+    }
+
+    let first = encrypt_blob(&form_pubkey, SELF_TEST_PLAINTEXT)
+        .map_err(|e| failed(&format!("encryption errored ({})", e)))?;
+    let second = encrypt_blob(&form_pubkey, SELF_TEST_PLAINTEXT)
+        .map_err(|e| failed(&format!("encryption errored ({})", e)))?;
+    if first == second {
+        return Err(failed("two encryptions are identical (randomness source is broken)"));
+    }
+    match decrypt_blob(&form_privkey, &first) {
+        Ok(plaintext) if plaintext == SELF_TEST_PLAINTEXT => Ok(()),
+        Ok(_) => Err(failed("round trip decrypted to the wrong plaintext")),
+        Err(e) => Err(failed(&format!("round trip did not decrypt ({})", e))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,8 +539,8 @@ mod tests {
     fn derive_form_privkey_deterministic() {
         let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
 
-        let derived1 = derive_form_privkey(&master_key, TEST_FORM_ID).unwrap();
-        let derived2 = derive_form_privkey(&master_key, TEST_FORM_ID).unwrap();
+        let derived1 = derive_form_privkey(&master_key, TEST_FORM_ID, None, None).unwrap();
+        let derived2 = derive_form_privkey(&master_key, TEST_FORM_ID, None, None).unwrap();
 
         assert_eq!(derived1.serialize(), derived2.serialize());
     }
@@ -362,8 +549,8 @@ mod tests {
     fn derive_form_privkey_different_forms_different_keys() {
         let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
 
-        let key1 = derive_form_privkey(&master_key, "form-a").unwrap();
-        let key2 = derive_form_privkey(&master_key, "form-b").unwrap();
+        let key1 = derive_form_privkey(&master_key, "form-a", None, None).unwrap();
+        let key2 = derive_form_privkey(&master_key, "form-b", None, None).unwrap();
 
         assert_ne!(key1.serialize(), key2.serialize());
     }
@@ -372,7 +559,7 @@ mod tests {
     fn derive_then_encrypt_decrypt() {
         // Simulates the full flow: derive form key, encrypt to form pubkey, decrypt with form privkey
         let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
-        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID).unwrap();
+        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID, None, None).unwrap();
         let form_pubkey = PublicKey::from_secret_key(&form_privkey);
 
         let plaintext = b"{\"q1\": \"answer\"}";
@@ -424,6 +611,42 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn decrypt_any_reports_matching_key_version() {
+        let current = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let previous = parse_private_key(&"22".repeat(32)).unwrap();
+        let keys = [("current", current), ("previous", previous)];
+
+        let old_blob = encrypt_blob(&PublicKey::from_secret_key(&previous), b"old").unwrap();
+        let (version, plaintext) = decrypt_blob_any(&keys, &old_blob).unwrap();
+        assert_eq!(version, "previous");
+        assert_eq!(plaintext, b"old");
+
+        let new_blob = encrypt_blob(&PublicKey::from_secret_key(&current), b"new").unwrap();
+        assert_eq!(decrypt_blob_any(&keys, &new_blob).unwrap().0, "current");
+    }
+
+    #[test]
+    fn decrypt_any_fails_when_no_key_matches() {
+        let current = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let other = parse_private_key(&"33".repeat(32)).unwrap();
+        let blob = encrypt_blob(&PublicKey::from_secret_key(&other), b"x").unwrap();
+        let err = decrypt_blob_any(&[("current", current)], &blob).unwrap_err().to_string();
+        assert!(err.contains("1 tried"), "got: {}", err);
+    }
+
+    #[test]
+    fn truncated_tag_reports_clear_error() {
+        let privkey = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let pubkey = PublicKey::from_secret_key(&privkey);
+        let encrypted = encrypt_blob(&pubkey, b"{}").unwrap();
+        // Keep the 49-byte prefix plus 10 bytes — less than a full Poly1305 tag
+        let truncated = &encrypted[..4 + 33 + 12 + 10];
+        let err = decrypt_blob(&privkey, truncated).unwrap_err().to_string();
+        assert!(err.contains("shorter than authentication tag"), "got: {}", err);
+        assert!(err.contains("10 bytes"));
+    }
+
     #[test]
     fn reduce_mod_order_below_order_unchanged() {
         // A value well below curve order should be returned unchanged
@@ -471,7 +694,7 @@ mod tests {
     fn full_submit_read_decrypt_flow() {
         // Setup: master key and form key derivation
         let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
-        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID).unwrap();
+        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID, None, None).unwrap();
         let form_pubkey = PublicKey::from_secret_key(&form_privkey);
 
         // Step 1-2: Client encrypts form answers with form public key
@@ -535,6 +758,21 @@ mod tests {
         assert!(parse_private_key("deadbeef").is_err());
     }
 
+    #[test]
+    fn parse_private_key_explains_bad_hex_and_bad_scalar() {
+        let not_hex = format!("{}zz", &TEST_MASTER_KEY[..62]);
+        let err = parse_private_key(&not_hex).unwrap_err().to_string();
+        assert!(err.contains("not valid hex"), "{}", err);
+        assert!(!err.contains("zz") && !err.contains(&TEST_MASTER_KEY[..8]), "{}", err);
+
+        // Zero and the curve order itself are 64 valid hex chars but not usable scalars
+        let curve_order = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        for scalar in ["0".repeat(64).as_str(), curve_order] {
+            let err = parse_private_key(scalar).unwrap_err().to_string();
+            assert!(err.contains("not a valid secp256k1 scalar"), "{}", err);
+        }
+    }
+
     #[test]
     fn encrypt_blob_rejects_over_8mb() {
         let privkey = parse_private_key(TEST_MASTER_KEY).unwrap();
@@ -561,6 +799,22 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("ephemeral pubkey"));
     }
 
+    #[test]
+    fn decrypt_rejects_uncompressed_ephemeral_pubkey() {
+        // A valid blob whose ephemeral key is swapped for the same point's uncompressed
+        // encoding: the 0x04 prefix must be refused, not reinterpreted by length
+        let privkey = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let pubkey = PublicKey::from_secret_key(&privkey);
+        let blob = encrypt_blob(&pubkey, b"answers").unwrap();
+        let ephemeral = PublicKey::parse_compressed(blob[4..37].try_into().unwrap()).unwrap();
+
+        let mut bad_blob = b"EC01".to_vec();
+        bad_blob.extend_from_slice(&ephemeral.serialize());
+        bad_blob.extend_from_slice(&blob[37..]);
+        let err = decrypt_blob(&privkey, &bad_blob).unwrap_err();
+        assert!(err.to_string().contains("ephemeral pubkey"), "{}", err);
+    }
+
     /// Golden test vector: master key 0x01 with form_id "daf14a0c-..."
     /// This derives a deterministic form private key that TypeScript must match.
     #[test]
@@ -568,7 +822,7 @@ mod tests {
         let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
         let master_pubkey = PublicKey::from_secret_key(&master_key);
 
-        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID).unwrap();
+        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID, None, None).unwrap();
         let form_pubkey = PublicKey::from_secret_key(&form_privkey);
 
         let master_pubkey_hex = hex::encode(master_pubkey.serialize_compressed());
@@ -585,5 +839,152 @@ mod tests {
             "02257731f1d53b68b0c8e8602250746131b1b037556343b4f666c9ac753e5cc4ea"
         );
     }
-}
 
+    #[test]
+    fn derive_form_pubkey_matches_privkey_derivation() {
+        for master_hex in [TEST_MASTER_KEY.to_string(), "22".repeat(32), "7f".repeat(32)] {
+            let master_key = parse_private_key(&master_hex).unwrap();
+            let master_pubkey = PublicKey::from_secret_key(&master_key);
+            for form_id in [TEST_FORM_ID, "another-form"] {
+                let expected =
+                    PublicKey::from_secret_key(&derive_form_privkey(&master_key, form_id, None, None).unwrap());
+                let derived = derive_form_pubkey(&master_pubkey, form_id, None, None).unwrap();
+                assert_eq!(derived.serialize_compressed(), expected.serialize_compressed());
+            }
+        }
+    }
+
+    /// Golden test vector for tenant "acme": TypeScript must match, and it must differ
+    /// from the untenanted key for the same master key and form.
+    #[test]
+    fn golden_derive_form_pubkey_with_tenant() {
+        let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let master_pubkey = PublicKey::from_secret_key(&master_key);
+
+        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID, Some("acme"), None).unwrap();
+        let form_pubkey = PublicKey::from_secret_key(&form_privkey);
+        assert_eq!(
+            derive_form_pubkey(&master_pubkey, TEST_FORM_ID, Some("acme"), None).unwrap().serialize_compressed(),
+            form_pubkey.serialize_compressed()
+        );
+        assert_eq!(hex::encode(form_pubkey.serialize_compressed()), "03c8d3bc21043073291acb6672dd4476fcf001c66bfb32bb1766460051f16bb4dd");
+
+        let untenanted = derive_form_pubkey(&master_pubkey, TEST_FORM_ID, None, None).unwrap();
+        assert_ne!(untenanted.serialize_compressed(), form_pubkey.serialize_compressed());
+    }
+
+    /// Golden test vector for a salted form: TypeScript must match, and the salt must
+    /// change the key for the same master key and form ID.
+    #[test]
+    fn golden_derive_form_pubkey_with_salt() {
+        const SALT: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+        let master_key = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let master_pubkey = PublicKey::from_secret_key(&master_key);
+
+        let form_privkey = derive_form_privkey(&master_key, TEST_FORM_ID, None, Some(SALT)).unwrap();
+        let form_pubkey = PublicKey::from_secret_key(&form_privkey);
+        assert_eq!(
+            derive_form_pubkey(&master_pubkey, TEST_FORM_ID, None, Some(SALT)).unwrap().serialize_compressed(),
+            form_pubkey.serialize_compressed()
+        );
+        assert_eq!(hex::encode(form_pubkey.serialize_compressed()), "03cf456917c7b881866a25be81469438dd8bfc2933b547c475bb8bf9dc307a8f98");
+
+        let unsalted = derive_form_pubkey(&master_pubkey, TEST_FORM_ID, None, None).unwrap();
+        assert_ne!(unsalted.serialize_compressed(), form_pubkey.serialize_compressed());
+        let tenanted = derive_form_pubkey(&master_pubkey, TEST_FORM_ID, Some("acme"), Some(SALT)).unwrap();
+        assert_ne!(tenanted.serialize_compressed(), form_pubkey.serialize_compressed());
+    }
+
+    #[test]
+    fn tenant_labels_are_validated() {
+        for ok in ["acme", "tenant-2", "eu.prod_1", &"a".repeat(MAX_TENANT_LEN)] {
+            assert!(validate_tenant(ok).is_ok(), "{}", ok);
+        }
+        for bad in ["", "a:b", "with space", "ünïcode", &"a".repeat(MAX_TENANT_LEN + 1)] {
+            let err = validate_tenant(bad).unwrap_err();
+            assert_eq!(err.code(), "config", "{}", bad);
+        }
+    }
+
+    #[test]
+    fn zero_tweak_is_rehashed_with_counter() {
+        let sha = |data: &[u8]| -> [u8; 32] { Sha256::digest(data).into() };
+        // Digests that reduce to zero (0 itself, and n) for the first two attempts
+        let crafted = |data: &[u8]| -> [u8; 32] {
+            match data {
+                b"form" => CURVE_ORDER,
+                b"form:1" => [0u8; 32],
+                other => sha(other),
+            }
+        };
+        let tweak = hash_to_tweak(b"form", crafted);
+        assert_eq!(tweak.serialize(), reduce_mod_order(&sha(b"form:2")));
+
+        // The ordinary path is the plain reduced hash
+        assert_eq!(hash_to_tweak(b"form", sha).serialize(), reduce_mod_order(&sha(b"form")));
+    }
+
+    #[test]
+    fn derivation_prefix_includes_tenant() {
+        assert_eq!(derivation_prefix(None), "near-forms:v1:");
+        assert_eq!(derivation_prefix(Some("acme")), "near-forms:v1:acme:");
+    }
+
+    // Known-answer vectors for the whole read path: master key → form key → EC01 plaintext.
+    // The ciphertexts were produced outside this crate (Python `cryptography`: secp256k1 ECDH,
+    // HKDF-SHA256, ChaCha20-Poly1305) from fixed ephemeral keys and nonces, following the format
+    // described on `decrypt_blob`. If any of these fail after a dependency bump or refactor, the
+    // derivation prefix, HKDF info, or AEAD changed, and stored submissions no longer decrypt.
+    // The untenanted vector (ephemeral key 0x11 * 32, nonce 00..0b) is the one `self_test` uses.
+
+    /// Tenant "acme"; ephemeral key 0x22 * 32, nonce 0c..17
+    const VECTOR_TENANT_FORM_PRIVKEY: &str = "738fd06e20f77784c2a885200ea23a08ff920726077c7c15278b9547d58c0e4a";
+    const VECTOR_TENANT_CIPHERTEXT: &str = "4543303102466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f270c0d0e0f1011121314151617fc12b3b8fcd7378425e9adf1e3464f6a04ef24c2250895ab8783411b1ecb2b2eeb5ac9ce1774600f4d7cb3cc5afbf5076edf99c345";
+
+    #[test]
+    fn self_test_passes() {
+        assert_eq!(hex::encode(SELF_TEST_MASTER_KEY), TEST_MASTER_KEY);
+        assert_eq!(SELF_TEST_FORM_ID, TEST_FORM_ID);
+        self_test().unwrap();
+    }
+
+    #[test]
+    fn vector_form_key_derivation() {
+        let master = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let form_privkey = derive_form_privkey(&master, TEST_FORM_ID, None, None).unwrap();
+        assert_eq!(hex::encode(form_privkey.serialize()), SELF_TEST_FORM_PRIVKEY);
+        let form_privkey = derive_form_privkey(&master, TEST_FORM_ID, Some("acme"), None).unwrap();
+        assert_eq!(hex::encode(form_privkey.serialize()), VECTOR_TENANT_FORM_PRIVKEY);
+    }
+
+    #[test]
+    fn vector_decrypts_fixed_ciphertext() {
+        let master = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let form_privkey = derive_form_privkey(&master, TEST_FORM_ID, None, None).unwrap();
+        let plaintext = decrypt_blob(&form_privkey, &hex::decode(SELF_TEST_CIPHERTEXT).unwrap()).unwrap();
+        assert_eq!(plaintext, SELF_TEST_PLAINTEXT);
+    }
+
+    #[test]
+    fn vector_decrypts_fixed_tenant_ciphertext() {
+        let master = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let form_privkey = derive_form_privkey(&master, TEST_FORM_ID, Some("acme"), None).unwrap();
+        let plaintext = decrypt_blob(&form_privkey, &hex::decode(VECTOR_TENANT_CIPHERTEXT).unwrap()).unwrap();
+        assert_eq!(plaintext, SELF_TEST_PLAINTEXT);
+
+        // Keys are not interchangeable across tenants
+        let untenanted = derive_form_privkey(&master, TEST_FORM_ID, None, None).unwrap();
+        assert!(decrypt_blob(&untenanted, &hex::decode(VECTOR_TENANT_CIPHERTEXT).unwrap()).is_err());
+    }
+
+    #[test]
+    fn vector_rotation_reports_the_key_version() {
+        let master = parse_private_key(TEST_MASTER_KEY).unwrap();
+        let keys = [
+            ("current", derive_form_privkey(&parse_private_key(&"22".repeat(32)).unwrap(), TEST_FORM_ID, None, None).unwrap()),
+            ("previous", derive_form_privkey(&master, TEST_FORM_ID, None, None).unwrap()),
+        ];
+        let (version, plaintext) = decrypt_blob_any(&keys, &hex::decode(SELF_TEST_CIPHERTEXT).unwrap()).unwrap();
+        assert_eq!((version, plaintext.as_slice()), ("previous", SELF_TEST_PLAINTEXT));
+    }
+}
@@ -3,44 +3,224 @@
 //! Fetches encrypted form submissions and stores new submissions via HTTP API.
 //! Uses chunked HTTP writes for POST requests to bypass the ~4KB WASI-HTTP single-write limit.
 //! Uses low-level wasi::http for GET requests to set all three timeout types.
+//!
+//! The request/response handling is written against the [`HttpClient`] trait so it can be
+//! exercised natively: [`WasiHttpClient`] is the real transport, tests inject a stub.
 
-use crate::http_chunked;
-use crate::types::{FormMetadata, SubmissionsPage};
-use std::time::Duration;
+use crate::error::FormsError;
+use crate::http_chunked::{self, block_until, http_error, request_options};
+use crate::timing::HttpTimeouts;
+use crate::types::{CreatedSubmission, EncryptedSubmission, FormDerivationSalt, FormMetadata, FormOwner, FormOwners, FormVersion, SubmissionsCursorPage, SubmissionsPage};
 use wasi::http::{
     outgoing_handler,
-    types::{Headers, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme},
+    types::{Headers, Method, OutgoingBody, OutgoingRequest, Scheme},
 };
 
-/// HTTP request timeout (connect, first-byte, and between-bytes)
-const TIMEOUT: Duration = Duration::from_secs(30);
+/// Status, `Content-Type` and body of one db-api response
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// `Content-Type` header, if one was sent
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Whether the body is an HTML page, which db-api never sends: a reverse proxy or
+    /// gateway in front of it answered instead (e.g. nginx's 502 page)
+    fn is_html(&self) -> bool {
+        self.content_type
+            .as_deref()
+            .is_some_and(|ct| ct.trim_start().to_ascii_lowercase().starts_with("text/html"))
+    }
+
+    /// Detail for logs: the start of db-api's body, or a one-line summary of a gateway's
+    /// HTML error page (its `<title>`, when it has one) instead of raw markup
+    fn error_detail(&self) -> String {
+        if !self.is_html() {
+            return snippet(&self.body);
+        }
+        match html_title(&self.body) {
+            Some(title) => format!("upstream gateway error: HTML page \"{}\" ({} bytes)", title, self.body.len()),
+            None => format!("upstream gateway error: HTML page ({} bytes)", self.body.len()),
+        }
+    }
+}
+
+/// Text of the first `<title>` element, whitespace-collapsed and cut to 100 chars
+fn html_title(body: &[u8]) -> Option<String> {
+    let html = String::from_utf8_lossy(&body[..body.len().min(4096)]);
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title: String = html[start..end].split_whitespace().collect::<Vec<_>>().join(" ").chars().take(100).collect();
+    (!title.is_empty()).then_some(title)
+}
+
+/// Transport for db-api calls: send a request, return the [`HttpResponse`].
+///
+/// Implementations report transport failures (connect, timeout, body read) as `Err`;
+/// HTTP error statuses are returned as `Ok` so the caller can map them.
+pub trait HttpClient {
+    /// GET `url` with `headers`.
+    fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError>;
+
+    /// POST a JSON `body` to `url`, sending `API-Secret` when given.
+    fn post_json(
+        &self,
+        url: &str,
+        body: &[u8],
+        api_secret: Option<&str>,
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError>;
+
+    /// PUT a JSON `body` to `url`, sending `API-Secret` when given.
+    fn put_json(
+        &self,
+        url: &str,
+        body: &[u8],
+        api_secret: Option<&str>,
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError>;
+}
+
+/// [`HttpClient`] over wasi:http (only works inside the WASI runtime).
+pub struct WasiHttpClient;
+
+impl HttpClient for WasiHttpClient {
+    fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError> {
+        get_with_timeout(url, timeouts, headers)
+    }
+
+    fn post_json(
+        &self,
+        url: &str,
+        body: &[u8],
+        api_secret: Option<&str>,
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError> {
+        let response = http_chunked::post_chunked(url, "application/json", body, timeouts, api_secret)?;
+        Ok(HttpResponse {
+            status: response.status(),
+            content_type: response.content_type().map(str::to_string),
+            body: response.body().to_vec(),
+        })
+    }
+
+    fn put_json(
+        &self,
+        url: &str,
+        body: &[u8],
+        api_secret: Option<&str>,
+        timeouts: &HttpTimeouts,
+    ) -> Result<HttpResponse, FormsError> {
+        let response = http_chunked::put_chunked(url, "application/json", body, timeouts, api_secret)?;
+        Ok(HttpResponse {
+            status: response.status(),
+            content_type: response.content_type().map(str::to_string),
+            body: response.body().to_vec(),
+        })
+    }
+}
+
+/// Build URL for GET /v1/health
+fn health_url(api_url: &str) -> String {
+    format!("{}/v1/health", api_url)
+}
 
 /// Build URL for GET /v1/forms/{form_id}
 fn form_url(api_url: &str, form_id: &str) -> String {
     format!("{}/v1/forms/{}", api_url, form_id)
 }
 
-/// Build URL for GET /v1/forms/{form_id}/submissions?offset=N&limit=N
-fn submissions_url(api_url: &str, form_id: &str, offset: u32, limit: u32) -> String {
+/// Build URL for GET /v1/forms/{form_id}/owners
+fn form_owners_url(api_url: &str, form_id: &str) -> String {
+    format!("{}/v1/forms/{}/owners", api_url, form_id)
+}
+
+/// Build URL for PUT /v1/forms/{form_id}/public-key
+fn form_public_key_url(api_url: &str, form_id: &str) -> String {
+    format!("{}/v1/forms/{}/public-key", api_url, form_id)
+}
+
+/// Build URL for GET /v1/forms/{form_id}/derivation-salt
+fn form_derivation_salt_url(api_url: &str, form_id: &str) -> String {
+    format!("{}/v1/forms/{}/derivation-salt", api_url, form_id)
+}
+
+/// Build URL for GET /v1/forms/{form_id}/versions/{version}
+fn form_version_url(api_url: &str, form_id: &str, version: i32) -> String {
+    format!("{}/v1/forms/{}/versions/{}", api_url, form_id, version)
+}
+
+/// Build URL for GET /v1/forms/{form_id}/submissions?offset=N&limit=N&with_total=true
+/// (total_count is opt-in on db-api; ReadResponses always needs it for pagination)
+fn submissions_url(api_url: &str, form_id: &str, offset: u32, limit: u32, include_drafts: bool, since: Option<&str>) -> String {
+    format!(
+        "{}/v1/forms/{}/submissions?offset={}&limit={}&with_total=true{}{}",
+        api_url, form_id, offset, limit, drafts_query(include_drafts), since_query(since)
+    )
+}
+
+/// `&include_drafts=true` when drafts are wanted (db-api leaves them out by default)
+fn drafts_query(include_drafts: bool) -> &'static str {
+    if include_drafts { "&include_drafts=true" } else { "" }
+}
+
+/// `&since=<ts>` for an incremental read. `since` must already have passed
+/// `validation::is_utc_timestamp`, so it needs no escaping.
+fn since_query(since: Option<&str>) -> String {
+    since.map(|ts| format!("&since={}", ts)).unwrap_or_default()
+}
+
+/// Build URL for GET /v1/forms/{form_id}/submissions?cursor=C&limit=N (keyset page after C)
+fn submissions_after_url(api_url: &str, form_id: &str, cursor: &str, limit: u32, include_drafts: bool, since: Option<&str>) -> String {
     format!(
-        "{}/v1/forms/{}/submissions?offset={}&limit={}",
-        api_url, form_id, offset, limit
+        "{}/v1/forms/{}/submissions?cursor={}&limit={}{}{}",
+        api_url, form_id, cursor, limit, drafts_query(include_drafts), since_query(since)
     )
 }
 
-/// Build URL for POST /v1/submissions
+/// db-api's keyset cursor (`<submitted_at micros>:<id>`) for the page that starts right
+/// after `submission`. None if the row has no id or an unparseable timestamp.
+pub fn keyset_cursor(submission: &EncryptedSubmission) -> Option<String> {
+    if submission.id.is_empty() {
+        return None;
+    }
+    let micros = crate::anomalies::parse_utc_micros(&submission.submitted_at)?;
+    Some(format!("{}:{}", micros, submission.id))
+}
+
+/// Build URL for GET /v1/forms/{form_id}/submissions/{submitter_id}
+fn submission_url(api_url: &str, form_id: &str, submitter_id: &str) -> String {
+    format!("{}/v1/forms/{}/submissions/{}", api_url, form_id, submitter_id)
+}
+
+/// Build URL for POST (create) and PUT (replace) /v1/submissions
 fn create_submission_url(api_url: &str) -> String {
     format!("{}/v1/submissions", api_url)
 }
 
-/// Send a GET request with full timeout control (connect, first-byte, between-bytes).
+/// Send a GET request with full timeout control (connect, first-byte, between-bytes, and
+/// an optional overall deadline checked while waiting on the response and body).
 ///
 /// Uses low-level wasi::http types because wasi-http-client only supports connect_timeout.
 fn get_with_timeout(
     url: &str,
-    timeout: Duration,
+    timeouts: &HttpTimeouts,
     extra_headers: &[(&str, &str)],
-) -> Result<(u16, Vec<u8>), Box<dyn std::error::Error>> {
+) -> Result<HttpResponse, FormsError> {
     let parsed = url::Url::parse(url)
         .map_err(|e| format!("Failed to parse URL '{}': {}", url, e))?;
 
@@ -90,29 +270,23 @@ fn get_with_timeout(
     OutgoingBody::finish(outgoing_body, None)
         .map_err(|e| format!("Failed to finish outgoing body: {:?}", e))?;
 
-    let options = RequestOptions::new();
-    let timeout_nanos = timeout.as_nanos() as u64;
-    options.set_connect_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set connect timeout: {:?}", e))?;
-    options.set_first_byte_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set first byte timeout: {:?}", e))?;
-    options.set_between_bytes_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set between bytes timeout: {:?}", e))?;
+    let options = request_options(timeouts)?;
 
     let future_response = outgoing_handler::handle(request, Some(options))
         .map_err(|e| format!("Failed to send request: {:?}", e))?;
 
     let pollable = future_response.subscribe();
-    pollable.block();
+    block_until(&pollable, timeouts.deadline.as_ref())?;
     drop(pollable);
 
     let response = future_response
         .get()
         .ok_or("No response received")?
         .map_err(|e| format!("Response error: {:?}", e))?
-        .map_err(|e| format!("HTTP error: {:?}", e))?;
+        .map_err(http_error)?;
 
     let status = response.status();
+    let content_type = http_chunked::response_content_type(&response.headers());
 
     let incoming_body = response.consume()
         .map_err(|e| format!("Failed to consume response body: {:?}", e))?;
@@ -131,7 +305,7 @@ fn get_with_timeout(
             return Err("Read loop exceeded maximum iterations".into());
         }
         let pollable = input_stream.subscribe();
-        pollable.block();
+        block_until(&pollable, timeouts.deadline.as_ref())?;
         drop(pollable);
 
         match input_stream.read(65536) {
@@ -147,119 +321,835 @@ fn get_with_timeout(
         }
     }
 
-    Ok((status, body))
+    Ok(HttpResponse { status, content_type, body })
+}
+
+/// First 200 bytes of a db-api response body, for logs and error detail.
+fn snippet(body: &[u8]) -> String {
+    String::from_utf8_lossy(&body[..body.len().min(200)]).into_owned()
+}
+
+/// Map a transport failure during `operation` to [`FormsError::UpstreamHttp`]. Timeouts keep
+/// their own variant so the caller is told to retry rather than that db-api is down.
+fn transport_error(operation: &'static str) -> impl Fn(FormsError) -> FormsError {
+    move |e| match e {
+        timeout @ FormsError::Timeout(_) => timeout,
+        other => FormsError::UpstreamHttp { operation, status: None, detail: other.to_string() },
+    }
+}
+
+/// Check that db-api is reachable and its database is up (public endpoint, no auth)
+///
+/// Calls GET /health, which pings PostgreSQL. Any transport failure or non-200 status is
+/// reported as [`FormsError::UpstreamHttp`] so callers get one clean, user-facing message.
+pub fn health(
+    http: &impl HttpClient,
+    api_url: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<(), FormsError> {
+    let url = health_url(api_url);
+    let unavailable = |status, detail| FormsError::UpstreamHttp { operation: "reach the database", status, detail };
+
+    let response = http.get(&url, &[], timeouts).map_err(|e| {
+        eprintln!("db-api health check failed: {}", e);
+        unavailable(None, e.to_string())
+    })?;
+
+    if response.status != 200 {
+        let detail = response.error_detail();
+        eprintln!("db-api health check returned status {}: {}", response.status, detail);
+        return Err(unavailable(Some(response.status), detail));
+    }
+
+    Ok(())
 }
 
 /// Fetch form metadata from db-api (public endpoint, no auth)
 ///
-/// Calls GET /forms/{form_id}
+/// Calls GET /forms/{form_id}. A 404 is [`FormsError::Rejected`] ("Form not found").
 pub fn get_form(
+    http: &impl HttpClient,
     api_url: &str,
     form_id: &str,
-) -> Result<FormMetadata, Box<dyn std::error::Error>> {
+    timeouts: &HttpTimeouts,
+) -> Result<FormMetadata, FormsError> {
     let url = form_url(api_url, form_id);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch form", status, detail };
+
+    let response = http.get(&url, &[], timeouts).map_err(transport_error("fetch form"))?;
+    let status = response.status;
+
+    if status == 404 && !response.is_html() {
+        return Err(FormsError::Rejected("Form not found".to_string()));
+    }
+    if status != 200 {
+        return Err(upstream(Some(status), response.error_detail()));
+    }
+
+    serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid form JSON: {} (body: {})", e, response.error_detail()))
+    })
+}
+
+/// Fetch the owners of a form, creator first (API-Secret required)
+///
+/// Calls GET /forms/{form_id}/owners. Any failure is a [`FormsError::UpstreamHttp`] (or
+/// [`FormsError::Timeout`]): without the list nobody but the creator can be authorized.
+pub fn get_form_owners(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<Vec<FormOwner>, FormsError> {
+    let url = form_owners_url(api_url, form_id);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch form owners", status, detail };
 
-    let (status, body) = get_with_timeout(&url, TIMEOUT, &[])?;
+    let response = http
+        .get(&url, &[("API-Secret", api_secret)], timeouts)
+        .map_err(transport_error("fetch form owners"))?;
+    let status = response.status;
+    if status != 200 {
+        return Err(upstream(Some(status), response.error_detail()));
+    }
+
+    let owners: FormOwners = serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid form owners JSON: {} (body: {})", e, response.error_detail()))
+    })?;
+    Ok(owners.owners)
+}
+
+/// Fetch the form's key derivation salt (API-Secret required)
+///
+/// Calls GET /forms/{form_id}/derivation-salt; the public form omits the salt so that form
+/// keys cannot be derived from public values. None for forms older than the salt column.
+pub fn get_form_derivation_salt(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<Option<String>, FormsError> {
+    let url = form_derivation_salt_url(api_url, form_id);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch form derivation salt", status, detail };
 
+    let response = http
+        .get(&url, &[("API-Secret", api_secret)], timeouts)
+        .map_err(transport_error("fetch form derivation salt"))?;
+    let status = response.status;
+    if status == 404 && !response.is_html() {
+        return Err(FormsError::Rejected("Form not found".to_string()));
+    }
     if status != 200 {
-        let snippet = String::from_utf8_lossy(&body[..body.len().min(200)]);
-        return Err(format!("Failed to fetch form (status {}): {}", status, snippet).into());
+        return Err(upstream(Some(status), response.error_detail()));
     }
 
-    let form: FormMetadata = serde_json::from_slice(&body)
-        .map_err(|e| {
-            let snippet = String::from_utf8_lossy(&body[..body.len().min(200)]);
-            format!("Invalid form JSON: {} (body: {})", e, snippet)
-        })?;
+    let salt: FormDerivationSalt = serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid derivation salt JSON: {} (body: {})", e, response.error_detail()))
+    })?;
+    Ok(salt.derivation_salt)
+}
+
+/// Fetch one revision of a form's questions from db-api (public endpoint, no auth)
+///
+/// Calls GET /forms/{form_id}/versions/{version}. A 404 is [`FormsError::Rejected`].
+pub fn get_form_version(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    version: i32,
+    timeouts: &HttpTimeouts,
+) -> Result<FormVersion, FormsError> {
+    let url = form_version_url(api_url, form_id, version);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch form version", status, detail };
 
-    Ok(form)
+    let response = http.get(&url, &[], timeouts).map_err(transport_error("fetch form version"))?;
+    let status = response.status;
+
+    if status == 404 && !response.is_html() {
+        return Err(FormsError::Rejected(format!("Form version {} not found", version)));
+    }
+    if status != 200 {
+        return Err(upstream(Some(status), response.error_detail()));
+    }
+
+    serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid form version JSON: {} (body: {})", e, response.error_detail()))
+    })
 }
 
 /// Fetch a page of encrypted form submissions from db-api
 ///
-/// Calls GET /forms/{form_id}/submissions?offset={offset}&limit={limit} with API-Secret header.
-/// Returns submissions and total count for pagination.
+/// Calls GET /forms/{form_id}/submissions?offset={offset}&limit={limit}&with_total=true with API-Secret header
+/// (and `&include_drafts=true` when `include_drafts`). With `since`, only submissions made
+/// strictly after it are returned and counted.
+/// Returns submissions and total count for pagination. A form with no submissions yet is
+/// an empty page, not an error. A page over db-api's response cap (413) is
+/// [`FormsError::InvalidInput`] carrying db-api's suggested `limit`; every other failure to
+/// get a page is a [`FormsError::UpstreamHttp`] (or [`FormsError::Timeout`]).
+#[allow(clippy::too_many_arguments)]
 pub fn get_submissions(
+    http: &impl HttpClient,
     api_url: &str,
     form_id: &str,
     api_secret: &str,
     offset: u32,
     limit: u32,
-) -> Result<SubmissionsPage, Box<dyn std::error::Error>> {
-    let url = submissions_url(api_url, form_id, offset, limit);
+    include_drafts: bool,
+    since: Option<&str>,
+    timeouts: &HttpTimeouts,
+) -> Result<SubmissionsPage, FormsError> {
+    fetch_submissions(http, &submissions_url(api_url, form_id, offset, limit, include_drafts, since), api_secret, timeouts)
+}
+
+/// Fetch the page of encrypted submissions after a db-api keyset `cursor`
+///
+/// Calls GET /forms/{form_id}/submissions?cursor={cursor}&limit={limit} with API-Secret header.
+/// Unlike [`get_submissions`] the page has no `total_count`, and it does not shift when
+/// submissions are inserted ahead of the cursor. Failures are as for [`get_submissions`].
+#[allow(clippy::too_many_arguments)]
+pub fn get_submissions_after(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    api_secret: &str,
+    cursor: &str,
+    limit: u32,
+    include_drafts: bool,
+    since: Option<&str>,
+    timeouts: &HttpTimeouts,
+) -> Result<SubmissionsCursorPage, FormsError> {
+    fetch_submissions(http, &submissions_after_url(api_url, form_id, cursor, limit, include_drafts, since), api_secret, timeouts)
+}
 
-    let (status, body) = get_with_timeout(&url, TIMEOUT, &[("API-Secret", api_secret)])?;
+fn fetch_submissions<T: serde::de::DeserializeOwned>(
+    http: &impl HttpClient,
+    url: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<T, FormsError> {
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch submissions", status, detail };
+
+    let response = http
+        .get(url, &[("API-Secret", api_secret)], timeouts)
+        .map_err(transport_error("fetch submissions"))?;
+    let status = response.status;
 
+    // db-api's MAX_RESPONSE_BYTES: the caller's `limit` is too big for these blobs, and
+    // db-api's message names one that fits
+    if status == 413 && !response.is_html() {
+        let message = serde_json::from_slice::<serde_json::Value>(&response.body)
+            .ok()
+            .and_then(|body| body["error"].as_str().map(str::to_string))
+            .unwrap_or_else(|| "This page of submissions is too large. Request a smaller limit.".to_string());
+        return Err(FormsError::InvalidInput(message));
+    }
     if status != 200 {
-        let snippet = String::from_utf8_lossy(&body[..body.len().min(200)]);
-        return Err(format!("Failed to fetch submissions (status {}): {}", status, snippet).into());
+        return Err(upstream(Some(status), response.error_detail()));
     }
 
-    let page: SubmissionsPage = serde_json::from_slice(&body)
-        .map_err(|e| {
-            let snippet = String::from_utf8_lossy(&body[..body.len().min(200)]);
-            format!("Invalid submissions JSON: {} (body: {})", e, snippet)
-        })?;
+    serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid submissions JSON: {} (body: {})", e, response.error_detail()))
+    })
+}
+
+/// Fetch an account's latest final submission to a form from db-api
+///
+/// Calls GET /forms/{form_id}/submissions/{submitter_id} with API-Secret header. A 404 (no
+/// final submission from that account; drafts are not returned) is [`FormsError::Rejected`].
+pub fn get_submission(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    submitter_id: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<EncryptedSubmission, FormsError> {
+    let url = submission_url(api_url, form_id, submitter_id);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "fetch submission", status, detail };
+
+    let response = http
+        .get(&url, &[("API-Secret", api_secret)], timeouts)
+        .map_err(transport_error("fetch submission"))?;
+    let status = response.status;
+
+    if status == 404 && !response.is_html() {
+        return Err(FormsError::Rejected(format!("No submission found for {}", submitter_id)));
+    }
+    if status != 200 {
+        return Err(upstream(Some(status), response.error_detail()));
+    }
 
-    Ok(page)
+    serde_json::from_slice(&response.body).map_err(|e| {
+        upstream(Some(status), format!("Invalid submission JSON: {} (body: {})", e, response.error_detail()))
+    })
 }
 
+/// SubmitForm error for a paused form (db-api 423, or `FormMetadata::paused` up front)
+pub const FORM_PAUSED_ERROR: &str = "This form is paused and is not accepting submissions right now.";
+
 /// Store a new encrypted form submission to db-api
 ///
-/// Calls POST /submissions with API-Secret header.
-/// Uses chunked HTTP writes to bypass the ~4KB WASI single-write limit,
+/// Calls POST /submissions with API-Secret header and returns the new submission's ID and
+/// stored blob hash. Uses chunked HTTP writes to bypass the ~4KB WASI single-write limit,
 /// since encrypted blobs can exceed 4KB when hex-encoded.
+#[allow(clippy::too_many_arguments)]
 pub fn create_submission(
+    http: &impl HttpClient,
     api_url: &str,
     form_id: &str,
     submitter_id: &str,
     encrypted_blob: &str,
+    is_draft: bool,
     api_secret: &str,
-) -> Result<String, Box<dyn std::error::Error>> {
+    timeouts: &HttpTimeouts,
+) -> Result<CreatedSubmission, FormsError> {
     let url = create_submission_url(api_url);
+    let upstream = |status, detail| FormsError::UpstreamHttp { operation: "create submission", status, detail };
 
     let body = serde_json::json!({
         "form_id": form_id,
         "submitter_id": submitter_id,
         "encrypted_blob": encrypted_blob,
+        "is_draft": is_draft,
     });
 
     let body_bytes = serde_json::to_vec(&body)?;
 
-    let response = http_chunked::post_chunked(
-        &url,
-        "application/json",
-        &body_bytes,
-        TIMEOUT,
-        Some(api_secret),
-    )?;
-
-    let status = response.status();
+    let response = http
+        .post_json(&url, &body_bytes, Some(api_secret), timeouts)
+        .map_err(transport_error("create submission"))?;
+    let (status, response_body) = (response.status, &response.body);
 
-    if status != 200 && status != 201 {
-        // Check for duplicate submission (unique constraint violation)
-        if status == 409 {
-            return Err("You have already submitted this form. Each account can only submit once.".into());
+    match status {
+        // db-api's own 404/409 are JSON; a proxy's HTML page with those codes is not them
+        _ if response.is_html() => return Err(upstream(Some(status), response.error_detail())),
+        200 | 201 => {}
+        // 409 covers two unique constraints: the same account submitting twice, or the exact
+        // same ciphertext being replayed (db-api's blob_hash index). Tell them apart by body.
+        409 if String::from_utf8_lossy(response_body).contains("already been received") => {
+            return Err(FormsError::Rejected(
+                "This encrypted submission has already been received. Please re-encrypt and submit again.".to_string(),
+            ));
         }
-
-        return Err(format!("Failed to create submission (status {})", status).into());
+        409 => {
+            return Err(FormsError::Rejected(
+                "You have already submitted this form. Each account can only submit once.".to_string(),
+            ));
+        }
+        404 => return Err(FormsError::Rejected("Form not found".to_string())),
+        423 => return Err(FormsError::Rejected(FORM_PAUSED_ERROR.to_string())),
+        // The account is on the form's denied_submitters
+        403 => {
+            return Err(FormsError::NotAuthorized(
+                "This account is not allowed to submit to this form.".to_string(),
+            ));
+        }
+        // The form's min_resubmit_interval_secs; db-api's message says how long to wait
+        429 => {
+            let message = serde_json::from_slice::<serde_json::Value>(response_body)
+                .ok()
+                .and_then(|body| body["error"].as_str().map(str::to_string))
+                .unwrap_or_else(|| "You submitted this form too recently. Please try again later.".to_string());
+            return Err(FormsError::Rejected(message));
+        }
+        _ => return Err(upstream(Some(status), response.error_detail())),
     }
 
     // Extract submission ID from response
-    let response_json: serde_json::Value = serde_json::from_slice(response.body())
-        .map_err(|e| format!("Invalid submission response JSON: {}", e))?;
+    let response_json: serde_json::Value = serde_json::from_slice(response_body)
+        .map_err(|e| upstream(Some(status), format!("Invalid submission response JSON: {}", e)))?;
 
     let submission_id = response_json["id"]
         .as_str()
-        .ok_or("Missing submission ID in response")?
+        .ok_or_else(|| upstream(Some(status), "Missing submission ID in response".to_string()))?
         .to_string();
 
-    Ok(submission_id)
+    Ok(CreatedSubmission {
+        id: submission_id,
+        blob_hash: response_json["blob_hash"].as_str().map(str::to_string),
+    })
+}
+
+/// Hex SHA-256 of a stored blob, as db-api's `PUT /submissions` expects in `previous_blob_hash`
+/// (and as `POST /submissions` reports in `blob_hash`)
+pub fn blob_hash(encrypted_blob: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(encrypted_blob.as_bytes()))
+}
+
+/// Register the form's derived public key with db-api, which serves it at
+/// GET /forms/{form_id}/public-key for clients that do not derive it themselves
+///
+/// Calls PUT /forms/{form_id}/public-key with API-Secret header. Idempotent.
+pub fn set_form_public_key(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    public_key: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<(), FormsError> {
+    let body = serde_json::json!({ "public_key": public_key });
+    let response = http
+        .put_json(&form_public_key_url(api_url, form_id), &serde_json::to_vec(&body)?, Some(api_secret), timeouts)
+        .map_err(transport_error("register form public key"))?;
+
+    match response.status {
+        200 if !response.is_html() => Ok(()),
+        404 if !response.is_html() => Err(FormsError::Rejected("Form not found".to_string())),
+        status => Err(FormsError::UpstreamHttp {
+            operation: "register form public key",
+            status: Some(status),
+            detail: response.error_detail(),
+        }),
+    }
+}
+
+/// Replace a stored submission's blob (e.g. re-encrypted under a rotated key)
+///
+/// Calls PUT /submissions with API-Secret header. `previous` is the submission as read from
+/// db-api; the write only applies if its blob is still stored, and repeating a completed
+/// replacement succeeds, so retries are safe.
+pub fn update_submission(
+    http: &impl HttpClient,
+    api_url: &str,
+    form_id: &str,
+    previous: &EncryptedSubmission,
+    encrypted_blob: &str,
+    api_secret: &str,
+    timeouts: &HttpTimeouts,
+) -> Result<(), FormsError> {
+    let url = create_submission_url(api_url);
+
+    let body = serde_json::json!({
+        "form_id": form_id,
+        "submitter_id": previous.submitter_id,
+        "encrypted_blob": encrypted_blob,
+        "previous_blob_hash": blob_hash(&previous.encrypted_blob),
+    });
+
+    let response = http
+        .put_json(&url, &serde_json::to_vec(&body)?, Some(api_secret), timeouts)
+        .map_err(transport_error("update submission"))?;
+
+    match response.status {
+        200 if !response.is_html() => Ok(()),
+        404 if !response.is_html() => Err(FormsError::Rejected("Submission not found".to_string())),
+        409 if !response.is_html() => {
+            Err(FormsError::Rejected(format!("Submission update conflict: {}", snippet(&response.body))))
+        }
+        // Younger than the form's min_resubmit_interval_secs; a later walk can retry it
+        429 if !response.is_html() => {
+            Err(FormsError::Rejected(format!("Submission too recent to update: {}", snippet(&response.body))))
+        }
+        status => Err(FormsError::UpstreamHttp {
+            operation: "update submission",
+            status: Some(status),
+            detail: response.error_detail(),
+        }),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::timing::DB_TIMEOUT_ERROR;
+    use crate::types::{EncryptionScheme, SubmissionPolicy};
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    const API: &str = "http://db-api:4001";
+    const FORM: &str = "daf14a0c-20f7-4199-a07b-c6456d53ef2d";
+
+    /// (url, API-Secret, body) of one request seen by `StubHttp`
+    type Recorded = (String, Option<String>, Vec<u8>);
+
+    /// Canned db-api: answers every request with one fixed response and records what was sent.
+    struct StubHttp {
+        /// `Err(None)` is a timeout
+        response: Result<HttpResponse, Option<String>>,
+        requests: RefCell<Vec<Recorded>>,
+    }
+
+    impl StubHttp {
+        fn new(status: u16, body: &str) -> Self {
+            Self::with_content_type(status, "application/json", body)
+        }
+
+        fn with_content_type(status: u16, content_type: &str, body: &str) -> Self {
+            let response = HttpResponse {
+                status,
+                content_type: Some(content_type.to_string()),
+                body: body.as_bytes().to_vec(),
+            };
+            Self { response: Ok(response), requests: RefCell::new(Vec::new()) }
+        }
+
+        fn failing(error: &str) -> Self {
+            Self { response: Err(Some(error.to_string())), requests: RefCell::new(Vec::new()) }
+        }
+
+        fn timing_out() -> Self {
+            Self { response: Err(None), requests: RefCell::new(Vec::new()) }
+        }
+
+        fn respond(&self, url: &str, secret: Option<&str>, body: &[u8]) -> Result<HttpResponse, FormsError> {
+            self.requests.borrow_mut().push((url.to_string(), secret.map(str::to_string), body.to_vec()));
+            self.response.clone().map_err(|e| e.map_or(FormsError::Timeout(DB_TIMEOUT_ERROR), FormsError::Internal))
+        }
+    }
+
+    impl HttpClient for StubHttp {
+        fn get(&self, url: &str, headers: &[(&str, &str)], _: &HttpTimeouts) -> Result<HttpResponse, FormsError> {
+            let secret = headers.iter().find(|(k, _)| *k == "API-Secret").map(|(_, v)| *v);
+            self.respond(url, secret, &[])
+        }
+
+        fn post_json(&self, url: &str, body: &[u8], api_secret: Option<&str>, _: &HttpTimeouts) -> Result<HttpResponse, FormsError> {
+            self.respond(url, api_secret, body)
+        }
+
+        fn put_json(&self, url: &str, body: &[u8], api_secret: Option<&str>, _: &HttpTimeouts) -> Result<HttpResponse, FormsError> {
+            self.respond(url, api_secret, body)
+        }
+    }
+
+    fn timeouts() -> HttpTimeouts {
+        HttpTimeouts { connect: Duration::from_secs(1), read: Duration::from_secs(1), deadline: None }
+    }
+
+    fn submit(http: &StubHttp) -> Result<CreatedSubmission, FormsError> {
+        create_submission(http, API, FORM, "bob.testnet", "4543303102", false, "secret", &timeouts())
+    }
+
+    #[test]
+    fn health_maps_any_failure_to_unavailable() {
+        assert!(health(&StubHttp::new(200, r#"{"status":"ok"}"#), API, &timeouts()).is_ok());
+        for http in [StubHttp::new(503, "down"), StubHttp::failing("connection refused")] {
+            let err = health(&http, API, &timeouts()).unwrap_err();
+            assert_eq!(err.code(), "upstream_unavailable");
+            assert!(err.public_message().starts_with("Database unavailable"), "{}", err.public_message());
+        }
+    }
+
+    #[test]
+    fn get_form_parses_metadata() {
+        let http = StubHttp::new(200, r#"{"id":"x","creator_id":"alice.testnet","questions":[{"id":"q1"}],"strict_answers":true,"tags":[],"submission_policy":"unlimited","encryption_scheme":"ec01_per_question"}"#);
+        let form = get_form(&http, API, FORM, &timeouts()).unwrap();
+        assert_eq!(form.creator_id, "alice.testnet");
+        assert!(form.strict_answers);
+        assert_eq!(form.submission_policy, SubmissionPolicy::Unlimited);
+        assert_eq!(form.encryption_scheme, EncryptionScheme::Ec01PerQuestion);
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, form_url(API, FORM));
+        assert_eq!(requests[0].1, None, "form metadata is public; no secret sent");
+    }
+
+    #[test]
+    fn get_form_non_200_and_malformed_json() {
+        let err = get_form(&StubHttp::new(404, r#"{"error":"Form not found"}"#), API, FORM, &timeouts()).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m == "Form not found"), "{}", err);
+
+        let err = get_form(&StubHttp::new(500, "boom"), API, FORM, &timeouts()).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to fetch form (status 500)"), "{}", err);
+        assert!(!err.public_message().contains("boom"));
+
+        let err = get_form(&StubHttp::new(200, "<html>"), API, FORM, &timeouts()).unwrap_err();
+        assert!(err.to_string().contains("Invalid form JSON"), "{}", err);
+        assert!(err.to_string().contains("<html>"), "{}", err);
+        assert_eq!(err.code(), "upstream_unavailable");
+    }
+
+    #[test]
+    fn set_form_public_key_sends_secret_and_key() {
+        let key = format!("02{}", "ab".repeat(32));
+        let http = StubHttp::new(200, "{}");
+        set_form_public_key(&http, API, FORM, &key, "secret", &timeouts()).unwrap();
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, format!("{}/v1/forms/{}/public-key", API, FORM));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&requests[0].2).unwrap()["public_key"], key);
+
+        let err = set_form_public_key(&StubHttp::new(400, r#"{"error":"bad"}"#), API, FORM, &key, "secret", &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(400), .. }), "{}", err);
+    }
+
+    #[test]
+    fn get_form_wraps_transport_errors() {
+        let err = get_form(&StubHttp::failing("Response error: closed"), API, FORM, &timeouts()).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to fetch form: Response error: closed");
+        assert!(matches!(err, FormsError::UpstreamHttp { status: None, .. }));
+    }
+
+    #[test]
+    fn get_form_owners_sends_secret_and_lists_accounts() {
+        let http = StubHttp::new(200, r#"{"owners":[{"account_id":"alice.testnet","added_at":"x","is_creator":true},{"account_id":"bob.testnet","added_at":"x","is_creator":false,"visible_questions":["q1"]}]}"#);
+        let owners = get_form_owners(&http, API, FORM, "secret", &timeouts()).unwrap();
+        assert_eq!(owners.iter().map(|o| o.account_id.as_str()).collect::<Vec<_>>(), ["alice.testnet", "bob.testnet"]);
+        assert_eq!(owners[0].visible_questions, None);
+        assert_eq!(owners[1].visible_questions.as_deref(), Some(&["q1".to_string()][..]));
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, format!("{}/v1/forms/{}/owners", API, FORM));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+
+        let err = get_form_owners(&StubHttp::new(401, r#"{"error":"Unauthorized"}"#), API, FORM, "bad", &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(401), .. }), "{}", err);
+    }
+
+    #[test]
+    fn get_form_derivation_salt_sends_secret() {
+        let http = StubHttp::new(200, r#"{"form_id":"f","derivation_salt":"ab"}"#);
+        let salt = get_form_derivation_salt(&http, API, FORM, "secret", &timeouts()).unwrap();
+        assert_eq!(salt.as_deref(), Some("ab"));
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, format!("{}/v1/forms/{}/derivation-salt", API, FORM));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+
+        let legacy = StubHttp::new(200, r#"{"form_id":"f","derivation_salt":null}"#);
+        assert_eq!(get_form_derivation_salt(&legacy, API, FORM, "secret", &timeouts()).unwrap(), None);
+        let err = get_form_derivation_salt(&StubHttp::new(401, r#"{"error":"Unauthorized"}"#), API, FORM, "bad", &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(401), .. }), "{}", err);
+    }
+
+    #[test]
+    fn get_submission_sends_secret_and_maps_404() {
+        let http = StubHttp::new(200, r#"{"id":"s1","submitter_id":"bob.testnet","encrypted_blob":"ab","submitted_at":"2026-01-01T00:00:00Z"}"#);
+        let submission = get_submission(&http, API, FORM, "bob.testnet", "secret", &timeouts()).unwrap();
+        assert_eq!(submission.encrypted_blob, "ab");
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, format!("{}/v1/forms/{}/submissions/bob.testnet", API, FORM));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+
+        let err = get_submission(&StubHttp::new(404, r#"{"error":"Submission not found"}"#), API, FORM, "bob.testnet", "secret", &timeouts()).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m == "No submission found for bob.testnet"), "{}", err);
+        let err = get_submission(&StubHttp::with_content_type(404, "text/html", NGINX_502), API, FORM, "bob.testnet", "secret", &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(404), .. }), "{}", err);
+    }
+
+    #[test]
+    fn get_form_version_parses_questions() {
+        let http = StubHttp::new(200, r#"{"version":2,"questions":[{"id":"q1"}],"created_at":"2026-01-01T00:00:00Z"}"#);
+        let version = get_form_version(&http, API, FORM, 2, &timeouts()).unwrap();
+        assert_eq!(version.questions[0]["id"], "q1");
+        assert_eq!(http.requests.borrow()[0].0, format!("{}/v1/forms/{}/versions/2", API, FORM));
+
+        let err = get_form_version(&StubHttp::new(404, r#"{"error":"Form version not found"}"#), API, FORM, 9, &timeouts()).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m == "Form version 9 not found"), "{}", err);
+    }
+
+    const NGINX_502: &str = "<html>\r\n<head><title>502 Bad Gateway</title></head>\r\n<body>\r\n<center><h1>502 Bad Gateway</h1></center>\r\n<hr><center>nginx</center>\r\n</body>\r\n</html>\r\n";
+
+    #[test]
+    fn html_error_page_is_summarized_not_dumped() {
+        let http = StubHttp::with_content_type(502, "text/html; charset=utf-8", NGINX_502);
+        let err = get_form(&http, API, FORM, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(502), .. }));
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to fetch form (status 502): upstream gateway error: HTML page \"502 Bad Gateway\" ({} bytes)", NGINX_502.len())
+        );
+
+        let err = get_submissions(&http, API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap_err();
+        assert!(!err.to_string().contains("<html>"), "{}", err);
+        let err = submit(&http).unwrap_err();
+        assert!(err.to_string().contains("upstream gateway error"), "{}", err);
+    }
+
+    #[test]
+    fn html_404_or_409_is_not_read_as_db_api_verdict() {
+        let err = get_form(&StubHttp::with_content_type(404, "text/html", "<h1>Not Found</h1>"), API, FORM, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(404), .. }), "{}", err);
+        assert!(err.to_string().ends_with("upstream gateway error: HTML page (18 bytes)"), "{}", err);
+
+        let err = submit(&StubHttp::with_content_type(409, "TEXT/HTML", NGINX_502)).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(409), .. }), "{}", err);
+        let err = update(&StubHttp::with_content_type(409, "text/html", NGINX_502)).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(409), .. }), "{}", err);
+    }
+
+    #[test]
+    fn html_title_extraction() {
+        assert_eq!(html_title(NGINX_502.as_bytes()).as_deref(), Some("502 Bad Gateway"));
+        assert_eq!(html_title(b"<TITLE lang=en>\n  Service \n Unavailable </TITLE>").as_deref(), Some("Service Unavailable"));
+        assert_eq!(html_title(b"<title></title>"), None);
+        assert_eq!(html_title(b"<title>never closed"), None);
+        assert_eq!(html_title(b"plain text"), None);
+    }
+
+    #[test]
+    fn get_submissions_sends_secret_and_parses_page() {
+        let http = StubHttp::new(200, r#"{"submissions":[{"submitter_id":"bob.testnet","encrypted_blob":"ab","submitted_at":"2026-01-01T00:00:00Z"}],"total_count":1,"has_more":false}"#);
+        let page = get_submissions(&http, API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.submissions[0].submitter_id, "bob.testnet");
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, submissions_url(API, FORM, 0, 50, false, None));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn get_submissions_non_200_and_malformed_json() {
+        let err = get_submissions(&StubHttp::new(401, "Unauthorized"), API, FORM, "bad", 0, 50, false, None, &timeouts()).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to fetch submissions (status 401)"), "{}", err);
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(401), .. }));
+
+        let err = get_submissions(&StubHttp::new(200, r#"{"submissions":[]}"#), API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap_err();
+        assert!(err.to_string().contains("Invalid submissions JSON"), "{}", err);
+        assert!(matches!(err, FormsError::UpstreamHttp { .. }));
+    }
+
+    #[test]
+    fn get_submissions_oversized_page_is_invalid_input() {
+        let body = r#"{"error":"This page of 200 submissions would be about 90000000 bytes (max: 33554432 bytes). Request fewer per page, e.g. limit=70, and follow next_cursor for the rest."}"#;
+        let err = get_submissions(&StubHttp::new(413, body), API, FORM, "secret", 0, 200, false, None, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::InvalidInput(ref m) if m.contains("limit=70")), "{}", err);
+
+        let err = get_submissions(&StubHttp::with_content_type(413, "text/html", NGINX_502), API, FORM, "secret", 0, 200, false, None, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: Some(413), .. }), "{}", err);
+    }
+
+    #[test]
+    fn get_submissions_empty_page_is_not_an_error() {
+        let http = StubHttp::new(200, r#"{"submissions":[],"total_count":0,"has_more":false}"#);
+        let page = get_submissions(&http, API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap();
+        assert!(page.submissions.is_empty());
+        assert_eq!(page.total_count, 0);
+    }
+
+    #[test]
+    fn get_submissions_transport_error_is_upstream() {
+        let err = get_submissions(&StubHttp::failing("Response error: timeout"), API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::UpstreamHttp { status: None, .. }));
+        assert_eq!(err.to_string(), "Failed to fetch submissions: Response error: timeout");
+
+        let err = get_submissions(&StubHttp::timing_out(), API, FORM, "secret", 0, 50, false, None, &timeouts()).unwrap_err();
+        assert!(matches!(err, FormsError::Timeout(_)), "timeouts keep their own code: {}", err);
+    }
+
+    #[test]
+    fn get_submissions_after_sends_cursor_and_parses_page() {
+        let http = StubHttp::new(200, r#"{"submissions":[{"id":"0b5f0c2e-0000-0000-0000-000000000002","submitter_id":"bob.testnet","encrypted_blob":"ab","submitted_at":"2026-01-01T00:00:00.000001Z"}],"has_more":true,"next_cursor":"x"}"#);
+        let cursor = "1767225600000002:0b5f0c2e-0000-0000-0000-000000000003";
+        let page = get_submissions_after(&http, API, FORM, "secret", cursor, 50, false, None, &timeouts()).unwrap();
+        assert!(page.has_more);
+        assert_eq!(
+            keyset_cursor(&page.submissions[0]).as_deref(),
+            Some("1767225600000001:0b5f0c2e-0000-0000-0000-000000000002")
+        );
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, format!("{}/v1/forms/{}/submissions?cursor={}&limit=50", API, FORM, cursor));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn keyset_cursor_needs_id_and_timestamp() {
+        let mut submission = EncryptedSubmission {
+            id: String::new(),
+            submitter_id: "bob.testnet".to_string(),
+            encrypted_blob: "ab".to_string(),
+            submitted_at: "2026-01-01T00:00:00Z".to_string(),
+            is_draft: false,
+            form_version: None,
+        };
+        assert_eq!(keyset_cursor(&submission), None);
+        submission.id = "0b5f0c2e-0000-0000-0000-000000000001".to_string();
+        submission.submitted_at = "yesterday".to_string();
+        assert_eq!(keyset_cursor(&submission), None);
+    }
+
+    #[test]
+    fn create_submission_posts_body_and_returns_id() {
+        let http = StubHttp::new(201, r#"{"id":"0b5f0c2e-0000-0000-0000-000000000001","blob_hash":"ab12"}"#);
+        let created = submit(&http).unwrap();
+        assert_eq!(created.id, "0b5f0c2e-0000-0000-0000-000000000001");
+        assert_eq!(created.blob_hash.as_deref(), Some("ab12"));
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, create_submission_url(API));
+        assert_eq!(requests[0].1.as_deref(), Some("secret"));
+        let sent: serde_json::Value = serde_json::from_slice(&requests[0].2).unwrap();
+        assert_eq!(sent, serde_json::json!({
+            "form_id": FORM,
+            "submitter_id": "bob.testnet",
+            "encrypted_blob": "4543303102",
+            "is_draft": false,
+        }));
+    }
+
+    #[test]
+    fn drafts_are_sent_and_requested_explicitly() {
+        let http = StubHttp::new(201, r#"{"id":"0b5f0c2e-0000-0000-0000-000000000001"}"#);
+        create_submission(&http, API, FORM, "bob.testnet", "4543303102", true, "secret", &timeouts()).unwrap();
+        let sent: serde_json::Value = serde_json::from_slice(&http.requests.borrow()[0].2).unwrap();
+        assert_eq!(sent["is_draft"], true);
+
+        assert!(submissions_url(API, FORM, 0, 50, true, None).ends_with("&with_total=true&include_drafts=true"));
+        assert!(submissions_after_url(API, FORM, "1:x", 50, true, None).ends_with("&limit=50&include_drafts=true"));
+        assert!(!submissions_url(API, FORM, 0, 50, false, None).contains("include_drafts"));
+    }
+
+    #[test]
+    fn since_is_forwarded_on_offset_and_cursor_pages() {
+        let since = Some("2026-01-01T00:00:00.000001Z");
+        assert!(submissions_url(API, FORM, 0, 50, false, since).ends_with("&with_total=true&since=2026-01-01T00:00:00.000001Z"));
+        assert!(submissions_after_url(API, FORM, "1:x", 50, true, since).ends_with("&include_drafts=true&since=2026-01-01T00:00:00.000001Z"));
+        assert!(!submissions_url(API, FORM, 0, 50, false, None).contains("since"));
+    }
+
+    #[test]
+    fn create_submission_conflicts() {
+        let err = submit(&StubHttp::new(409, r#"{"error":"Already submitted"}"#)).unwrap_err();
+        assert!(err.to_string().starts_with("You have already submitted this form"), "{}", err);
+
+        let err = submit(&StubHttp::new(409, r#"{"error":"This encrypted submission has already been received."}"#)).unwrap_err();
+        assert!(err.to_string().contains("already been received"), "{}", err);
+        assert_eq!(err.code(), "rejected");
+        assert_eq!(err.public_message(), err.to_string());
+    }
+
+    #[test]
+    fn create_submission_error_status_and_malformed_json() {
+        let err = submit(&StubHttp::new(500, r#"{"error":"Database error"}"#)).unwrap_err();
+        assert_eq!(err.to_string(), r#"Failed to create submission (status 500): {"error":"Database error"}"#);
+        assert_eq!(err.code(), "upstream_unavailable");
+
+        let err = submit(&StubHttp::new(201, "not json")).unwrap_err();
+        assert!(err.to_string().contains("Invalid submission response JSON"), "{}", err);
+
+        let err = submit(&StubHttp::new(201, r#"{"status":"ok"}"#)).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to create submission (status 201): Missing submission ID in response");
+
+        let err = submit(&StubHttp::new(404, r#"{"error":"Form not found"}"#)).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m == "Form not found"), "{}", err);
+    }
+
+    #[test]
+    fn create_submission_423_is_paused() {
+        let err = submit(&StubHttp::new(423, r#"{"error":"paused"}"#)).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m == FORM_PAUSED_ERROR), "{}", err);
+    }
+
+    #[test]
+    fn create_submission_403_is_not_authorized() {
+        let err = submit(&StubHttp::new(403, r#"{"error":"This account is not allowed to submit to this form."}"#)).unwrap_err();
+        assert!(matches!(&err, FormsError::NotAuthorized(m) if m.contains("not allowed to submit")), "{}", err);
+        assert_eq!(err.code(), "not_authorized");
+    }
+
+    #[test]
+    fn create_submission_429_passes_on_the_wait() {
+        let throttled = r#"{"error":"Submitted too recently: this form accepts one submission per account every 60 seconds (retry in 42 seconds)"}"#;
+        let err = submit(&StubHttp::new(429, throttled)).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m.ends_with("(retry in 42 seconds)")), "{}", err);
+
+        let err = submit(&StubHttp::new(429, "")).unwrap_err();
+        assert!(matches!(&err, FormsError::Rejected(m) if m.contains("too recently")), "{}", err);
+    }
+
+    #[test]
+    fn test_health_url() {
+        assert_eq!(health_url("http://db-api:4001"), "http://db-api:4001/v1/health");
+    }
 
     #[test]
     fn test_form_url() {
@@ -269,10 +1159,10 @@ mod tests {
 
     #[test]
     fn test_submissions_url_no_double_v1() {
-        let url = submissions_url("http://db-api:4001", "daf14a0c-20f7-4199-a07b-c6456d53ef2d", 0, 200);
+        let url = submissions_url("http://db-api:4001", "daf14a0c-20f7-4199-a07b-c6456d53ef2d", 0, 200, false, None);
         assert_eq!(
             url,
-            "http://db-api:4001/v1/forms/daf14a0c-20f7-4199-a07b-c6456d53ef2d/submissions?offset=0&limit=200"
+            "http://db-api:4001/v1/forms/daf14a0c-20f7-4199-a07b-c6456d53ef2d/submissions?offset=0&limit=200&with_total=true"
         );
         // Ensure no double /v1/ prefix
         assert!(url.matches("/v1/").count() == 1,
@@ -281,7 +1171,7 @@ mod tests {
 
     #[test]
     fn test_submissions_url_pagination() {
-        let url = submissions_url("http://localhost:4001", "abc-123", 100, 50);
+        let url = submissions_url("http://localhost:4001", "abc-123", 100, 50, false, None);
         assert!(url.contains("offset=100"));
         assert!(url.contains("limit=50"));
     }
@@ -291,4 +1181,39 @@ mod tests {
         let url = create_submission_url("http://db-api:4001");
         assert_eq!(url, "http://db-api:4001/v1/submissions");
     }
+
+    fn update(http: &StubHttp) -> Result<(), FormsError> {
+        let previous = EncryptedSubmission {
+            id: String::new(),
+            submitter_id: "bob.testnet".to_string(),
+            encrypted_blob: "45433031aa".to_string(),
+            submitted_at: "2026-01-01T00:00:00Z".to_string(),
+            is_draft: false,
+            form_version: None,
+        };
+        update_submission(http, API, FORM, &previous, "45433031bb", "secret", &timeouts())
+    }
+
+    #[test]
+    fn update_submission_sends_previous_hash() {
+        let http = StubHttp::new(200, r#"{"id":"x"}"#);
+        update(&http).unwrap();
+        let requests = http.requests.borrow();
+        assert_eq!(requests[0].0, create_submission_url(API));
+        let sent: serde_json::Value = serde_json::from_slice(&requests[0].2).unwrap();
+        assert_eq!(sent["submitter_id"], "bob.testnet");
+        assert_eq!(sent["encrypted_blob"], "45433031bb");
+        assert_eq!(sent["previous_blob_hash"], blob_hash("45433031aa"));
+        assert_eq!(blob_hash("").len(), 64);
+    }
+
+    #[test]
+    fn update_submission_error_statuses() {
+        assert_eq!(update(&StubHttp::new(404, "")).unwrap_err().to_string(), "Submission not found");
+        let err = update(&StubHttp::new(409, r#"{"error":"Submission changed since it was read"}"#)).unwrap_err();
+        assert!(err.to_string().starts_with("Submission update conflict: "), "{}", err);
+        let err = update(&StubHttp::new(429, r#"{"error":"Submitted too recently"}"#)).unwrap_err();
+        assert!(err.to_string().starts_with("Submission too recent to update: "), "{}", err);
+        assert_eq!(update(&StubHttp::new(500, "")).unwrap_err().to_string(), "Failed to update submission (status 500)");
+    }
 }
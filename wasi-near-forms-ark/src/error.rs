@@ -0,0 +1,206 @@
+//! Error type for the near-forms WASI module.
+//!
+//! Every fallible function returns [`FormsError`]. Each variant knows what part of it is
+//! safe to publish: `main` writes [`FormsError::public_message`] and [`FormsError::code`]
+//! into the on-chain `ErrorResponse`, while `Display` carries the full detail for the
+//! TEE-internal log. Clients branch on `code`, never on message text.
+
+use std::fmt;
+
+/// Shown for any failure whose detail is internal (crypto, transport, unexpected bodies).
+const GENERIC_ERROR: &str = "Request failed. Please try again or contact the form administrator.";
+
+/// Shown when a blob does not decrypt with any configured key.
+const DECRYPT_FAILED_ERROR: &str =
+    "Decryption failed: the data is corrupted or was not encrypted to this form's key.";
+
+#[derive(Debug)]
+pub enum FormsError {
+    /// No signer account: the action was not called in transaction mode.
+    AuthRequired,
+    /// The signer may not perform this action (not the creator, implicit account).
+    NotAuthorized(String),
+    /// The caller's input is malformed, too large or inconsistent with the form.
+    InvalidInput(String),
+    /// db-api refused the request for a reason the caller can act on (duplicate or replayed
+    /// submission, missing form or submission, stale compare-and-swap).
+    Rejected(String),
+    /// db-api could not be reached, answered with an error status, or returned a body
+    /// that did not parse. `status` is `None` for transport failures.
+    UpstreamHttp {
+        operation: &'static str,
+        status: Option<u16>,
+        detail: String,
+    },
+    /// The action ran out of time, usually waiting on db-api (`timing::DB_TIMEOUT_ERROR`).
+    /// Holds the public message, which says what to retry.
+    Timeout(&'static str),
+    /// A blob did not decrypt with any configured key. Holds the crypto detail.
+    DecryptFailed(String),
+    /// The TEE environment is missing or has an invalid setting. Names the variable,
+    /// never its value.
+    Config(String),
+    /// Anything else; the detail is logged but never published.
+    Internal(String),
+}
+
+impl FormsError {
+    /// Stable, machine-readable identifier for the on-chain `ErrorResponse`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FormsError::AuthRequired => "auth_required",
+            FormsError::NotAuthorized(_) => "not_authorized",
+            FormsError::InvalidInput(_) => "invalid_input",
+            FormsError::Rejected(_) => "rejected",
+            FormsError::UpstreamHttp { .. } => "upstream_unavailable",
+            FormsError::Timeout(_) => "timeout",
+            FormsError::DecryptFailed(_) => "decrypt_failed",
+            FormsError::Config(_) => "config",
+            FormsError::Internal(_) => "internal",
+        }
+    }
+
+    /// Message safe to publish on-chain: no response bodies, crypto or transport internals.
+    pub fn public_message(&self) -> String {
+        match self {
+            FormsError::NotAuthorized(message)
+            | FormsError::InvalidInput(message)
+            | FormsError::Rejected(message)
+            | FormsError::Config(message) => message.clone(),
+            FormsError::UpstreamHttp { operation, status: Some(status), .. } => format!(
+                "Database unavailable: could not {} (status {}). Please try again later.",
+                operation, status
+            ),
+            FormsError::UpstreamHttp { operation, status: None, .. } => {
+                format!("Database unavailable: could not {}. Please try again later.", operation)
+            }
+            FormsError::AuthRequired | FormsError::Timeout(_) => self.to_string(),
+            FormsError::DecryptFailed(_) => DECRYPT_FAILED_ERROR.to_string(),
+            FormsError::Internal(_) => GENERIC_ERROR.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FormsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormsError::AuthRequired => {
+                f.write_str("Authentication required - signer_account_id not available")
+            }
+            FormsError::NotAuthorized(message)
+            | FormsError::InvalidInput(message)
+            | FormsError::Rejected(message)
+            | FormsError::DecryptFailed(message)
+            | FormsError::Config(message)
+            | FormsError::Internal(message) => f.write_str(message),
+            FormsError::UpstreamHttp { operation, status, detail } => {
+                write!(f, "Failed to {}", operation)?;
+                if let Some(status) = status {
+                    write!(f, " (status {})", status)?;
+                }
+                if !detail.is_empty() {
+                    write!(f, ": {}", detail)?;
+                }
+                Ok(())
+            }
+            FormsError::Timeout(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for FormsError {}
+
+/// Untyped messages (`?` on a `format!` or a literal) are internal by default; anything
+/// meant for the caller must pick a variant explicitly.
+impl From<String> for FormsError {
+    fn from(message: String) -> Self {
+        FormsError::Internal(message)
+    }
+}
+
+impl From<&str> for FormsError {
+    fn from(message: &str) -> Self {
+        FormsError::Internal(message.to_string())
+    }
+}
+
+impl From<serde_json::Error> for FormsError {
+    fn from(e: serde_json::Error) -> Self {
+        FormsError::Internal(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing::DB_TIMEOUT_ERROR;
+
+    #[test]
+    fn upstream_public_message_hides_detail() {
+        let err = FormsError::UpstreamHttp {
+            operation: "fetch submissions",
+            status: Some(503),
+            detail: "pool timed out at 10.0.0.4".to_string(),
+        };
+        assert_eq!(err.code(), "upstream_unavailable");
+        assert_eq!(
+            err.public_message(),
+            "Database unavailable: could not fetch submissions (status 503). Please try again later."
+        );
+        assert_eq!(err.to_string(), "Failed to fetch submissions (status 503): pool timed out at 10.0.0.4");
+    }
+
+    #[test]
+    fn upstream_transport_failure_has_no_status() {
+        let err = FormsError::UpstreamHttp {
+            operation: "fetch submissions",
+            status: None,
+            detail: "Response error: timeout".to_string(),
+        };
+        assert!(!err.public_message().contains("timeout"));
+        assert_eq!(err.to_string(), "Failed to fetch submissions: Response error: timeout");
+    }
+
+    #[test]
+    fn caller_facing_variants_publish_their_message() {
+        for err in [
+            FormsError::NotAuthorized("Not authorized to read responses".into()),
+            FormsError::InvalidInput("encrypted_answers too short: 10 bytes, need at least 65".into()),
+            FormsError::Rejected("You have already submitted this form. Each account can only submit once.".into()),
+            FormsError::Config("MAX_BLOB_SIZE_BYTES must be a positive integer".into()),
+            FormsError::AuthRequired,
+            FormsError::Timeout(DB_TIMEOUT_ERROR),
+        ] {
+            assert_eq!(err.public_message(), err.to_string(), "{}", err.code());
+        }
+        assert_eq!(FormsError::Timeout(DB_TIMEOUT_ERROR).public_message(), DB_TIMEOUT_ERROR);
+    }
+
+    #[test]
+    fn internal_detail_is_hidden() {
+        let err: FormsError = "secp256k1 scalar multiplication failed: InvalidSecretKey".into();
+        assert_eq!(err.code(), "internal");
+        assert_eq!(err.public_message(), GENERIC_ERROR);
+
+        let err = FormsError::DecryptFailed("ChaCha20-Poly1305 decryption failed: aead::Error".into());
+        assert_eq!(err.public_message(), DECRYPT_FAILED_ERROR);
+        assert!(err.to_string().contains("ChaCha20"));
+    }
+
+    #[test]
+    fn codes_are_distinct() {
+        let errors = [
+            FormsError::AuthRequired,
+            FormsError::NotAuthorized(String::new()),
+            FormsError::InvalidInput(String::new()),
+            FormsError::Rejected(String::new()),
+            FormsError::UpstreamHttp { operation: "", status: None, detail: String::new() },
+            FormsError::Timeout(DB_TIMEOUT_ERROR),
+            FormsError::DecryptFailed(String::new()),
+            FormsError::Config(String::new()),
+            FormsError::Internal(String::new()),
+        ];
+        let codes: std::collections::BTreeSet<_> = errors.iter().map(FormsError::code).collect();
+        assert_eq!(codes.len(), errors.len());
+    }
+}
@@ -5,15 +5,19 @@
 //!
 //! Adapted from near-email's http_chunked.rs implementation.
 
-use std::time::Duration;
+use crate::error::FormsError;
+use crate::timing::{Deadline, HttpTimeouts, DB_TIMEOUT_ERROR};
+use wasi::clocks::monotonic_clock;
 use wasi::http::{
     outgoing_handler,
-    types::{Headers, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme},
+    types::{ErrorCode, Headers, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme},
 };
+use wasi::io::poll::{self, Pollable};
 
 /// Response from a chunked HTTP request
 pub struct ChunkedResponse {
     status: u16,
+    content_type: Option<String>,
     body: Vec<u8>,
 }
 
@@ -22,11 +26,71 @@ impl ChunkedResponse {
         self.status
     }
 
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
     pub fn body(&self) -> &[u8] {
         &self.body
     }
 }
 
+/// First `Content-Type` value of an incoming response, if any (lossy UTF-8)
+pub fn response_content_type(headers: &Headers) -> Option<String> {
+    headers
+        .get(&"content-type".to_string())
+        .first()
+        .map(|value| String::from_utf8_lossy(value).into_owned())
+}
+
+/// Build `RequestOptions` from `timeouts` (connect jittered; read used for both first-byte
+/// and between-bytes; everything capped to the deadline if one is set).
+pub fn request_options(timeouts: &HttpTimeouts) -> Result<RequestOptions, FormsError> {
+    let (connect, read) = timeouts.for_request()?;
+    // Safe: timeouts are configured in seconds, far below u64::MAX nanos (~584 years)
+    let connect_nanos = connect.as_nanos() as u64;
+    let read_nanos = read.as_nanos() as u64;
+    let options = RequestOptions::new();
+    options
+        .set_connect_timeout(Some(connect_nanos))
+        .map_err(|e| format!("Failed to set connect timeout: {:?}", e))?;
+    options
+        .set_first_byte_timeout(Some(read_nanos))
+        .map_err(|e| format!("Failed to set first byte timeout: {:?}", e))?;
+    options
+        .set_between_bytes_timeout(Some(read_nanos))
+        .map_err(|e| format!("Failed to set between bytes timeout: {:?}", e))?;
+    Ok(options)
+}
+
+/// Block until `pollable` is ready, or fail with [`DB_TIMEOUT_ERROR`] if `deadline`
+/// passes first. Without a deadline this is a plain `block()`.
+pub fn block_until(pollable: &Pollable, deadline: Option<&Deadline>) -> Result<(), FormsError> {
+    let Some(deadline) = deadline else {
+        pollable.block();
+        return Ok(());
+    };
+    let left = deadline.remaining().ok_or(FormsError::Timeout(DB_TIMEOUT_ERROR))?;
+    let timer = monotonic_clock::subscribe_duration(left.as_nanos() as u64);
+    if poll::poll(&[pollable, &timer]).contains(&0) {
+        Ok(())
+    } else {
+        Err(FormsError::Timeout(DB_TIMEOUT_ERROR))
+    }
+}
+
+/// Convert a host HTTP error, reporting any timeout variant as [`DB_TIMEOUT_ERROR`].
+pub fn http_error(e: ErrorCode) -> FormsError {
+    match e {
+        ErrorCode::DnsTimeout
+        | ErrorCode::ConnectionTimeout
+        | ErrorCode::ConnectionReadTimeout
+        | ErrorCode::ConnectionWriteTimeout
+        | ErrorCode::HttpResponseTimeout => FormsError::Timeout(DB_TIMEOUT_ERROR),
+        other => format!("HTTP error: {:?}", other).into(),
+    }
+}
+
 /// Send a POST request with chunked body encoding to bypass WASI 4KB write limit.
 ///
 /// Uses low-level wasi::http types to write the body in chunks via
@@ -35,9 +99,31 @@ pub fn post_chunked(
     url: &str,
     content_type: &str,
     body: &[u8],
-    timeout: Duration,
+    timeouts: &HttpTimeouts,
     api_secret: Option<&str>,
-) -> Result<ChunkedResponse, Box<dyn std::error::Error>> {
+) -> Result<ChunkedResponse, FormsError> {
+    send_chunked(Method::Post, url, content_type, body, timeouts, api_secret)
+}
+
+/// Send a PUT request with chunked body encoding (see `post_chunked`).
+pub fn put_chunked(
+    url: &str,
+    content_type: &str,
+    body: &[u8],
+    timeouts: &HttpTimeouts,
+    api_secret: Option<&str>,
+) -> Result<ChunkedResponse, FormsError> {
+    send_chunked(Method::Put, url, content_type, body, timeouts, api_secret)
+}
+
+fn send_chunked(
+    method: Method,
+    url: &str,
+    content_type: &str,
+    body: &[u8],
+    timeouts: &HttpTimeouts,
+    api_secret: Option<&str>,
+) -> Result<ChunkedResponse, FormsError> {
     let parsed = url::Url::parse(url)
         .map_err(|e| format!("Failed to parse URL '{}': {}", url, e))?;
 
@@ -83,7 +169,7 @@ pub fn post_chunked(
 
     let request = OutgoingRequest::new(headers);
     request
-        .set_method(&Method::Post)
+        .set_method(&method)
         .map_err(|e| format!("Failed to set method: {:?}", e))?;
     request
         .set_scheme(Some(&scheme))
@@ -148,33 +234,23 @@ pub fn post_chunked(
     OutgoingBody::finish(outgoing_body, None)
         .map_err(|e| format!("Failed to finish outgoing body: {:?}", e))?;
 
-    let options = RequestOptions::new();
-    // Safe: 30s = 3e10 nanos, well within u64::MAX (1.8e19)
-    let timeout_nanos = timeout.as_nanos() as u64;
-    options
-        .set_connect_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set connect timeout: {:?}", e))?;
-    options
-        .set_first_byte_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set first byte timeout: {:?}", e))?;
-    options
-        .set_between_bytes_timeout(Some(timeout_nanos))
-        .map_err(|e| format!("Failed to set between bytes timeout: {:?}", e))?;
+    let options = request_options(timeouts)?;
 
     let future_response = outgoing_handler::handle(request, Some(options))
         .map_err(|e| format!("Failed to send request: {:?}", e))?;
 
     let pollable = future_response.subscribe();
-    pollable.block();
+    block_until(&pollable, timeouts.deadline.as_ref())?;
     drop(pollable);
 
     let response = future_response
         .get()
         .ok_or("No response received")?
         .map_err(|e| format!("Response error: {:?}", e))?
-        .map_err(|e| format!("HTTP error: {:?}", e))?;
+        .map_err(http_error)?;
 
     let status = response.status();
+    let content_type_header = response_content_type(&response.headers());
 
     let incoming_body = response
         .consume()
@@ -194,7 +270,7 @@ pub fn post_chunked(
             return Err("Read loop exceeded maximum iterations".into());
         }
         let pollable = input_stream.subscribe();
-        pollable.block();
+        block_until(&pollable, timeouts.deadline.as_ref())?;
         drop(pollable);
 
         match input_stream.read(65536) {
@@ -214,6 +290,7 @@ pub fn post_chunked(
 
     Ok(ChunkedResponse {
         status,
+        content_type: content_type_header,
         body: response_body,
     })
 }
@@ -1,26 +1,58 @@
 //! OutLayer WASI module for near-forms
 //!
-//! Three actions:
+//! Actions:
 //! 1. ReadResponses: Creator reads decrypted form submissions (Transaction mode)
 //! 2. SubmitForm: Respondent submits encrypted answers (Transaction mode)
 //! 3. GetMasterPublicKey: Returns the master public key (no auth required)
+//! 4. GetFormPublicKey: Returns the derived form public key (no auth required)
+//! 5. ImportResponses: Creator encrypts and stores legacy plaintext responses (Transaction mode)
+//! 6. ReEncryptResponses: Creator migrates submissions from the previous to the current master key (Transaction mode)
+//! 7. EstimateSize: Checks encrypted answers against the size cap before submitting (no auth required)
+//! 8. VerifySubmission: Respondent (or a form owner) checks a stored submission against its receipt hash (Transaction mode)
+//! 9. Diagnostics: Reports which configuration checks pass, never secret values (no auth required)
 
+#[cfg(test)]
+mod alloc_peak;
+mod anomalies;
+mod answers;
+mod blob_format;
 mod crypto;
 mod db;
+mod error;
 mod http_chunked;
+mod read_cursor;
+mod timing;
 mod types;
 mod validation;
 
 use libsecp256k1::{PublicKey, SecretKey};
 use outlayer::env;
+use std::time::Duration;
+use timing::{Deadline, HttpTimeouts, PhaseTimer};
+use blob_format::StoredBlob;
+use error::FormsError;
 use types::*;
-use validation::{is_implicit_account, sanitize_error, validate_ec01_hex};
+use validation::{
+    blob_too_large, check_answer_keys, check_encoded_size, check_not_empty, check_required_answers, has_required_questions, decoded_size, is_form_creator, is_form_owner, is_implicit_account, is_utc_timestamp, parse_input, receipt_matches, validate_ec01_hex, validate_per_question_hex, DEFAULT_MAX_BLOB_SIZE,
+};
 
 // ==================== Hardcoded Single Form Config ====================
 
 /// Fixed form ID — must match `FORM_ID` in `db-api/src/main.rs`.
 const FORM_ID: &str = "daf14a0c-20f7-4199-a07b-c6456d53ef2d";
 
+/// Transport for every db-api call. The one place to swap in an instrumented or
+/// connection-reusing `db::HttpClient`.
+const DB_HTTP: db::WasiHttpClient = db::WasiHttpClient;
+
+/// Rejection for an implicit (64-char hex) signer
+const IMPLICIT_ACCOUNT_ERROR: &str =
+    "Implicit accounts (64-char hex) are not allowed. Please use a named NEAR account.";
+
+/// Rejection for an implicit (64-char hex) submitter
+const IMPLICIT_SUBMITTER_ERROR: &str =
+    "Implicit accounts (64-char hex) are not allowed to submit forms. Please use a named NEAR account.";
+
 /// Maximum responses per page (caps user-provided limit)
 const MAX_PAGE_LIMIT: u32 = 200;
 
@@ -31,14 +63,14 @@ const MAX_PAGE_LIMIT: u32 = 200;
 const MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
 
 /// Database API URL (internal service)
-fn get_database_url() -> Result<String, Box<dyn std::error::Error>> {
+fn get_database_url() -> Result<String, FormsError> {
     std::env::var("DATABASE_API_URL")
-        .map_err(|_| "DATABASE_API_URL environment variable not found".into())
+        .map_err(|_| FormsError::Config("DATABASE_API_URL environment variable not found".to_string()))
 }
 
 /// Shared secret for db-api authentication.
 /// Prefers DATABASE_API_SECRET, falls back to API_SECRET.
-fn get_api_secret() -> Result<String, Box<dyn std::error::Error>> {
+fn get_api_secret() -> Result<String, FormsError> {
     let primary = std::env::var("DATABASE_API_SECRET").ok();
     let fallback = std::env::var("API_SECRET").ok();
 
@@ -55,15 +87,168 @@ fn get_api_secret() -> Result<String, Box<dyn std::error::Error>> {
 
     primary
         .or(fallback)
-        .ok_or_else(|| "API_SECRET or DATABASE_API_SECRET environment variable not found".into())
+        .ok_or_else(|| FormsError::Config("API_SECRET or DATABASE_API_SECRET environment variable not found".to_string()))
+}
+
+/// Whether to ping db-api `/health` before running a database-backed action.
+/// Opt-in via DB_HEALTH_PREFLIGHT=true (default: false) since it costs an extra round trip.
+fn health_preflight_enabled() -> Result<bool, FormsError> {
+    match std::env::var("DB_HEALTH_PREFLIGHT").ok().as_deref() {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(_) => Err(FormsError::Config("DB_HEALTH_PREFLIGHT must be 'true' or 'false'".to_string())),
+    }
+}
+
+/// Whether to run `crypto::self_test` before every action.
+/// Opt-in via RUN_SELF_TEST=true (default: false): each call starts a fresh WASI instance,
+/// so it would otherwise cost two EC01 round trips even on GetMasterPublicKey.
+fn self_test_enabled() -> Result<bool, FormsError> {
+    match std::env::var("RUN_SELF_TEST").ok().as_deref() {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(_) => Err(FormsError::Config("RUN_SELF_TEST must be 'true' or 'false'".to_string())),
+    }
+}
+
+/// Whether ReadResponses retries answers JSON that fails to parse with a permissive parse
+/// (BOM, trailing commas, trailing bytes) instead of skipping the submission.
+/// Opt-in via LENIENT_ANSWERS_JSON=true (default: false); recovered responses are tagged.
+fn lenient_answers_enabled() -> Result<bool, FormsError> {
+    match std::env::var("LENIENT_ANSWERS_JSON").ok().as_deref() {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(_) => Err(FormsError::Config("LENIENT_ANSWERS_JSON must be 'true' or 'false'".to_string())),
+    }
+}
+
+/// Maximum encrypted blob size in bytes, from MAX_BLOB_SIZE_BYTES (default 200 KB).
+/// Must match db-api's MAX_BLOB_SIZE_BYTES so a blob accepted here is not rejected there.
+fn max_blob_size() -> Result<usize, FormsError> {
+    match std::env::var("MAX_BLOB_SIZE_BYTES") {
+        Err(_) => Ok(DEFAULT_MAX_BLOB_SIZE),
+        Ok(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(FormsError::Config("MAX_BLOB_SIZE_BYTES must be a positive integer".to_string())),
+        },
+    }
+}
+
+/// Maximum decrypted answers size in bytes, from MAX_PLAINTEXT_SIZE_BYTES (default 200 KB).
+fn max_plaintext_size() -> Result<usize, FormsError> {
+    match std::env::var("MAX_PLAINTEXT_SIZE_BYTES") {
+        Err(_) => Ok(answers::DEFAULT_MAX_PLAINTEXT_SIZE),
+        Ok(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(FormsError::Config("MAX_PLAINTEXT_SIZE_BYTES must be a positive integer".to_string())),
+        },
+    }
+}
+
+/// Structural limits on answers JSON, from MAX_ANSWERS_DEPTH (default 16) and
+/// MAX_ANSWERS_KEYS (object keys at any depth, default 1000).
+fn answer_limits() -> Result<answers::JsonLimits, FormsError> {
+    Ok(answers::JsonLimits {
+        max_depth: env_count("MAX_ANSWERS_DEPTH", answers::DEFAULT_MAX_ANSWERS_DEPTH)?,
+        max_keys: env_count("MAX_ANSWERS_KEYS", answers::DEFAULT_MAX_ANSWERS_KEYS)?,
+    })
+}
+
+/// Read a positive integer from `name`, or `default` if unset.
+fn env_count(name: &str, default: usize) -> Result<usize, FormsError> {
+    match std::env::var(name) {
+        Err(_) => Ok(default),
+        Ok(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(FormsError::Config(format!("{} must be a positive integer", name))),
+        },
+    }
+}
+
+/// Read a positive whole number of seconds from `name`, or `default` if unset.
+fn env_secs(name: &str, default: u64) -> Result<Duration, FormsError> {
+    match std::env::var(name) {
+        Err(_) => Ok(Duration::from_secs(default)),
+        Ok(value) => match value.parse::<u64>() {
+            Ok(n) if n > 0 => Ok(Duration::from_secs(n)),
+            _ => Err(FormsError::Config(format!("{} must be a positive number of seconds", name))),
+        },
+    }
+}
+
+/// ReadResponses time budget from READ_RESPONSES_BUDGET_SECS (default 45), capped at
+/// READ_RESPONSES_DEADLINE_SECS so a lowered deadline never fires first.
+fn read_budget() -> Result<Deadline, FormsError> {
+    let budget = env_secs("READ_RESPONSES_BUDGET_SECS", 45)?;
+    let deadline = env_secs("READ_RESPONSES_DEADLINE_SECS", 60)?;
+    Ok(Deadline::after(budget.min(deadline)))
+}
+
+/// The form's key derivation salt (None for forms older than the column). db-api keeps it
+/// off the public form, so it costs an authenticated request of its own.
+fn form_derivation_salt(db_url: &str, timeouts: &HttpTimeouts) -> Result<Option<String>, FormsError> {
+    db::get_form_derivation_salt(&DB_HTTP, db_url, FORM_ID, &get_api_secret()?, timeouts)
+}
+
+/// db-api HTTP timeouts from DB_CONNECT_TIMEOUT_SECS (default 10) and
+/// DB_READ_TIMEOUT_SECS (first byte and between body chunks, default 30).
+fn db_timeouts() -> Result<HttpTimeouts, FormsError> {
+    Ok(HttpTimeouts {
+        connect: env_secs("DB_CONNECT_TIMEOUT_SECS", 10)?,
+        read: env_secs("DB_READ_TIMEOUT_SECS", 30)?,
+        deadline: None,
+    })
+}
+
+/// Load `PROTECTED_MASTER_KEY`. "Not configured" (unset or blank) and "configured but
+/// invalid" get different messages because the operator fix differs: add the secret vs.
+/// replace its value. Neither message echoes any part of the value.
+fn load_master_key() -> Result<SecretKey, FormsError> {
+    match std::env::var("PROTECTED_MASTER_KEY") {
+        Ok(master_key_hex) if !master_key_hex.trim().is_empty() => crypto::parse_private_key(&master_key_hex)
+            .map_err(|e| FormsError::Config(format!("PROTECTED_MASTER_KEY is set but invalid: {}", e))),
+        Ok(_) | Err(std::env::VarError::NotPresent) => Err(FormsError::Config(
+            "PROTECTED_MASTER_KEY is not configured: add the master private key (64 hex characters) to the protected secrets"
+                .to_string(),
+        )),
+        Err(std::env::VarError::NotUnicode(_)) => Err(FormsError::Config(
+            "PROTECTED_MASTER_KEY is set but invalid: value is not UTF-8 text (expected 64 hex characters)".to_string(),
+        )),
+    }
 }
 
-/// Load master private key from env
-fn load_master_key() -> Result<SecretKey, Box<dyn std::error::Error>> {
-    if let Ok(master_key_hex) = std::env::var("PROTECTED_MASTER_KEY") {
-        return crypto::parse_private_key(&master_key_hex);
+/// Optional tenant label from FORM_KEY_TENANT, mixed into every form key derivation so
+/// deployments sharing a master key still get unrelated form keys. Unset or empty means
+/// the original derivation. Changing it invalidates every existing derived key: stored
+/// submissions stop decrypting and clients must use the new form public key.
+fn form_key_tenant() -> Result<Option<String>, FormsError> {
+    match std::env::var("FORM_KEY_TENANT") {
+        Ok(tenant) if !tenant.is_empty() => {
+            crypto::validate_tenant(&tenant)?;
+            Ok(Some(tenant))
+        }
+        _ => Ok(None),
     }
-    Err("Master key (PROTECTED_MASTER_KEY) not found in env".into())
+}
+
+/// Key version label for `PROTECTED_MASTER_KEY`
+const KEY_VERSION_CURRENT: &str = "current";
+/// Key version label for `PROTECTED_MASTER_KEY_PREV`
+const KEY_VERSION_PREVIOUS: &str = "previous";
+
+/// Load every master key usable for decryption, newest first.
+///
+/// `PROTECTED_MASTER_KEY` is required; `PROTECTED_MASTER_KEY_PREV` is optional and only
+/// set during a key rotation so submissions encrypted to the old form key stay readable.
+/// A PREV value that fails to parse is an error rather than silently ignored.
+fn load_master_keys() -> Result<Vec<(&'static str, SecretKey)>, FormsError> {
+    let mut keys = vec![(KEY_VERSION_CURRENT, load_master_key()?)];
+    if let Ok(prev_hex) = std::env::var("PROTECTED_MASTER_KEY_PREV") {
+        let prev = crypto::parse_private_key(&prev_hex)
+            .map_err(|e| FormsError::Config(format!("Invalid PROTECTED_MASTER_KEY_PREV: {}", e)))?;
+        keys.push((KEY_VERSION_PREVIOUS, prev));
+    }
+    Ok(keys)
 }
 
 fn main() {
@@ -78,115 +263,414 @@ fn main() {
         Err(e) => {
             // Log full error details to stderr for debugging (TEE-internal only)
             eprintln!("near-forms error: {}", e);
-            // Return sanitized error to on-chain response (visible to anyone)
-            let user_message = sanitize_error(&format!("{}", e));
+            // Return only the public part to the on-chain response (visible to anyone),
+            // with a stable code the UI can branch on
             let error_response = ErrorResponse {
                 success: false,
-                error: user_message,
+                error: e.public_message(),
+                code: e.code(),
             };
             env::output_json(&error_response).expect("Failed to serialize error response JSON");
         }
     }
 }
 
-fn process() -> Result<Output, Box<dyn std::error::Error>> {
+fn process() -> Result<Output, FormsError> {
+    // Before anything is encrypted or decrypted: a build whose crypto is off must not store
+    // submissions nobody can read (or hand out a wrong form public key)
+    if self_test_enabled()? {
+        crypto::self_test()?;
+    }
+
     // Get the input (determines which action to perform)
     // env::input() returns Vec<u8>, return error if parsing fails
     let body = env::input();
-    let input: Input = serde_json::from_slice(&body)
-        .map_err(|e| format!("Invalid input JSON: {}", e))?;
+    let input = parse_input(&body)?;
+
+    // Optional preflight: fail fast with a clean error if db-api is down, instead of a
+    // confusing mid-flow failure. The public-key and size actions never touch the database,
+    // and Diagnostics reports an unreachable db-api instead of failing on it.
+    let needs_db = !matches!(
+        input,
+        Input::GetMasterPublicKey(_) | Input::GetFormPublicKey(_) | Input::EstimateSize(_) | Input::Diagnostics(_)
+    );
+    if needs_db && health_preflight_enabled()? {
+        db::health(&DB_HTTP, &get_database_url()?, &db_timeouts()?)?;
+    }
 
     match input {
         Input::ReadResponses(read_input) => handle_read_responses(read_input),
         Input::SubmitForm(submit_input) => handle_submit_form(submit_input),
         Input::GetMasterPublicKey(_) => handle_get_master_public_key(),
+        Input::GetFormPublicKey(_) => handle_get_form_public_key(),
+        Input::ImportResponses(import_input) => handle_import_responses(import_input),
+        Input::ReEncryptResponses(re_encrypt_input) => handle_re_encrypt_responses(re_encrypt_input),
+        Input::EstimateSize(estimate_input) => handle_estimate_size(estimate_input),
+        Input::VerifySubmission(verify_input) => handle_verify_submission(verify_input),
+        Input::Diagnostics(_) => handle_diagnostics(),
     }
 }
 
+/// Handle Diagnostics action (deployment self-check, no auth required)
+/// Runs each configuration check independently and reports pass/fail instead of stopping
+/// at the first error. Output is public, so it carries only booleans, the form public key,
+/// and public error messages (the full errors go to stderr), none of which echo an env value.
+fn handle_diagnostics() -> Result<Output, FormsError> {
+    let mut problems = Vec::new();
+    let mut check = |result: Result<(), FormsError>| match result {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Diagnostics: {}", e);
+            problems.push(e.public_message());
+            false
+        }
+    };
+
+    let db_url = get_database_url();
+    let db_url_set = db_url.is_ok();
+    let db_reachable = check(match &db_url {
+        Ok(url) => db_timeouts().and_then(|timeouts| db::health(&DB_HTTP, url, &timeouts)),
+        Err(e) => Err(FormsError::Config(e.to_string())),
+    });
+    let master_keys = load_master_keys();
+    let master_key_valid = check(master_keys.as_ref().map(|_| ()).map_err(|e| FormsError::Config(e.public_message())));
+    let api_secret_set = check(get_api_secret().map(|_| ()));
+    // Fixed test key only, so the failure detail is safe to return as-is
+    let crypto_self_test_passed = check(crypto::self_test().map_err(|e| FormsError::Config(e.to_string())));
+
+    // Same derivation as GetFormPublicKey, without registering the key with db-api
+    let form_pubkey = match (&master_keys, db_url) {
+        (Ok(keys), Ok(url)) if db_reachable => {
+            let derived = (|| -> Result<String, FormsError> {
+                let salt = form_derivation_salt(&url, &db_timeouts()?)?;
+                let master_pubkey = PublicKey::from_secret_key(&keys[0].1);
+                let form_pubkey = crypto::derive_form_pubkey(&master_pubkey, FORM_ID, form_key_tenant()?.as_deref(), salt.as_deref())?;
+                Ok(hex::encode(form_pubkey.serialize_compressed()))
+            })();
+            derived
+                .map_err(|e| {
+                    eprintln!("Diagnostics: form public key: {}", e);
+                    problems.push(format!("Form public key: {}", e.public_message()));
+                })
+                .ok()
+        }
+        _ => None,
+    };
+
+    Ok(Output::Diagnostics(DiagnosticsOutput {
+        db_url_set,
+        db_reachable,
+        master_key_valid,
+        api_secret_set,
+        crypto_self_test_passed,
+        form_pubkey,
+        problems,
+    }))
+}
+
 /// Handle GetMasterPublicKey action (returns compressed secp256k1 public key)
-/// No auth required — the public key is not sensitive.
-fn handle_get_master_public_key() -> Result<Output, Box<dyn std::error::Error>> {
+/// No auth required — the public key is not sensitive. Also describes the key format and
+/// derivation scheme, so clients need not hardcode them.
+fn handle_get_master_public_key() -> Result<Output, FormsError> {
     let master_privkey = load_master_key()?;
     let master_pubkey = PublicKey::from_secret_key(&master_privkey);
     let pubkey_hex = hex::encode(master_pubkey.serialize_compressed());
     Ok(Output::GetMasterPublicKey(GetMasterPublicKeyOutput {
         master_public_key: pubkey_hex,
+        curve: "secp256k1",
+        encoding: "compressed",
+        scheme: crypto::SCHEME,
+        derivation_prefix: crypto::derivation_prefix(form_key_tenant()?.as_deref()),
+    }))
+}
+
+/// Handle GetFormPublicKey action (returns the compressed form public key for FORM_ID)
+/// No auth required. Derived from the master public key and the form's derivation salt,
+/// so clients can encrypt submissions without reimplementing `deriveFormPublicKey()`.
+/// Also registers the key with db-api, which serves it at GET /forms/{id}/public-key.
+fn handle_get_form_public_key() -> Result<Output, FormsError> {
+    let master_privkey = load_master_key()?;
+    let master_pubkey = PublicKey::from_secret_key(&master_privkey);
+    let tenant = form_key_tenant()?;
+    let db_url = get_database_url()?;
+    let timeouts = db_timeouts()?;
+    let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+    let salt = form_derivation_salt(&db_url, &timeouts)?;
+    let form_pubkey = crypto::derive_form_pubkey(&master_pubkey, FORM_ID, tenant.as_deref(), salt.as_deref())?;
+    let form_public_key = hex::encode(form_pubkey.serialize_compressed());
+
+    // Keep db-api's cached copy current (first call, or after a master key rotation). The
+    // key is returned either way; a failed registration is retried on the next call.
+    if form.public_key.as_deref() != Some(form_public_key.as_str()) {
+        let registered = get_api_secret().and_then(|secret| {
+            db::set_form_public_key(&DB_HTTP, &db_url, FORM_ID, &form_public_key, &secret, &timeouts)
+        });
+        if let Err(e) = registered {
+            eprintln!("GetFormPublicKey: could not register the form public key with db-api: {}", e);
+        }
+    }
+    Ok(Output::GetFormPublicKey(GetFormPublicKeyOutput { form_public_key }))
+}
+
+/// Authorize `caller_id` as an owner of the form: its creator, or a co-owner in db-api's
+/// `form_owners`. The creator needs no extra round trip; anyone else costs one
+/// authenticated GET. `action` completes "Not authorized to ...".
+///
+/// Returns the question IDs the owner may read (None: all of them; always None for the
+/// creator). Only ReadResponses applies it.
+fn authorize_owner(
+    caller_id: &str,
+    form: &FormMetadata,
+    db_url: &str,
+    timeouts: &HttpTimeouts,
+    action: &str,
+) -> Result<Option<Vec<String>>, FormsError> {
+    if is_form_creator(caller_id, &form.creator_id) {
+        return Ok(None);
+    }
+    let owners = db::get_form_owners(&DB_HTTP, db_url, FORM_ID, &get_api_secret()?, timeouts)?;
+    let account_ids: Vec<String> = owners.iter().map(|o| o.account_id.clone()).collect();
+    if !is_form_owner(caller_id, &account_ids) {
+        return Err(FormsError::NotAuthorized(format!("Not authorized to {}", action)));
+    }
+    Ok(owners.into_iter().find(|o| o.account_id == caller_id).and_then(|o| o.visible_questions))
+}
+
+/// Reject a blob layout the form's `encryption_scheme` does not allow, so a client cannot
+/// pick a different format than the form requires (db-api enforces the same on insert).
+fn check_encryption_scheme(scheme: EncryptionScheme, per_question: bool) -> Result<(), FormsError> {
+    let required = match scheme {
+        EncryptionScheme::Any => return Ok(()),
+        EncryptionScheme::Ec01 if !per_question => return Ok(()),
+        EncryptionScheme::Ec01PerQuestion if per_question => return Ok(()),
+        EncryptionScheme::Ec01 => "a single EC01 hex blob",
+        EncryptionScheme::Ec01PerQuestion => "a JSON object of question ID to EC01 hex",
+    };
+    Err(FormsError::InvalidInput(format!("This form requires encrypted answers as {}", required)))
+}
+
+/// Handle EstimateSize action (no auth required, nothing stored)
+/// Lets clients warn respondents about an oversized submission before SubmitForm fails.
+/// Uses the same size check as SubmitForm; EC01 structure is left to SubmitForm.
+fn handle_estimate_size(input: EstimateSizeInput) -> Result<Output, FormsError> {
+    let max_blob_size_bytes = max_blob_size()?;
+    let decoded_size_bytes = decoded_size(&input.encrypted_answers)?;
+    let within_limit = check_encoded_size(input.encrypted_answers.len(), max_blob_size_bytes).is_ok();
+    Ok(Output::EstimateSize(EstimateSizeOutput {
+        decoded_size_bytes,
+        max_blob_size_bytes,
+        within_limit,
     }))
 }
 
 /// Handle ReadResponses action (creator reads decrypted submissions)
-/// Requires: signer is the form creator
+/// Requires: signer is a form owner (creator or co-owner)
 /// Response is encrypted to the caller's ephemeral public key so plaintext never appears on-chain.
 /// Supports pagination via offset/limit and response size budgeting.
-fn handle_read_responses(input: ReadResponsesInput) -> Result<Output, Box<dyn std::error::Error>> {
+fn handle_read_responses(input: ReadResponsesInput) -> Result<Output, FormsError> {
     // 1. Authenticate via OutLayer TEE (transaction mode) — before parsing untrusted input
-    let caller_id = env::signer_account_id()
-        .ok_or("Authentication required - signer_account_id not available")?;
+    let caller_id = env::signer_account_id().ok_or(FormsError::AuthRequired)?;
 
     // 1b. Reject implicit accounts (consistency with SubmitForm — defense-in-depth)
     if is_implicit_account(&caller_id) {
-        return Err("Implicit accounts (64-char hex) are not allowed. Please use a named NEAR account.".into());
+        return Err(FormsError::NotAuthorized(IMPLICIT_ACCOUNT_ERROR.to_string()));
     }
 
-    // 2. Load master private key early (fail fast before DB round-trip)
-    let master_privkey = load_master_key()?;
+    // 2. Load master private keys early (fail fast before DB round-trip)
+    let master_keys = load_master_keys()?;
+    let tenant = form_key_tenant()?;
+
+    // Hard cap on the whole action so a slow db-api fails predictably instead of
+    // holding the TEE; every db call below shares this deadline.
+    let deadline = Deadline::after(env_secs("READ_RESPONSES_DEADLINE_SECS", 60)?);
+    let timeouts = db_timeouts()?.with_deadline(deadline);
+    // Softer budget inside that deadline: once spent, decryption stops and the page returns
+    // what it has (truncated, with a cursor) instead of running into the hard timeout
+    let budget = read_budget()?;
 
-    // 3. Fetch form metadata and verify caller is the creator (before parsing untrusted input)
+    // 3. Fetch form metadata and verify caller is an owner (before parsing untrusted input)
     let db_url = get_database_url()?;
-    let form = db::get_form(&db_url, FORM_ID)?;
-    if caller_id != form.creator_id {
-        return Err("Not authorized to read responses".into());
+    let mut timings = ReadResponsesTimings::default();
+    let mut timer = PhaseTimer::start();
+    let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+    timings.fetch_form = timer.lap();
+    let permitted_questions = authorize_owner(&caller_id, &form, &db_url, &timeouts, "read responses")?;
+    // Answers to other questions are left out of the decrypted output (never decrypted at
+    // all in the per-question layout)
+    let visible = answers::visible_question_ids(input.visible_questions.as_deref(), permitted_questions.as_deref());
+    // The ciphertext covers every answer, hidden ones included
+    if input.include_ciphertext && visible.is_some() {
+        return Err(FormsError::InvalidInput(
+            "include_ciphertext cannot be combined with visible_questions".to_string(),
+        ));
+    }
+    // Filtering on a hidden question would reveal its answers through which rows come back
+    if let (Some(filter), Some(visible)) = (&input.filter, &visible) {
+        if !visible.contains(&filter.question_id) {
+            return Err(FormsError::InvalidInput(format!(
+                "filter question '{}' is not among the visible questions",
+                filter.question_id
+            )));
+        }
     }
 
     // 4. Parse and validate response_pubkey (after authorization — unauthorized callers
     //    should always see "Not authorized", not "Invalid response_pubkey")
     let response_pubkey = crypto::parse_public_key(&input.response_pubkey)
-        .map_err(|e| format!("Invalid response_pubkey: {}", e))?;
+        .map_err(|e| FormsError::InvalidInput(format!("Invalid response_pubkey: {}", e)))?;
 
-    // 5. Fetch paginated encrypted submissions from db-api
+    // 5. Fetch paginated encrypted submissions from db-api: by offset, or after the position
+    //    in a continuation token from the previous page
     let api_secret = get_api_secret()?;
     let limit = input.limit.clamp(1, MAX_PAGE_LIMIT);
     // Reject absurdly large offsets instead of silently clamping (confusing pagination)
     // Prevents memory exhaustion from absurdly large page offsets
     const MAX_OFFSET: u32 = 1_000_000;
     if input.offset > MAX_OFFSET {
-        return Err(format!("Offset too large: {} (max: {})", input.offset, MAX_OFFSET).into());
+        return Err(FormsError::InvalidInput(format!("Offset too large: {} (max: {})", input.offset, MAX_OFFSET)));
     }
     let offset = input.offset;
-    let page = db::get_submissions(&db_url, FORM_ID, &api_secret, offset, limit)?;
+    if input.cursor.is_some() && offset > 0 {
+        return Err(FormsError::InvalidInput("cursor and offset cannot be combined".to_string()));
+    }
+    // An incremental walk's `since` travels in its cursor from the second page on
+    if let Some(since) = &input.since {
+        if input.cursor.is_some() {
+            return Err(FormsError::InvalidInput("cursor and since cannot be combined".to_string()));
+        }
+        if !is_utc_timestamp(since) {
+            return Err(FormsError::InvalidInput(
+                "since must be a UTC RFC 3339 timestamp (high_water_mark from an earlier read)".to_string(),
+            ));
+        }
+    }
+
+    // 6. Derive the form-specific private key for each master key version. A form with no
+    //    submissions yet (or a page past the end) has nothing to decrypt: skip straight to an
+    //    empty payload so "no responses" never surfaces as an error. A cursor needs the keys
+    //    up front to be read.
+    let salt = form_derivation_salt(&db_url, &timeouts)?;
+    let derive_form_privkeys = || {
+        master_keys
+            .iter()
+            .map(|(version, master)| Ok((*version, crypto::derive_form_privkey(master, FORM_ID, tenant.as_deref(), salt.as_deref())?)))
+            .collect::<Result<Vec<_>, FormsError>>()
+    };
+    let resume = match input.cursor.as_deref() {
+        Some(token) => Some(read_cursor::decode(token, &derive_form_privkeys()?)?),
+        None => None,
+    };
+
+    timer.lap();
+    // `more_in_db`: whether db-api holds submissions after this whole page
+    let (since, walk_high_water_mark) = match &resume {
+        Some(resume) => (resume.since.clone(), resume.high_water_mark.clone()),
+        None => (input.since.clone(), input.since.clone()),
+    };
+    let (submissions, total_count, more_in_db) = match &resume {
+        Some(resume) => {
+            let page = db::get_submissions_after(&DB_HTTP, &db_url, FORM_ID, &api_secret, &resume.db_cursor, limit, input.include_drafts, since.as_deref(), &timeouts)?;
+            (page.submissions, resume.total_count, page.has_more)
+        }
+        None => {
+            let page = db::get_submissions(&DB_HTTP, &db_url, FORM_ID, &api_secret, offset, limit, input.include_drafts, since.as_deref(), &timeouts)?;
+            let more_in_db = (offset as i64).saturating_add(page.submissions.len() as i64) < page.total_count;
+            (page.submissions, page.total_count, more_in_db)
+        }
+    };
+    timings.fetch_submissions = timer.lap();
+
+    // Questions of earlier revisions, for submissions answered before the form was edited
+    // (at most one fetch per revision on the page)
+    let mut earlier_questions: std::collections::BTreeMap<i32, serde_json::Value> = Default::default();
+    for version in submissions.iter().filter_map(|s| s.form_version) {
+        if Some(version) != form.version && !earlier_questions.contains_key(&version) {
+            let revision = db::get_form_version(&DB_HTTP, &db_url, FORM_ID, version, &timeouts)?;
+            earlier_questions.insert(version, revision.questions);
+        }
+    }
+    timings.fetch_form = timings.fetch_form.saturating_add(timer.lap());
+    // Question IDs renamed since those revisions, applied to their answers below
+    let renames = answers::renamed_question_ids(&form.questions);
 
-    // 6. Derive form-specific private key
-    let form_privkey = crypto::derive_form_privkey(&master_privkey, FORM_ID)?;
+    let form_privkeys = if submissions.is_empty() {
+        Vec::new()
+    } else {
+        derive_form_privkeys()?
+    };
+    timings.derive_key = timer.lap();
 
-    // 7. Decrypt each submission with size budgeting
+    // 7. Decrypt each submission with size budgeting. Decryption is sequential (wasm32-wasip2
+    //    has no threads), so at most one submission's plaintext is being parsed at a time and
+    //    peak memory is bounded by MAX_PLAINTEXT_SIZE_BYTES plus the page built so far. A
+    //    parallel path would need its own cap on concurrent decryptions to keep that bound.
+    let max_plaintext = max_plaintext_size()?;
+    let limits = answer_limits()?;
+    let lenient = lenient_answers_enabled()?;
     let mut responses: Vec<Response> = Vec::new();
     let mut skipped_count = 0usize;
+    let mut filtered_out_count = 0usize;
     let mut skipped_submissions: Vec<SkippedSubmission> = Vec::new();
     let mut accumulated_size = 0usize;
     let mut size_limit_hit = false;
+    let mut truncated = false;
 
-    for submission in page.submissions.iter() {
-        match (|| -> Result<Response, String> {
-            let ciphertext = hex::decode(&submission.encrypted_blob)
-                .map_err(|e| format!("Invalid hex ciphertext: {}", e))?;
-
-            let plaintext = crypto::decrypt_blob(&form_privkey, &ciphertext)
-                .map_err(|e| format!("Decryption failed: {}", e))?;
+    for submission in submissions.iter() {
+        // Only after some progress, so a slow db-api cannot produce an endless run of empty pages
+        if budget.remaining().is_none() && responses.len() + skipped_count + filtered_out_count > 0 {
+            truncated = true;
+            break;
+        }
+        if deadline.remaining().is_none() {
+            return Err(FormsError::Timeout("Request timed out while decrypting responses. Try a smaller page limit."));
+        }
+        // Ok(None): decrypted, but does not match `input.filter`
+        match (|| -> Result<Option<Response>, FormsError> {
+            let mut blob = StoredBlob::parse(&submission.encrypted_blob)?;
+            if let Some(visible) = &visible {
+                // A field may still be stored under a renamed question's earlier ID
+                blob.retain_fields(|id| visible.contains(id) || renames.get(id).is_some_and(|current| visible.contains(current)));
+            }
+            let (key_version, plaintext) = blob
+                .decrypt(&form_privkeys)
+                .map_err(|e| FormsError::DecryptFailed(format!("Decryption failed: {}", e)))?;
 
-            let answers: serde_json::Value = serde_json::from_slice(&plaintext)
-                .map_err(|e| format!("Invalid JSON in decrypted answers: {}", e))?;
+            let (answers, recovered) = plaintext.into_answers(max_plaintext, &limits, lenient)?;
+            // Stable key order for table/CSV rendering: the order of the questions this
+            // submission answered, unknown keys last
+            let earlier = submission.form_version.and_then(|version| earlier_questions.get(&version));
+            let answers = answers::order_answers(answers, earlier.unwrap_or(&form.questions));
+            let answers = match earlier {
+                Some(_) => answers::rename_answer_keys(answers, &renames),
+                None => answers,
+            };
+            if input.filter.as_ref().is_some_and(|filter| !answers::matches_filter(&answers, filter)) {
+                return Ok(None);
+            }
+            let answers = match &visible {
+                Some(visible) => answers::retain_visible_answers(answers, visible),
+                None => answers,
+            };
 
-            Ok(Response {
+            Ok(Some(Response {
                 submitter_id: submission.submitter_id.clone(),
                 answers,
                 submitted_at: submission.submitted_at.clone(),
-            })
+                key_version: key_version.to_string(),
+                is_draft: submission.is_draft,
+                form_version: submission.form_version,
+                recovered,
+                encrypted_blob: input.include_ciphertext.then(|| submission.encrypted_blob.clone()),
+            }))
         })() {
-            Ok(response) => {
+            Ok(None) => filtered_out_count += 1,
+            Ok(Some(response)) => {
                 // Estimate serialized size: submitter_id + answers JSON + submitted_at + overhead
                 let estimated_size = response.submitter_id.len()
                     .saturating_add(response.submitted_at.len())
+                    .saturating_add(response.key_version.len())
                     .saturating_add(response.answers.to_string().len())
+                    .saturating_add(response.encrypted_blob.as_ref().map_or(0, String::len))
                     .saturating_add(64); // JSON structural overhead
 
                 // Always include at least one response to avoid returning empty pages
@@ -209,26 +693,110 @@ fn handle_read_responses(input: ReadResponsesInput) -> Result<Output, Box<dyn st
         }
     }
 
+    timings.decrypt = timer.lap();
+
     // 8. Determine if there are more results (from pagination or size limit)
-    // Safe cast: both values bounded by MAX_PAGE_LIMIT (200) << u32::MAX
-    let returned_count = (responses.len() + skipped_count) as u32;
+    // Safe cast: all values bounded by MAX_PAGE_LIMIT (200) << u32::MAX
+    let returned_count = (responses.len() + skipped_count + filtered_out_count) as u32;
     let next_offset = offset.saturating_add(returned_count);
-    let has_more = size_limit_hit || (next_offset as i64) < page.total_count;
+    let has_more = size_limit_hit || truncated || more_in_db;
+    if truncated {
+        eprintln!("ReadResponses: time budget spent after {} of {} fetched submissions", returned_count, submissions.len());
+    }
+    // Newest submission this walk has covered, including ones that could not be decrypted:
+    // the next incremental read should not fetch them again
+    let high_water_mark = submissions[..returned_count as usize]
+        .iter()
+        .map(|s| &s.submitted_at)
+        .chain(walk_high_water_mark.as_ref())
+        .max_by_key(|ts| anomalies::parse_utc_micros(ts).unwrap_or(i64::MIN))
+        .cloned();
+
+    // Resume after the last row this call consumed, not db-api's own next_cursor: a size-limit
+    // or budget break leaves part of the fetched page for the next call
+    let next_cursor = match submissions[..returned_count as usize].last().filter(|_| has_more) {
+        Some(last) => {
+            let db_cursor = db::keyset_cursor(last).ok_or_else(|| {
+                FormsError::Internal(format!("Cannot build a cursor from submission at {}", last.submitted_at))
+            })?;
+            let form_pubkey = PublicKey::from_secret_key(&form_privkeys[0].1);
+            let cursor = read_cursor::ReadCursor {
+                db_cursor,
+                total_count,
+                since: since.clone(),
+                high_water_mark: high_water_mark.clone(),
+            };
+            Some(read_cursor::encode(&cursor, &form_pubkey)?)
+        }
+        None => None,
+    };
+
+    // Counted above before deduplication so next_offset still advances past dropped rows.
+    // Only duplicates within this page are detected. Forms that accept several submissions
+    // per account keep them all.
+    let (responses, duplicate_submitters) = match form.submission_policy {
+        SubmissionPolicy::OnePerAccount => answers::dedupe_by_submitter(responses),
+        SubmissionPolicy::Unlimited => (responses, Vec::new()),
+    };
+    if !duplicate_submitters.is_empty() {
+        eprintln!("ReadResponses: {} submitter(s) with duplicate submissions on this page", duplicate_submitters.len());
+    }
+
+    // Spam signal from timestamps already on hand: every submission this page covers counts,
+    // including ones that could not be decrypted (rows past a size-limit break are next page's)
+    let submitted_at: Vec<&str> = submissions
+        .iter()
+        .take(returned_count as usize)
+        .map(|s| s.submitted_at.as_str())
+        .collect();
+    let anomalies = anomalies::detect(&submitted_at);
+    // A TEE without a usable wall clock just skips the check
+    let clock_skew = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .and_then(|now| anomalies::clock_skew(&submitted_at, i64::try_from(now.as_micros()).ok()?, form.created_at.as_deref()));
+    if let Some(skew) = &clock_skew {
+        eprintln!(
+            "ReadResponses: possible db-api clock skew ({} future-dated by up to {}s, {} before form creation)",
+            skew.future_dated, skew.max_ahead_secs, skew.before_form_created
+        );
+    }
 
     // 9. Serialize the plaintext payload, then encrypt it to the caller's ephemeral key
     let payload = ReadResponsesPayload {
         responses,
         skipped_count,
+        filtered_out_count,
         skipped_submissions,
-        total_count: page.total_count,
+        duplicate_submitters,
+        anomalies,
+        clock_skew,
+        total_count,
         has_more,
+        truncated,
         next_offset,
+        next_cursor,
+        high_water_mark,
+        visible_questions: visible.map(|ids| ids.into_iter().collect()),
+        timings_ms: timings,
     };
     let payload_json = serde_json::to_vec(&payload)
         .map_err(|e| format!("Failed to serialize response payload: {}", e))?;
+    let serialize_ms = timer.lap();
+    eprintln!(
+        "read_responses timings_ms: fetch_form={} fetch_submissions={} derive_key={} decrypt={} serialize={} (submissions={})",
+        timings.fetch_form,
+        timings.fetch_submissions,
+        timings.derive_key,
+        timings.decrypt,
+        serialize_ms,
+        submissions.len(),
+    );
 
     if payload_json.len() > MAX_RESPONSE_BYTES {
-        return Err("Response payload too large. Try using a smaller page size (limit parameter).".into());
+        return Err(FormsError::InvalidInput(
+            "Response payload too large. Try using a smaller page size (limit parameter).".to_string(),
+        ));
     }
 
     let encrypted = crypto::encrypt_blob(&response_pubkey, &payload_json)
@@ -242,32 +810,302 @@ fn handle_read_responses(input: ReadResponsesInput) -> Result<Output, Box<dyn st
 /// Handle SubmitForm action (respondent submits pre-encrypted form)
 /// Answers are encrypted client-side using EC01 format so plaintext never appears on-chain.
 /// Requires: caller has a valid NEAR wallet (authenticated by OutLayer transaction)
-fn handle_submit_form(input: SubmitFormInput) -> Result<Output, Box<dyn std::error::Error>> {
+fn handle_submit_form(input: SubmitFormInput) -> Result<Output, FormsError> {
     // 1. Authenticate respondent via OutLayer TEE
-    let submitter_id = env::signer_account_id()
-        .ok_or("Authentication required - wallet signature not valid")?;
+    let submitter_id = env::signer_account_id().ok_or(FormsError::AuthRequired)?;
 
     // 2. Reject implicit accounts (64-char hex = ed25519 pubkey, defense-in-depth)
     if is_implicit_account(&submitter_id) {
-        return Err("Implicit accounts (64-char hex) are not allowed to submit forms. Please use a named NEAR account.".into());
+        return Err(FormsError::NotAuthorized(IMPLICIT_SUBMITTER_ERROR.to_string()));
     }
 
-    // 3. Validate the pre-encrypted EC01 blob (format, size, pubkey)
-    let _encrypted_bytes = validate_ec01_hex(&input.encrypted_answers)?;
+    // 3. Validate the pre-encrypted answers (format, size, pubkey): one EC01 blob over the
+    //    whole answers object, or a JSON object of per-question EC01 blobs. Only headers
+    //    are decoded; the hex string is what gets stored.
+    check_not_empty(&input.encrypted_answers)?;
+    let max_blob = max_blob_size()?;
+    let question_fields = if blob_format::is_per_question(&input.encrypted_answers) {
+        Some(validate_per_question_hex(&input.encrypted_answers, max_blob)?)
+    } else {
+        validate_ec01_hex(&input.encrypted_answers, max_blob)?;
+        None
+    };
 
-    // 4. Store pre-encrypted blob to db-api (uses chunked HTTP writes)
+    // 4. For strict forms, reject answer keys that are not question IDs, and reject final
+    //    submissions missing a required answer. A single blob is decrypted inside the TEE
+    //    (the plaintext never leaves this function); per-question keys are visible without
+    //    decrypting anything, but their values are not, so `show_if` conditions cannot be
+    //    evaluated and only unconditionally required questions are checked for them.
     let db_url = get_database_url()?;
+    let timeouts = db_timeouts()?;
+    let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+    // Drafts may still be saved while a form is paused
+    if form.paused && !input.is_draft {
+        return Err(FormsError::Rejected(db::FORM_PAUSED_ERROR.to_string()));
+    }
+    check_encryption_scheme(form.encryption_scheme, question_fields.is_some())?;
+    // Drafts are partial by nature
+    let enforce_required = !input.is_draft && has_required_questions(&form.questions);
+    if form.strict_answers || enforce_required {
+        let answers = match &question_fields {
+            None => {
+                // Decoded here, not during validation, and dropped before the db-api write
+                let encrypted_bytes = hex::decode(&input.encrypted_answers)
+                    .map_err(|e| FormsError::InvalidInput(format!("Invalid hex in encrypted_answers: {}", e)))?;
+                let master_privkey = load_master_key()?;
+                let tenant = form_key_tenant()?;
+                let salt = form_derivation_salt(&db_url, &timeouts)?;
+                let form_privkey = crypto::derive_form_privkey(&master_privkey, FORM_ID, tenant.as_deref(), salt.as_deref())?;
+                let plaintext = crypto::decrypt_blob(&form_privkey, &encrypted_bytes)
+                    .map_err(|e| FormsError::DecryptFailed(format!("Could not decrypt answers for validation: {}", e)))?;
+                answers::check_plaintext_size(plaintext.len(), max_plaintext_size()?)?;
+                answers::check_json_shape(&plaintext, &answer_limits()?)?;
+                serde_json::from_slice(&plaintext)
+                    .map_err(|_| FormsError::InvalidInput("Answers must be a JSON object".to_string()))?
+            }
+            // Values stay encrypted; `true` only marks the question as answered
+            Some(fields) => serde_json::Value::Object(
+                fields.keys().map(|id| (id.clone(), serde_json::Value::Bool(true))).collect(),
+            ),
+        };
+        if form.strict_answers {
+            check_answer_keys(&answers, &form.questions)?;
+        }
+        if enforce_required {
+            check_required_answers(&answers, &form.questions)?;
+        }
+    }
+
+    // 5. Store pre-encrypted blob to db-api (uses chunked HTTP writes)
     let api_secret = get_api_secret()?;
-    let submission_id = db::create_submission(
+    let created = db::create_submission(
+        &DB_HTTP,
         &db_url,
         FORM_ID,
         &submitter_id,
         &input.encrypted_answers,
+        input.is_draft,
         &api_secret,
+        &timeouts,
     )?;
 
     Ok(Output::SubmitForm(SubmitFormOutput {
         success: true,
-        submission_id,
+        submission_id: created.id,
+        blob_hash: created.blob_hash,
+    }))
+}
+
+/// Handle VerifySubmission action (a respondent checks what was recorded for them)
+/// Requires: signer is `submitter_id`, or a form owner (creator or co-owner)
+/// Compares the receipt `blob_hash` from SubmitForm with the blob db-api holds for the
+/// account's latest final submission. Nothing is decrypted.
+fn handle_verify_submission(input: VerifySubmissionInput) -> Result<Output, FormsError> {
+    let caller_id = env::signer_account_id().ok_or(FormsError::AuthRequired)?;
+
+    let db_url = get_database_url()?;
+    let timeouts = db_timeouts()?;
+    if caller_id != input.submitter_id {
+        let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+        authorize_owner(&caller_id, &form, &db_url, &timeouts, "verify another account's submission")?;
+    }
+
+    let submission = db::get_submission(&DB_HTTP, &db_url, FORM_ID, &input.submitter_id, &get_api_secret()?, &timeouts)?;
+    Ok(Output::VerifySubmission(VerifySubmissionOutput {
+        hash_matches: receipt_matches(&submission.encrypted_blob, &input.blob_hash)?,
+        stored_submission_id: submission.id,
+        stored_submitted_at: submission.submitted_at,
+    }))
+}
+
+/// Maximum responses per ImportResponses call (each is a separate db-api round trip)
+const MAX_IMPORT_ITEMS: usize = 100;
+
+/// Handle ImportResponses action (creator migrates plaintext responses from another tool)
+/// Requires: signer is a form owner (creator or co-owner)
+/// Each answers object is encrypted to the form public key inside the TEE — exactly what a
+/// respondent's client would have produced — and stored via db-api. Items fail independently.
+fn handle_import_responses(input: ImportResponsesInput) -> Result<Output, FormsError> {
+    // 1. Authenticate and authorize as a form owner
+    let caller_id = env::signer_account_id().ok_or(FormsError::AuthRequired)?;
+    if is_implicit_account(&caller_id) {
+        return Err(FormsError::NotAuthorized(IMPLICIT_ACCOUNT_ERROR.to_string()));
+    }
+    let db_url = get_database_url()?;
+    let timeouts = db_timeouts()?;
+    let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+    authorize_owner(&caller_id, &form, &db_url, &timeouts, "import responses")?;
+    // Imports are stored as one blob per response
+    check_encryption_scheme(form.encryption_scheme, false)?;
+
+    if input.responses.is_empty() || input.responses.len() > MAX_IMPORT_ITEMS {
+        return Err(FormsError::InvalidInput(format!(
+            "Invalid input JSON: responses must contain 1 to {} items (got {})",
+            MAX_IMPORT_ITEMS,
+            input.responses.len()
+        )));
+    }
+
+    // 2. Encrypt to the same form key SubmitForm clients use
+    let master_pubkey = PublicKey::from_secret_key(&load_master_key()?);
+    let tenant = form_key_tenant()?;
+    let salt = form_derivation_salt(&db_url, &timeouts)?;
+    let form_pubkey = crypto::derive_form_pubkey(&master_pubkey, FORM_ID, tenant.as_deref(), salt.as_deref())?;
+    let api_secret = get_api_secret()?;
+    let max_blob = max_blob_size()?;
+    let limits = answer_limits()?;
+
+    // 3. Encrypt and store each item; a failure is recorded and the rest continue
+    let mut import_results = Vec::with_capacity(input.responses.len());
+    for item in &input.responses {
+        let outcome = (|| -> Result<CreatedSubmission, FormsError> {
+            if is_implicit_account(&item.submitter_id) {
+                return Err(FormsError::InvalidInput(IMPLICIT_SUBMITTER_ERROR.to_string()));
+            }
+            if !item.answers.is_object() {
+                return Err(FormsError::InvalidInput("Answers must be a JSON object".to_string()));
+            }
+            if form.strict_answers {
+                check_answer_keys(&item.answers, &form.questions)?;
+            }
+            let plaintext = serde_json::to_vec(&item.answers)?;
+            answers::check_json_shape(&plaintext, &limits)?;
+            let encrypted = crypto::encrypt_blob(&form_pubkey, &plaintext)?;
+            if encrypted.len() > max_blob {
+                return Err(blob_too_large(encrypted.len(), max_blob));
+            }
+            db::create_submission(
+                &DB_HTTP,
+                &db_url,
+                FORM_ID,
+                &item.submitter_id,
+                &hex::encode(&encrypted),
+                false,
+                &api_secret,
+                &timeouts,
+            )
+        })();
+
+        import_results.push(match outcome {
+            Ok(created) => ImportResponseResult {
+                submitter_id: item.submitter_id.clone(),
+                success: true,
+                submission_id: Some(created.id),
+                error: None,
+            },
+            Err(e) => {
+                eprintln!("ImportResponses: item for {} failed: {}", item.submitter_id, e);
+                ImportResponseResult {
+                    submitter_id: item.submitter_id.clone(),
+                    success: false,
+                    submission_id: None,
+                    error: Some(e.public_message()),
+                }
+            }
+        });
+    }
+
+    let imported = import_results.iter().filter(|r| r.success).count();
+    Ok(Output::ImportResponses(ImportResponsesOutput {
+        imported,
+        failed: import_results.len() - imported,
+        import_results,
+    }))
+}
+
+/// Handle ReEncryptResponses action (creator migrates a page of submissions to the current key)
+/// Requires: signer is a form owner, and PROTECTED_MASTER_KEY_PREV set alongside
+/// PROTECTED_MASTER_KEY (both from env; keys are never taken from input).
+///
+/// Each submission is decrypted with whichever form key works. Blobs on the previous key are
+/// re-encrypted to the current form public key and replaced via db-api `PUT /submissions`,
+/// guarded by the hash of the blob that was read. Blobs already on the current key are left
+/// alone, so re-running any page is safe. Once a full walk reports nothing but
+/// `already_current`, the previous key can be retired.
+fn handle_re_encrypt_responses(input: ReEncryptResponsesInput) -> Result<Output, FormsError> {
+    // 1. Authenticate and authorize as a form owner
+    let caller_id = env::signer_account_id().ok_or(FormsError::AuthRequired)?;
+    if is_implicit_account(&caller_id) {
+        return Err(FormsError::NotAuthorized(IMPLICIT_ACCOUNT_ERROR.to_string()));
+    }
+    let db_url = get_database_url()?;
+    let timeouts = db_timeouts()?;
+    let form = db::get_form(&DB_HTTP, &db_url, FORM_ID, &timeouts)?;
+    authorize_owner(&caller_id, &form, &db_url, &timeouts, "re-encrypt responses")?;
+
+    // 2. Both key versions are required: there is nothing to migrate from otherwise
+    let master_keys = load_master_keys()?;
+    if master_keys.len() < 2 {
+        return Err(FormsError::Config(
+            "Re-encryption requires PROTECTED_MASTER_KEY_PREV (the key being retired) to be set".to_string(),
+        ));
+    }
+    let tenant = form_key_tenant()?;
+    let salt = form_derivation_salt(&db_url, &timeouts)?;
+    let form_privkeys = master_keys
+        .iter()
+        .map(|(version, master)| Ok((*version, crypto::derive_form_privkey(master, FORM_ID, tenant.as_deref(), salt.as_deref())?)))
+        .collect::<Result<Vec<_>, FormsError>>()?;
+    let current_pubkey = PublicKey::from_secret_key(&master_keys[0].1);
+    let form_pubkey = crypto::derive_form_pubkey(&current_pubkey, FORM_ID, tenant.as_deref(), salt.as_deref())?;
+
+    // 3. Fetch the page
+    const MAX_OFFSET: u32 = 1_000_000;
+    if input.offset > MAX_OFFSET {
+        return Err(FormsError::InvalidInput(format!("Offset too large: {} (max: {})", input.offset, MAX_OFFSET)));
+    }
+    let limit = input.limit.clamp(1, MAX_PAGE_LIMIT);
+    let api_secret = get_api_secret()?;
+    // Drafts are encrypted to the same form key, so they move with everything else
+    let page = db::get_submissions(&DB_HTTP, &db_url, FORM_ID, &api_secret, input.offset, limit, true, None, &timeouts)?;
+
+    // 4. Migrate each submission; failures are recorded and the rest continue
+    let mut re_encrypt_results = Vec::with_capacity(page.submissions.len());
+    for submission in &page.submissions {
+        let outcome = (|| -> Result<&'static str, FormsError> {
+            let (version, plaintext) = StoredBlob::parse(&submission.encrypted_blob)?.decrypt(&form_privkeys)?;
+            if version == KEY_VERSION_CURRENT {
+                return Ok("already_current");
+            }
+            let re_encrypted = plaintext.encrypt(&form_pubkey)?;
+            db::update_submission(
+                &DB_HTTP,
+                &db_url,
+                FORM_ID,
+                submission,
+                &re_encrypted,
+                &api_secret,
+                &timeouts,
+            )?;
+            Ok("re_encrypted")
+        })();
+
+        re_encrypt_results.push(match outcome {
+            Ok(status) => ReEncryptResult {
+                submitter_id: submission.submitter_id.clone(),
+                status: status.to_string(),
+                error: None,
+            },
+            Err(e) => {
+                eprintln!("ReEncryptResponses: {} failed: {}", submission.submitter_id, e);
+                ReEncryptResult {
+                    submitter_id: submission.submitter_id.clone(),
+                    status: "failed".to_string(),
+                    error: Some(e.public_message()),
+                }
+            }
+        });
+    }
+
+    let count = |status: &str| re_encrypt_results.iter().filter(|r| r.status == status).count();
+    let next_offset = input.offset + page.submissions.len() as u32;
+    Ok(Output::ReEncryptResponses(ReEncryptResponsesOutput {
+        re_encrypted: count("re_encrypted"),
+        already_current: count("already_current"),
+        re_encrypt_failed: count("failed"),
+        total_count: page.total_count,
+        has_more: (next_offset as i64) < page.total_count,
+        next_offset,
+        re_encrypt_results,
     }))
 }
@@ -0,0 +1,145 @@
+//! Continuation tokens for ReadResponses (`next_cursor` / `cursor`)
+//!
+//! A token wraps db-api's keyset cursor plus the `total_count` seen on the first page, so
+//! later pages can report it without another COUNT(*). The cursor names a submission's
+//! timestamp and id, and the creator passes it back in a public transaction, so the token
+//! is EC01-encrypted to the form key: only this module can read it.
+//!
+//! An incremental walk (`since`) also carries its `since` and the high-water mark of its
+//! earlier pages, so every page filters and reports the same way.
+//!
+//! Anyone can encrypt to the form public key, so a decoded token is still untrusted input
+//! and its cursor and timestamps are checked before they go into a db-api URL.
+
+use crate::crypto;
+use crate::error::FormsError;
+use crate::validation::is_utc_timestamp;
+use libsecp256k1::{PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+
+/// db-api cursors are `<micros>:<uuid>`; anything longer or outside this charset is forged
+const MAX_DB_CURSOR_LEN: usize = 64;
+
+/// Upper bound on a token's hex length (the plaintext is under 256 bytes)
+const MAX_TOKEN_HEX_LEN: usize = 1024;
+
+/// Plaintext inside a continuation token
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReadCursor {
+    /// db-api keyset cursor: the page starts after this submission
+    pub db_cursor: String,
+    /// `total_count` from the page that started this walk
+    pub total_count: i64,
+    /// `since` of an incremental walk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Newest `submitted_at` on the walk's pages so far
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_water_mark: Option<String>,
+}
+
+fn invalid_cursor() -> FormsError {
+    FormsError::InvalidInput("Invalid cursor: pass next_cursor from the previous page unchanged".to_string())
+}
+
+/// Encrypt `cursor` to the current form public key and hex-encode it.
+pub fn encode(cursor: &ReadCursor, form_pubkey: &PublicKey) -> Result<String, FormsError> {
+    let json = serde_json::to_vec(cursor).map_err(|e| format!("Failed to serialize cursor: {}", e))?;
+    Ok(hex::encode(crypto::encrypt_blob(form_pubkey, &json)?))
+}
+
+/// Decrypt a token produced by [`encode`] with any configured form key version.
+/// Every failure is the same [`FormsError::InvalidInput`].
+pub fn decode(token: &str, form_privkeys: &[(&str, SecretKey)]) -> Result<ReadCursor, FormsError> {
+    if token.len() > MAX_TOKEN_HEX_LEN {
+        return Err(invalid_cursor());
+    }
+    let blob = hex::decode(token).map_err(|_| invalid_cursor())?;
+    let (_, json) = crypto::decrypt_blob_any(form_privkeys, &blob).map_err(|_| invalid_cursor())?;
+    let cursor: ReadCursor = serde_json::from_slice(&json).map_err(|_| invalid_cursor())?;
+
+    let well_formed = !cursor.db_cursor.is_empty()
+        && cursor.db_cursor.len() <= MAX_DB_CURSOR_LEN
+        && cursor.db_cursor.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f' | b':' | b'-'))
+        && cursor.total_count >= 0
+        && [&cursor.since, &cursor.high_water_mark].into_iter().flatten().all(|ts| is_utc_timestamp(ts));
+    if !well_formed {
+        return Err(invalid_cursor());
+    }
+    Ok(cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form_key(byte: u8) -> SecretKey {
+        SecretKey::parse_slice(&[byte; 32]).unwrap()
+    }
+
+    fn cursor() -> ReadCursor {
+        ReadCursor {
+            db_cursor: "1767225600000001:0b5f0c2e-0000-0000-0000-000000000001".to_string(),
+            total_count: 250,
+            since: None,
+            high_water_mark: None,
+        }
+    }
+
+    #[test]
+    fn token_round_trips_and_hides_the_cursor() {
+        let key = form_key(1);
+        let token = encode(&cursor(), &PublicKey::from_secret_key(&key)).unwrap();
+        assert!(!token.contains("0b5f0c2e"));
+        assert!(token.len() <= MAX_TOKEN_HEX_LEN, "{}", token.len());
+        assert_eq!(decode(&token, &[("current", key)]).unwrap(), cursor());
+    }
+
+    #[test]
+    fn incremental_token_fits_and_round_trips() {
+        let key = form_key(1);
+        let incremental = ReadCursor {
+            since: Some("2026-01-01T00:00:00.000001Z".to_string()),
+            high_water_mark: Some("2026-01-02T00:00:00.000001Z".to_string()),
+            ..cursor()
+        };
+        let token = encode(&incremental, &PublicKey::from_secret_key(&key)).unwrap();
+        assert!(token.len() <= MAX_TOKEN_HEX_LEN, "{}", token.len());
+        assert_eq!(decode(&token, &[("current", key)]).unwrap(), incremental);
+    }
+
+    #[test]
+    fn token_survives_key_rotation() {
+        let previous = form_key(1);
+        let token = encode(&cursor(), &PublicKey::from_secret_key(&previous)).unwrap();
+        let keys = [("current", form_key(2)), ("previous", previous)];
+        assert_eq!(decode(&token, &keys).unwrap(), cursor());
+    }
+
+    #[test]
+    fn rejects_tampered_foreign_and_forged_tokens() {
+        let key = form_key(1);
+        let keys = [("current", key)];
+        let token = encode(&cursor(), &PublicKey::from_secret_key(&key)).unwrap();
+
+        let mut tampered = token.clone().into_bytes();
+        let last = tampered.len() - 1;
+        tampered[last] = if tampered[last] == b'0' { b'1' } else { b'0' };
+        let foreign = encode(&cursor(), &PublicKey::from_secret_key(&form_key(3))).unwrap();
+        let forged = encode(
+            &ReadCursor { db_cursor: "1&limit=100000".to_string(), ..cursor() },
+            &PublicKey::from_secret_key(&key),
+        )
+        .unwrap();
+        let forged_since = encode(
+            &ReadCursor { since: Some("2026-01-01T00:00:00Z&limit=100000".to_string()), ..cursor() },
+            &PublicKey::from_secret_key(&key),
+        )
+        .unwrap();
+
+        for bad in [String::from_utf8(tampered).unwrap(), foreign, forged, forged_since, "zz".to_string(), String::new()] {
+            let err = decode(&bad, &keys).unwrap_err();
+            assert!(matches!(err, FormsError::InvalidInput(_)), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-phase wall-clock timing and HTTP time budgets for the WASI module.
+//!
+//! `std::time::Instant` maps to the WASI monotonic clock (`wasi:clocks/monotonic-clock`),
+//! so this works unchanged inside OutLayer and in native unit tests.
+
+use crate::error::FormsError;
+use std::time::{Duration, Instant};
+
+/// Message for a db-api call that ran out of time (connect, read or overall deadline);
+/// carried by `FormsError::Timeout`. Starts with "Request timed out", which web-ui passes through.
+pub const DB_TIMEOUT_ERROR: &str = "Request timed out waiting for the database. Please try again later.";
+
+/// Maximum extra connect timeout added by [`jitter`], as a fraction of the base (10%).
+const JITTER_DIVISOR: u32 = 10;
+
+/// Measures consecutive phases: each `lap` returns the milliseconds since the previous lap
+/// (or since `start` for the first one).
+pub struct PhaseTimer {
+    last: Instant,
+}
+
+impl PhaseTimer {
+    pub fn start() -> Self {
+        Self { last: Instant::now() }
+    }
+
+    /// Milliseconds elapsed since the previous lap; resets the lap start to now.
+    pub fn lap(&mut self) -> u64 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last);
+        self.last = now;
+        // Saturate rather than truncate: a u64 of milliseconds covers ~584 million years
+        u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+/// Fixed point in time after which remaining HTTP work is abandoned.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    at: Instant,
+}
+
+impl Deadline {
+    pub fn after(budget: Duration) -> Self {
+        Self { at: Instant::now() + budget }
+    }
+
+    /// Time left, or `None` once the deadline has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.at.checked_duration_since(Instant::now()).filter(|d| !d.is_zero())
+    }
+}
+
+/// Timeouts for db-api HTTP calls.
+///
+/// `connect` bounds connection setup; `read` bounds time to first byte and each gap
+/// between body chunks. An optional `deadline` caps the whole call (and any later calls
+/// sharing it), including slow-but-steady bodies that never trip `read`.
+#[derive(Debug, Clone, Copy)]
+pub struct HttpTimeouts {
+    pub connect: Duration,
+    pub read: Duration,
+    pub deadline: Option<Deadline>,
+}
+
+impl HttpTimeouts {
+    pub fn with_deadline(self, deadline: Deadline) -> Self {
+        Self { deadline: Some(deadline), ..self }
+    }
+
+    /// Effective `(connect, read)` timeouts for a request starting now: connect is
+    /// jittered, and both are capped to the time left before the deadline.
+    pub fn for_request(&self) -> Result<(Duration, Duration), FormsError> {
+        let connect = jitter(self.connect);
+        match self.deadline {
+            None => Ok((connect, self.read)),
+            Some(deadline) => {
+                let left = deadline.remaining().ok_or(FormsError::Timeout(DB_TIMEOUT_ERROR))?;
+                Ok((connect.min(left), self.read.min(left)))
+            }
+        }
+    }
+}
+
+/// Add up to 10% random extra time so concurrent callers don't give up in lockstep.
+/// Falls back to the base duration if randomness is unavailable.
+pub fn jitter(base: Duration) -> Duration {
+    let mut byte = [0u8; 1];
+    if getrandom::getrandom(&mut byte).is_err() {
+        return base;
+    }
+    base + base / JITTER_DIVISOR * u32::from(byte[0]) / 255
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lap_measures_elapsed_time() {
+        let mut timer = PhaseTimer::start();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(timer.lap() >= 20);
+    }
+
+    #[test]
+    fn lap_resets_start() {
+        let mut timer = PhaseTimer::start();
+        std::thread::sleep(Duration::from_millis(20));
+        timer.lap();
+        // Immediately after a lap, the next phase starts from ~0
+        assert!(timer.lap() < 20);
+    }
+
+    #[test]
+    fn jitter_stays_within_ten_percent() {
+        let base = Duration::from_secs(10);
+        for _ in 0..50 {
+            let d = jitter(base);
+            assert!(d >= base && d <= base + Duration::from_secs(1), "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn deadline_caps_request_timeouts() {
+        let timeouts = HttpTimeouts {
+            connect: Duration::from_secs(10),
+            read: Duration::from_secs(30),
+            deadline: None,
+        };
+        let (connect, read) = timeouts.for_request().unwrap();
+        assert!(connect >= Duration::from_secs(10));
+        assert_eq!(read, Duration::from_secs(30));
+
+        let capped = timeouts.with_deadline(Deadline::after(Duration::from_secs(2)));
+        let (connect, read) = capped.for_request().unwrap();
+        assert!(connect <= Duration::from_secs(2));
+        assert!(read <= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn expired_deadline_is_a_timeout() {
+        let deadline = Deadline::after(Duration::ZERO);
+        assert!(deadline.remaining().is_none());
+        let timeouts = HttpTimeouts {
+            connect: Duration::from_secs(10),
+            read: Duration::from_secs(30),
+            deadline: Some(deadline),
+        };
+        assert!(matches!(timeouts.for_request().unwrap_err(), FormsError::Timeout(DB_TIMEOUT_ERROR)));
+    }
+}
@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize};
 pub const MAX_RESPONSE_SIZE: usize = 10 * 1024 * 1024;
 
 /// WASI module input - determines which action to perform
+///
+/// Every input struct uses `deny_unknown_fields` so a typo'd field name (e.g.
+/// `encrypted_answer`) is rejected with serde's "unknown field ..., expected ..." message
+/// instead of being silently ignored. Parse with `validation::parse_input`.
 #[derive(Debug, Deserialize)]
 #[serde(tag = "action")]
 pub enum Input {
@@ -15,19 +19,70 @@ pub enum Input {
     SubmitForm(SubmitFormInput),
     /// GetMasterPublicKey: Return the master public key (no auth required)
     GetMasterPublicKey(GetMasterPublicKeyInput),
+    /// GetFormPublicKey: Return the derived form public key clients encrypt to (no auth required)
+    GetFormPublicKey(GetFormPublicKeyInput),
+    /// ImportResponses: Encrypt and store legacy plaintext responses (creator only, via transaction)
+    ImportResponses(ImportResponsesInput),
+    /// ReEncryptResponses: Move submissions from the previous master key to the current one
+    /// (creator only, via transaction)
+    ReEncryptResponses(ReEncryptResponsesInput),
+    /// EstimateSize: Report whether `encrypted_answers` fits the size cap, without storing
+    /// anything (no auth required)
+    EstimateSize(EstimateSizeInput),
+    /// VerifySubmission: Check a stored submission against a SubmitForm receipt hash
+    /// (the respondent or a form owner, via transaction)
+    VerifySubmission(VerifySubmissionInput),
+    /// Diagnostics: Report which parts of the module's configuration work, without any
+    /// secret values (no auth required)
+    Diagnostics(DiagnosticsInput),
 }
 
 /// Input for ReadResponses action
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ReadResponsesInput {
     /// Hex-encoded compressed secp256k1 public key for encrypting the response
     pub response_pubkey: String,
     /// Pagination offset (0-based, default: 0)
     #[serde(default)]
     pub offset: u32,
-    /// Pagination limit (default: 50, max: 200)
-    #[serde(default = "default_page_limit")]
+    /// Maximum submissions to read in this call (default: 50, max: 200); `max_responses` is an alias
+    #[serde(default = "default_page_limit", alias = "max_responses")]
     pub limit: u32,
+    /// Continuation token (`next_cursor` from the previous page); cannot be combined with `offset`
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Also read respondents' drafts (default: final submissions only). Keep it the same
+    /// across the pages of one walk.
+    #[serde(default)]
+    pub include_drafts: bool,
+    /// Incremental read: only submissions made after this UTC timestamp (`high_water_mark`
+    /// from an earlier read). Page through with `cursor`; cannot be combined with `cursor`.
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Only return answers to these question IDs (current IDs; answers under a renamed
+    /// question's earlier ID match its current one). Narrowed further by the caller's
+    /// owner record in db-api, which may limit the questions a co-owner can read.
+    #[serde(default)]
+    pub visible_questions: Option<Vec<String>>,
+    /// Only return responses whose answer to one question matches (see [`AnswerFilter`]).
+    /// Applied inside the TEE after decryption; `total_count` still counts every submission.
+    #[serde(default)]
+    pub filter: Option<AnswerFilter>,
+    /// Also return each submission's stored `encrypted_blob` (for re-encryption and
+    /// verification tooling). Off by default: it roughly triples the payload per response.
+    #[serde(default)]
+    pub include_ciphertext: bool,
+}
+
+/// Answer-content filter for ReadResponses
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnswerFilter {
+    /// Current question ID (answers under a renamed question's earlier ID match too)
+    pub question_id: String,
+    /// Matches an answer equal to it, or an array answer (multi-select) containing it
+    pub value: serde_json::Value,
 }
 
 fn default_page_limit() -> u32 {
@@ -36,20 +91,86 @@ fn default_page_limit() -> u32 {
 
 /// Input for SubmitForm action
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SubmitFormInput {
-    /// Pre-encrypted EC01 blob (hex-encoded) from client-side encryption
+    /// Pre-encrypted EC01 blob (hex-encoded) from client-side encryption, or a JSON object
+    /// of question ID to hex EC01 blob (see `blob_format`)
+    pub encrypted_answers: String,
+    /// Save progress instead of submitting: replaces this account's previous draft and does
+    /// not count as having submitted the form
+    #[serde(default)]
+    pub is_draft: bool,
+}
+
+/// Input for EstimateSize action
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateSizeInput {
+    /// Same value the client would send to SubmitForm
     pub encrypted_answers: String,
 }
 
+/// Input for VerifySubmission action
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifySubmissionInput {
+    /// NEAR account ID whose latest final submission is checked
+    pub submitter_id: String,
+    /// Hex SHA-256 of the stored blob, as returned by SubmitForm (`blob_hash`)
+    pub blob_hash: String,
+}
+
+/// Input for ImportResponses action
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportResponsesInput {
+    /// Plaintext responses to encrypt to the form key and store (max 100 per call)
+    pub responses: Vec<ImportResponseItem>,
+}
+
+/// One legacy response to import
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportResponseItem {
+    /// NEAR account ID the response is stored under
+    pub submitter_id: String,
+    /// Answers object, same shape as a SubmitForm plaintext
+    pub answers: serde_json::Value,
+}
+
+/// Input for ReEncryptResponses action (one page of submissions per call)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReEncryptResponsesInput {
+    /// Pagination offset (0-based, default: 0)
+    #[serde(default)]
+    pub offset: u32,
+    /// Submissions to process (default: 50, max: 200)
+    #[serde(default = "default_page_limit")]
+    pub limit: u32,
+}
+
 /// Input for GetMasterPublicKey action
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetMasterPublicKeyInput {}
 
+/// Input for GetFormPublicKey action
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetFormPublicKeyInput {}
+
+/// Input for Diagnostics action
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DiagnosticsInput {}
+
 /// WASI module output - union of possible response types.
 ///
 /// Uses `#[serde(untagged)]` so each action returns its own JSON shape without a
 /// type discriminator field. The web-ui differentiates responses by checking for
-/// action-specific fields (`encrypted_payload`, `success`, `master_public_key`).
+/// action-specific fields (`encrypted_payload`, `success`, `master_public_key`,
+/// `form_public_key`).
 ///
 /// **IMPORTANT for future contributors:** Because this enum is `untagged`, serde tries
 /// each variant in declaration order until one serializes successfully. All variants
@@ -57,8 +178,17 @@ pub struct GetMasterPublicKeyInput {}
 /// (e.g., both have `success`), serde will silently serialize as the first matching
 /// variant, producing incorrect output. Current variants are disjoint:
 /// - `EncryptedResponseOutput`: `encrypted_payload`
-/// - `SubmitFormOutput`: `success`, `submission_id`
-/// - `GetMasterPublicKeyOutput`: `master_public_key`
+/// - `SubmitFormOutput`: `success`, `submission_id`, `blob_hash`
+/// - `GetMasterPublicKeyOutput`: `master_public_key`, `curve`, `encoding`, `scheme`,
+///   `derivation_prefix`
+/// - `GetFormPublicKeyOutput`: `form_public_key`
+/// - `ImportResponsesOutput`: `imported`, `failed`, `import_results`
+/// - `ReEncryptResponsesOutput`: `re_encrypted`, `already_current`, `re_encrypt_failed`,
+///   `re_encrypt_results`, `total_count`, `has_more`, `next_offset`
+/// - `EstimateSizeOutput`: `decoded_size_bytes`, `max_blob_size_bytes`, `within_limit`
+/// - `VerifySubmissionOutput`: `hash_matches`, `stored_submission_id`, `stored_submitted_at`
+/// - `DiagnosticsOutput`: `db_url_set`, `db_reachable`, `master_key_valid`, `api_secret_set`,
+///   `crypto_self_test_passed`, `form_pubkey`, `problems`
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum Output {
@@ -68,12 +198,135 @@ pub enum Output {
     SubmitForm(SubmitFormOutput),
     /// GetMasterPublicKey output: hex-encoded compressed public key
     GetMasterPublicKey(GetMasterPublicKeyOutput),
+    /// GetFormPublicKey output: hex-encoded compressed form public key
+    GetFormPublicKey(GetFormPublicKeyOutput),
+    /// ImportResponses output: per-item results
+    ImportResponses(ImportResponsesOutput),
+    /// ReEncryptResponses output: per-submission results for one page
+    ReEncryptResponses(ReEncryptResponsesOutput),
+    /// EstimateSize output: ciphertext size against the configured cap
+    EstimateSize(EstimateSizeOutput),
+    /// VerifySubmission output: whether the stored blob matches the receipt
+    VerifySubmission(VerifySubmissionOutput),
+    /// Diagnostics output: pass/fail per configuration check
+    Diagnostics(DiagnosticsOutput),
+}
+
+/// Output for Diagnostics action. Holds only booleans, the (public) form key, and error
+/// messages that never echo a configured value.
+#[derive(Debug, Serialize)]
+pub struct DiagnosticsOutput {
+    /// DATABASE_API_URL is set
+    pub db_url_set: bool,
+    /// db-api answered GET /health (its database is up)
+    pub db_reachable: bool,
+    /// PROTECTED_MASTER_KEY (and PROTECTED_MASTER_KEY_PREV, if set) parse as private keys
+    pub master_key_valid: bool,
+    /// DATABASE_API_SECRET or API_SECRET is set. Not checked against db-api.
+    pub api_secret_set: bool,
+    /// `crypto::self_test` passed (always run here, whatever RUN_SELF_TEST says)
+    pub crypto_self_test_passed: bool,
+    /// Form public key clients encrypt to; absent when any input to it is unavailable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_pubkey: Option<String>,
+    /// Why each failed check failed, in check order
+    pub problems: Vec<String>,
+}
+
+/// Output for EstimateSize action
+#[derive(Debug, Serialize)]
+pub struct EstimateSizeOutput {
+    /// Decoded ciphertext bytes (summed over fields for per-question answers)
+    pub decoded_size_bytes: usize,
+    /// Configured `MAX_BLOB_SIZE_BYTES`
+    pub max_blob_size_bytes: usize,
+    /// Whether SubmitForm's size check would pass
+    pub within_limit: bool,
+}
+
+/// Output for VerifySubmission action
+#[derive(Debug, Serialize)]
+pub struct VerifySubmissionOutput {
+    /// Whether the stored blob hashes to the claimed `blob_hash`
+    pub hash_matches: bool,
+    /// ID of the submission that was checked
+    pub stored_submission_id: String,
+    /// ISO 8601 timestamp of that submission
+    pub stored_submitted_at: String,
 }
 
 /// Output for GetMasterPublicKey action
 #[derive(Debug, Serialize)]
 pub struct GetMasterPublicKeyOutput {
     pub master_public_key: String,
+    /// Always "secp256k1"
+    pub curve: &'static str,
+    /// Point encoding of `master_public_key` (and of every form public key): "compressed"
+    pub encoding: &'static str,
+    /// Derivation and ciphertext contract (`crypto::SCHEME`); clients should refuse one
+    /// they do not implement rather than encrypt to the wrong key
+    pub scheme: &'static str,
+    /// Hashed ahead of the form ID in the tweak, tenant included:
+    /// `SHA256(derivation_prefix + form_id + [":" + derivation_salt])`
+    pub derivation_prefix: String,
+}
+
+/// Output for GetFormPublicKey action
+#[derive(Debug, Serialize)]
+pub struct GetFormPublicKeyOutput {
+    pub form_public_key: String,
+}
+
+/// Output for ImportResponses action
+#[derive(Debug, Serialize)]
+pub struct ImportResponsesOutput {
+    /// Number of responses stored
+    pub imported: usize,
+    /// Number of responses rejected
+    pub failed: usize,
+    /// One entry per input item, in input order
+    pub import_results: Vec<ImportResponseResult>,
+}
+
+/// Result of importing one response
+#[derive(Debug, Serialize)]
+pub struct ImportResponseResult {
+    pub submitter_id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<String>,
+    /// Sanitized error (this output is public on-chain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Output for ReEncryptResponses action
+#[derive(Debug, Serialize)]
+pub struct ReEncryptResponsesOutput {
+    /// Submissions moved from the previous to the current key on this call
+    pub re_encrypted: usize,
+    /// Submissions that already decrypt with the current key (nothing to do)
+    pub already_current: usize,
+    /// Submissions that could not be migrated
+    pub re_encrypt_failed: usize,
+    /// One entry per submission on the page
+    pub re_encrypt_results: Vec<ReEncryptResult>,
+    /// Total number of submissions across all pages
+    pub total_count: i64,
+    /// Whether there are more submissions beyond this page
+    pub has_more: bool,
+    /// Offset for the next call
+    pub next_offset: u32,
+}
+
+/// Result of migrating one submission: `re_encrypted`, `already_current`, or `failed`
+#[derive(Debug, Serialize)]
+pub struct ReEncryptResult {
+    pub submitter_id: String,
+    pub status: String,
+    /// Sanitized error (this output is public on-chain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Output for ReadResponses action (encrypted wrapper — plaintext never appears on-chain)
@@ -90,15 +343,102 @@ pub struct ReadResponsesPayload {
     pub responses: Vec<Response>,
     /// Number of submissions that could not be decrypted (indicates potential data loss)
     pub skipped_count: usize,
+    /// Decrypted submissions left out because they did not match `filter` (not skipped:
+    /// nothing went wrong with them). Counted in `next_offset`.
+    pub filtered_out_count: usize,
     /// Details of skipped submissions so the creator can investigate
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub skipped_submissions: Vec<SkippedSubmission>,
+    /// Submitters with more than one submission on this page; only the latest is in `responses`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicate_submitters: Vec<DuplicateSubmitter>,
+    /// Submission-rate anomalies among this page's `submitted_at` values (present only when flagged)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anomalies: Option<SubmissionAnomalies>,
+    /// Submission timestamps on this page that db-api's clock got wrong (present only when flagged)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_skew: Option<ClockSkew>,
     /// Total number of submissions across all pages (i64 to match PostgreSQL COUNT(*) bigint)
     pub total_count: i64,
     /// Whether there are more submissions beyond this page
     pub has_more: bool,
+    /// The page stopped early because READ_RESPONSES_BUDGET_SECS ran out; `has_more` and
+    /// `next_cursor` pick up from the last submission it covered
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
     /// Authoritative offset for the next page (accounts for size-limit breaks and skipped items)
     pub next_offset: u32,
+    /// Continuation token for the next page (present when `has_more` is true). Stable under
+    /// concurrent inserts, unlike `next_offset`; pass it back unchanged as `cursor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Newest `submitted_at` this walk has covered (or the `since` it started from when
+    /// nothing is newer). Once `has_more` is false, pass it as `since` to read only what
+    /// arrives after. Absent when no submissions were read and no `since` was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_water_mark: Option<String>,
+    /// Question IDs whose answers were kept (the request's filter and the caller's owner
+    /// permissions combined). Absent when every answer was returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible_questions: Option<Vec<String>>,
+    /// Per-phase timing for this call (serialization is measured afterwards and only logged)
+    pub timings_ms: ReadResponsesTimings,
+}
+
+/// Milliseconds spent in each phase of ReadResponses
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct ReadResponsesTimings {
+    /// GET /forms/{id} round-trip (creator check), plus any earlier question revisions
+    pub fetch_form: u64,
+    /// GET /forms/{id}/submissions round-trip
+    pub fetch_submissions: u64,
+    /// SHA-256 scalar-tweak derivation of the form private key(s) from the master key
+    pub derive_key: u64,
+    /// Hex decode + EC01 decrypt + JSON parse of every submission on the page
+    pub decrypt: u64,
+}
+
+/// A submitter with several decrypted submissions on one ReadResponses page
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DuplicateSubmitter {
+    pub submitter_id: String,
+    /// How many submissions were found (kept + dropped)
+    pub submission_count: usize,
+    /// `submitted_at` of the submission kept in `responses`
+    pub kept_submitted_at: String,
+}
+
+/// Spam signals over one ReadResponses page (see `anomalies::detect`)
+#[derive(Debug, Serialize)]
+pub struct SubmissionAnomalies {
+    /// Most submissions in any single clock minute
+    pub peak_per_minute: usize,
+    /// Minutes with at least `anomalies::BURST_PER_MINUTE` submissions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bursts: Vec<SubmissionBurst>,
+    /// Mean gap when submissions arrive at near-equal intervals (likely scripted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regular_interval_ms: Option<u64>,
+}
+
+/// `submitted_at` values implausible against the TEE clock (see `anomalies::clock_skew`);
+/// a sign db-api's clock is off and ordering by time cannot be trusted
+#[derive(Debug, Serialize)]
+pub struct ClockSkew {
+    /// Submissions dated more than `anomalies::CLOCK_SKEW_TOLERANCE_SECS` in the future
+    pub future_dated: usize,
+    /// Furthest any of them is ahead of the TEE clock
+    pub max_ahead_secs: u64,
+    /// Submissions dated before the form was created
+    pub before_form_created: usize,
+}
+
+/// One clock minute with a burst of submissions
+#[derive(Debug, Serialize)]
+pub struct SubmissionBurst {
+    /// Start of the minute, RFC 3339 UTC
+    pub minute: String,
+    pub count: usize,
 }
 
 /// A submission that could not be decrypted
@@ -113,6 +453,9 @@ pub struct SkippedSubmission {
 pub struct SubmitFormOutput {
     pub success: bool,
     pub submission_id: String,
+    /// Hex SHA-256 of the blob as db-api stored it; keep it as a receipt for VerifySubmission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_hash: Option<String>,
 }
 
 /// Decrypted form submission response
@@ -120,10 +463,28 @@ pub struct SubmitFormOutput {
 pub struct Response {
     /// NEAR account ID of the form submitter (plaintext - intentional)
     pub submitter_id: String,
-    /// Decrypted form answers as JSON object
+    /// Decrypted form answers as JSON object, keys in question definition order. Answers to
+    /// a question renamed since `form_version` are under its current ID (`renamed_from`).
     pub answers: serde_json::Value,
     /// ISO 8601 timestamp when the form was submitted
     pub submitted_at: String,
+    /// Master key version that decrypted this submission (`current` or `previous`).
+    /// Submissions still on `previous` need re-encryption before the old key is retired.
+    pub key_version: String,
+    /// Saved progress rather than a final submission (only read with `include_drafts`)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_draft: bool,
+    /// Revision of the form's questions `answers` follows; older than the form's current
+    /// version when the questions were edited after this submission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_version: Option<i32>,
+    /// `answers` only parsed after a lenient recovery (`LENIENT_ANSWERS_JSON`); check them
+    /// against what the respondent meant
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub recovered: bool,
+    /// The stored ciphertext exactly as db-api returned it (only with `include_ciphertext`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_blob: Option<String>,
 }
 
 /// Paginated submissions response from db-api
@@ -135,21 +496,124 @@ pub struct SubmissionsPage {
     pub total_count: i64,
 }
 
+/// Keyset page of submissions from db-api (`?cursor=`); db-api omits `total_count` here
+#[derive(Debug, Deserialize)]
+pub struct SubmissionsCursorPage {
+    /// Submissions for this page
+    pub submissions: Vec<EncryptedSubmission>,
+    /// Whether more submissions exist after this page
+    pub has_more: bool,
+}
+
 /// Encrypted form submission from database
 #[derive(Debug, Deserialize)]
 pub struct EncryptedSubmission {
+    /// Submission UUID (half of db-api's keyset cursor)
+    #[serde(default)]
+    pub id: String,
     /// Wallet address that submitted the form
     pub submitter_id: String,
     /// Hex-encoded EC01 ciphertext (magic + ephemeral_pubkey + nonce + chacha20 ciphertext)
     pub encrypted_blob: String,
     /// ISO 8601 timestamp of submission
     pub submitted_at: String,
+    /// Saved progress rather than a final submission
+    #[serde(default)]
+    pub is_draft: bool,
+    /// Revision of the form's questions the answers were given against
+    #[serde(default)]
+    pub form_version: Option<i32>,
+}
+
+/// db-api's reply to POST /submissions
+#[derive(Debug)]
+pub struct CreatedSubmission {
+    /// Submission UUID
+    pub id: String,
+    /// Hex SHA-256 of the stored (normalized) blob; absent from older db-api versions
+    pub blob_hash: Option<String>,
 }
 
 /// Form metadata from db-api (GET /forms/{form_id})
 #[derive(Debug, Deserialize)]
 pub struct FormMetadata {
     pub creator_id: String,
+    /// Question definitions (`id`, `optional`, `show_if` and `renamed_from` are used: strict
+    /// answer and required answer checks, and ordering and renaming decrypted answers in
+    /// ReadResponses)
+    #[serde(default)]
+    pub questions: serde_json::Value,
+    /// Reject answers with keys that are not question IDs
+    #[serde(default)]
+    pub strict_answers: bool,
+    /// Whether an account may submit more than once (db-api enforces it on insert)
+    #[serde(default)]
+    pub submission_policy: SubmissionPolicy,
+    /// Ciphertext layout SubmitForm must receive (db-api enforces it on insert too)
+    #[serde(default)]
+    pub encryption_scheme: EncryptionScheme,
+    /// Temporarily not accepting final submissions (db-api answers 423 too)
+    #[serde(default)]
+    pub paused: bool,
+    /// Current revision of `questions` (absent from db-api builds without form versions)
+    #[serde(default)]
+    pub version: Option<i32>,
+    /// Form public key db-api has cached (see `db::set_form_public_key`); None until registered
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// UTC RFC 3339 creation time (see `anomalies::clock_skew`)
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// A form's key derivation salt from db-api (GET /forms/{form_id}/derivation-salt), kept
+/// off the public form; mixed into `crypto::derive_form_privkey`. None for forms created
+/// before db-api stored one.
+#[derive(Debug, Deserialize)]
+pub struct FormDerivationSalt {
+    pub derivation_salt: Option<String>,
+}
+
+/// Owners of a form from db-api (GET /forms/{form_id}/owners), creator first
+#[derive(Debug, Deserialize)]
+pub struct FormOwners {
+    pub owners: Vec<FormOwner>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FormOwner {
+    pub account_id: String,
+    /// Questions this owner may read in ReadResponses; None (or absent) means all
+    #[serde(default)]
+    pub visible_questions: Option<Vec<String>>,
+}
+
+/// One revision of a form's questions from db-api (GET /forms/{form_id}/versions/{version})
+#[derive(Debug, Deserialize)]
+pub struct FormVersion {
+    pub questions: serde_json::Value,
+}
+
+/// Per-form required ciphertext layout (db-api `forms.encryption_scheme`)
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionScheme {
+    /// Either layout
+    #[default]
+    Any,
+    /// One EC01 blob over the whole answers object
+    Ec01,
+    /// A JSON object of question ID to EC01 blob
+    Ec01PerQuestion,
+}
+
+/// Per-form submission policy (db-api `forms.submission_policy`)
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionPolicy {
+    #[default]
+    OnePerAccount,
+    Unlimited,
 }
 
 /// Error response from WASI module
@@ -157,4 +621,6 @@ pub struct FormMetadata {
 pub struct ErrorResponse {
     pub success: bool,
     pub error: String,
+    /// Stable identifier clients branch on (see `error::FormsError::code`)
+    pub code: &'static str,
 }
@@ -2,18 +2,37 @@
 //!
 //! Pure functions extracted from `main.rs` for testability:
 //! - [`is_implicit_account`] — detects 64-char hex NEAR implicit accounts
-//! - [`sanitize_error`] — strips internal details from on-chain error messages
+//! - [`is_form_creator`] — constant-time creator authorization check
+//! - [`is_form_owner`] — the same check against every co-owner
+//! - [`check_encoded_size`] — size cap on `encrypted_answers` (SubmitForm and EstimateSize)
+//! - [`check_hex`] — validates hex without decoding it, returning the decoded length
+//! - [`decoded_size`] — ciphertext byte count of `encrypted_answers`, either layout
 //! - [`validate_ec01_hex`] — validates hex-encoded EC01 ciphertext format
-
-/// Maximum binary size for encrypted submissions (200 KB).
-const MAX_BLOB_SIZE: usize = 200 * 1024;
-
-/// Maximum hex-encoded length (hex doubles the byte count).
-const MAX_HEX_LEN: usize = MAX_BLOB_SIZE * 2;
+//! - [`validate_per_question_hex`] — validates a JSON object of per-question EC01 blobs
+//! - [`check_answer_keys`] — rejects answer keys that are not question IDs (strict forms)
+//! - [`check_required_answers`] — rejects final submissions missing a required answer
+//! - [`receipt_matches`] — checks a stored blob against a VerifySubmission receipt hash
+//! - [`is_utc_timestamp`] — accepts a `since` high-water mark before it reaches a db-api URL
+//! - [`parse_input`] — parses the action input with field-level error messages
+
+use crate::error::FormsError;
+use crate::types::Input;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Default maximum binary size for encrypted submissions (200 KB).
+/// Overridable via `MAX_BLOB_SIZE_BYTES`, which must match db-api's setting.
+pub const DEFAULT_MAX_BLOB_SIZE: usize = 200 * 1024;
 
 /// Minimum EC01 ciphertext size: magic(4) + compressed pubkey(33) + nonce(12) + Poly1305 tag(16).
 const MIN_EC01_SIZE: usize = 4 + 33 + 12 + 16;
 
+/// EC01 bytes checked structurally at submit time: magic(4) + compressed pubkey(33).
+const EC01_HEADER_SIZE: usize = 4 + 33;
+
+/// Error for `encrypted_answers` that is neither hex nor a JSON object of hex strings.
+const PER_QUESTION_SHAPE_ERROR: &str = "encrypted_answers must be hex or a JSON object of question ID to hex";
+
 /// Check if a NEAR account ID is an implicit account (64-char lowercase hex = ed25519 pubkey).
 ///
 /// Implicit accounts can be created without on-chain registration and could be used
@@ -29,109 +48,332 @@ pub fn is_implicit_account(account_id: &str) -> bool {
     account_id.len() == 64 && account_id.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'))
 }
 
-/// Known safe prefixes from this module's own error messages.
-const PASSTHROUGH_PREFIXES: &[&str] = &[
-    "Authentication required",
-    "Not authorized",
-    "Implicit accounts",
-    "Invalid input JSON",
-    "encrypted_answers too short",
-    "encrypted_answers too large",
-    "encrypted_answers hex too long",
-    "encrypted_answers must start with EC01 magic bytes",
-    "Invalid response_pubkey",
-    "Private key must be exactly",
-];
-
-/// Known safe substrings (exact phrases from db-api responses).
-/// COUPLING: These strings must match db-api error messages in:
-///   - db-api/src/lib.rs create_submission() → "already submitted this form" (unique violation)
-///   - db-api/src/lib.rs get_form() / create_submission() → "Form not found" (404 / FK violation)
-const PASSTHROUGH_CONTAINS: &[&str] = &[
-    "already submitted this form",
-    "Form not found",
-];
-
-/// Sanitize internal error messages for on-chain responses.
-///
-/// Keeps user-actionable messages (auth, format, size) but strips crypto
-/// implementation details. Uses prefix-based matching on this module's own error
-/// strings to avoid accidentally passing through dependency errors.
+/// Check that every key in a decrypted answers object is a question ID from `questions`.
 ///
-/// # Examples
+/// Used for forms with `strict_answers` so clients cannot smuggle arbitrary data inside
+/// the encrypted payload. Only the count of unknown keys is reported, since the error is
+/// returned on-chain.
+pub fn check_answer_keys(
+    answers: &serde_json::Value,
+    questions: &serde_json::Value,
+) -> Result<(), FormsError> {
+    let answers = answers
+        .as_object()
+        .ok_or_else(|| FormsError::InvalidInput("Answers must be a JSON object".to_string()))?;
+    let question_ids: Vec<&str> = questions
+        .as_array()
+        .map(|qs| qs.iter().filter_map(|q| q.get("id")?.as_str()).collect())
+        .unwrap_or_default();
+
+    let unknown = answers
+        .keys()
+        .filter(|key| !question_ids.contains(&key.as_str()))
+        .count();
+    if unknown > 0 {
+        return Err(FormsError::InvalidInput(format!(
+            "Answers contain {} key(s) that are not questions in this form",
+            unknown
+        )));
+    }
+    Ok(())
+}
+
+/// Whether any question in `questions` is required (not `optional`).
+pub fn has_required_questions(questions: &serde_json::Value) -> bool {
+    questions
+        .as_array()
+        .is_some_and(|qs| qs.iter().any(|q| !q.get("optional").and_then(|v| v.as_bool()).unwrap_or(false)))
+}
+
+/// Check that every required question has an answer, listing the ids of any that do not.
 ///
-/// ```ignore
-/// assert_eq!(sanitize_error("Not authorized to read responses"), "Not authorized to read responses");
-/// assert_eq!(sanitize_error("secp256k1 internal panic"), "Request failed. Please try again or contact the form administrator.");
-/// ```
-pub fn sanitize_error(err: &str) -> String {
-    for prefix in PASSTHROUGH_PREFIXES {
-        if err.starts_with(prefix) {
-            return err.to_string();
+/// Mirrors db-api's answer schema: `optional` questions may be omitted, and a question
+/// with `show_if` is required only when its parent's answer is one of the accepted
+/// values (or, for an array answer, contains one). Absent, `null`, `""` and `[]` count
+/// as unanswered. Question ids come from the form definition, so naming them reveals
+/// nothing about the respondent.
+pub fn check_required_answers(
+    answers: &serde_json::Value,
+    questions: &serde_json::Value,
+) -> Result<(), FormsError> {
+    use serde_json::Value;
+
+    let answers = answers
+        .as_object()
+        .ok_or_else(|| FormsError::InvalidInput("Answers must be a JSON object".to_string()))?;
+    let answered = |id: &str| match answers.get(id) {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(_) => true,
+    };
+    let shown = |show_if: &Value| {
+        let Some(parent) = show_if.get("question_id").and_then(Value::as_str) else {
+            return true;
+        };
+        let accepted: Vec<&Value> = match (show_if.get("value"), show_if.get("values")) {
+            (_, Some(Value::Array(values))) => values.iter().collect(),
+            (Some(value), _) if !value.is_null() => vec![value],
+            _ => return true,
+        };
+        match answers.get(parent) {
+            Some(Value::Array(items)) => items.iter().any(|item| accepted.contains(&item)),
+            Some(value) => accepted.contains(&value),
+            None => false,
         }
+    };
+
+    let missing: Vec<&str> = questions
+        .as_array()
+        .map(|qs| {
+            qs.iter()
+                .filter(|q| !q.get("optional").and_then(Value::as_bool).unwrap_or(false))
+                .filter(|q| q.get("show_if").filter(|v| !v.is_null()).is_none_or(shown))
+                .filter_map(|q| q.get("id")?.as_str())
+                .filter(|id| !answered(id))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !missing.is_empty() {
+        return Err(FormsError::InvalidInput(format!(
+            "Missing answers for required question(s): {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Parse the module input, naming the action in the error when it can be read.
+///
+/// Input structs deny unknown fields, so serde's message already names the offending field
+/// and lists the expected ones, e.g. `Invalid input JSON for action SubmitForm: unknown
+/// field `encrypted_answer`, expected `encrypted_answers` at line 1 column 38`.
+pub fn parse_input(body: &[u8]) -> Result<Input, FormsError> {
+    serde_json::from_slice(body).map_err(|e| {
+        // The action is echoed on-chain, so only include short plain identifiers
+        let action = serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v.get("action")?.as_str().map(str::to_string))
+            .filter(|a| a.len() <= 64 && a.bytes().all(|b| b.is_ascii_alphanumeric()));
+        FormsError::InvalidInput(match action {
+            Some(action) => format!("Invalid input JSON for action {}: {}", action, e),
+            None => format!("Invalid input JSON: {}", e),
+        })
+    })
+}
+
+/// Whether `caller_id` is the form's `creator_id`. Use this for every creator-auth check.
+///
+/// Account IDs are not secrets, but the check gates decrypted responses inside the TEE, so
+/// it follows db-api's API-Secret check: hash both sides (hiding the length) and compare
+/// the digests in constant time rather than with an early-exit `==`.
+pub fn is_form_creator(caller_id: &str, creator_id: &str) -> bool {
+    let caller: [u8; 32] = Sha256::digest(caller_id.as_bytes()).into();
+    let creator: [u8; 32] = Sha256::digest(creator_id.as_bytes()).into();
+    constant_time_eq::constant_time_eq_32(&caller, &creator)
+}
+
+/// Whether `caller_id` is one of `owners` (db-api's `form_owners`, creator included).
+///
+/// Every entry is compared with [`is_form_creator`] and none is skipped after a match, so
+/// timing does not reveal the caller's position in the list.
+pub fn is_form_owner(caller_id: &str, owners: &[String]) -> bool {
+    owners.iter().fold(false, |found, owner| found | is_form_creator(caller_id, owner))
+}
+
+/// Reject `encrypted_answers` longer than `max_blob_size * 2` characters (two hex characters
+/// per byte). Per-question answers are held to the same limit on their full JSON text.
+///
+/// `MAX_BLOB_SIZE_BYTES` caps decoded bytes in both services; this is how it is applied to
+/// the encoded text, before anything is decoded. db-api applies the same rule with the same
+/// message (naming `encrypted_blob`), so a blob passes both or neither.
+pub fn check_encoded_size(encoded_len: usize, max_blob_size: usize) -> Result<(), FormsError> {
+    let max_hex_len = max_blob_size * 2;
+    if encoded_len > max_hex_len {
+        return Err(FormsError::InvalidInput(format!(
+            "encrypted_answers too long: {} characters (max: {} characters for {} bytes, 2 hex characters per byte)",
+            encoded_len, max_hex_len, max_blob_size
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `stored_blob` hashes to `claimed_hash`, a SubmitForm `blob_hash` receipt (hex
+/// SHA-256 of the blob as db-api stored it, in either letter case). A value that is not
+/// a SHA-256 hex digest is invalid input rather than a mismatch.
+pub fn receipt_matches(stored_blob: &str, claimed_hash: &str) -> Result<bool, FormsError> {
+    if claimed_hash.len() != 64 || !claimed_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(FormsError::InvalidInput(
+            "blob_hash must be a hex SHA-256 digest (64 hex characters)".to_string(),
+        ));
     }
-    for substr in PASSTHROUGH_CONTAINS {
-        if err.contains(substr) {
-            return err.to_string();
-        }
+    Ok(crate::db::blob_hash(stored_blob).eq_ignore_ascii_case(claimed_hash))
+}
+
+/// Whether `ts` is a UTC RFC 3339 timestamp as db-api formats `submitted_at` (`Z` suffix).
+/// Anything else, including characters that would change a query string, is rejected.
+pub fn is_utc_timestamp(ts: &str) -> bool {
+    ts.len() <= 40
+        && ts.bytes().all(|b| matches!(b, b'0'..=b'9' | b'-' | b':' | b'.' | b'T' | b'Z'))
+        && crate::anomalies::parse_utc_micros(ts).is_some()
+}
+
+/// Error for ciphertext over `max_blob_size` decoded bytes (see [`check_encoded_size`]).
+pub fn blob_too_large(decoded_len: usize, max_blob_size: usize) -> FormsError {
+    FormsError::InvalidInput(format!(
+        "encrypted_answers too large: {} bytes decoded (max: {} bytes)",
+        decoded_len, max_blob_size
+    ))
+}
+
+/// Check that `hex_str` is valid hex and return its decoded byte count, without
+/// allocating the decoded bytes. Errors match `hex::decode`'s.
+///
+/// Submit-time validation only needs the EC01 header, so decoding the whole blob (up to
+/// the size cap, alongside the hex string that must be kept to forward to db-api) would
+/// double peak memory for nothing.
+pub fn check_hex(hex_str: &str) -> Result<usize, FormsError> {
+    let invalid = |e: hex::FromHexError| FormsError::InvalidInput(format!("Invalid hex in encrypted_answers: {}", e));
+    if !hex_str.len().is_multiple_of(2) {
+        return Err(invalid(hex::FromHexError::OddLength));
+    }
+    if let Some(index) = hex_str.bytes().position(|b| !b.is_ascii_hexdigit()) {
+        let c = hex_str.as_bytes()[index] as char;
+        return Err(invalid(hex::FromHexError::InvalidHexCharacter { c, index }));
+    }
+    Ok(hex_str.len() / 2)
+}
+
+/// Reject an empty or whitespace-only `encrypted_answers` up front, so the caller is told
+/// the field was empty rather than that zero bytes is too short for EC01.
+pub fn check_not_empty(encrypted_answers: &str) -> Result<(), FormsError> {
+    if encrypted_answers.trim().is_empty() {
+        return Err(FormsError::InvalidInput("encrypted_answers must not be empty".to_string()));
     }
+    Ok(())
+}
 
-    // Everything else (crypto internals, database errors) — return generic message
-    "Request failed. Please try again or contact the form administrator.".to_string()
+/// Return the ciphertext byte count of `encrypted_answers` (either layout), summed over
+/// fields for per-question answers. Checks the hex but neither the EC01 structure nor
+/// the size cap.
+pub fn decoded_size(encrypted_answers: &str) -> Result<usize, FormsError> {
+    if !crate::blob_format::is_per_question(encrypted_answers) {
+        return check_hex(encrypted_answers);
+    }
+    let fields: BTreeMap<String, String> = serde_json::from_str(encrypted_answers)
+        .map_err(|_| FormsError::InvalidInput(PER_QUESTION_SHAPE_ERROR.to_string()))?;
+    fields.values().try_fold(0usize, |total, hex_str| Ok(total + check_hex(hex_str)?))
 }
 
-/// Validate a hex-encoded EC01 ciphertext blob and return the decoded bytes.
+/// Validate a hex-encoded EC01 ciphertext blob and return its decoded byte count.
 ///
 /// Checks in order:
-/// 1. Hex length does not exceed [`MAX_HEX_LEN`] (400 KB)
-/// 2. Valid hex decoding
+/// 1. Hex length does not exceed `max_blob_size * 2`
+/// 2. Valid hex ([`check_hex`], nothing decoded)
 /// 3. Minimum binary size (magic + pubkey + nonce + tag = 65 bytes)
 /// 4. EC01 magic bytes (`b"EC01"`)
 /// 5. Ephemeral public key is a valid compressed secp256k1 point
-/// 6. Binary size does not exceed [`MAX_BLOB_SIZE`] (200 KB)
+/// 6. Binary size does not exceed `max_blob_size`
 ///
-/// Returns the decoded ciphertext bytes on success.
-pub fn validate_ec01_hex(hex_str: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-    if hex_str.len() > MAX_HEX_LEN {
-        return Err(format!(
-            "encrypted_answers hex too long: {} chars (max: {})",
-            hex_str.len(), MAX_HEX_LEN
-        ).into());
-    }
+/// Only the 37-byte header is decoded, into a stack buffer.
+pub fn validate_ec01_hex(hex_str: &str, max_blob_size: usize) -> Result<usize, FormsError> {
+    check_encoded_size(hex_str.len(), max_blob_size)?;
 
-    let encrypted_bytes = hex::decode(hex_str)
-        .map_err(|e| format!("Invalid hex in encrypted_answers: {}", e))?;
+    let decoded_len = check_hex(hex_str)?;
 
-    if encrypted_bytes.len() < MIN_EC01_SIZE {
-        return Err(format!(
+    if decoded_len < MIN_EC01_SIZE {
+        return Err(FormsError::InvalidInput(format!(
             "encrypted_answers too short: {} bytes, need at least {}",
-            encrypted_bytes.len(), MIN_EC01_SIZE
-        ).into());
+            decoded_len, MIN_EC01_SIZE
+        )));
     }
 
-    if &encrypted_bytes[0..4] != b"EC01" {
-        return Err("encrypted_answers must start with EC01 magic bytes".into());
+    let mut header = [0u8; EC01_HEADER_SIZE];
+    hex::decode_to_slice(&hex_str[..EC01_HEADER_SIZE * 2], &mut header)
+        .map_err(|e| FormsError::InvalidInput(format!("Invalid hex in encrypted_answers: {}", e)))?;
+
+    if &header[0..4] != b"EC01" {
+        return Err(FormsError::InvalidInput("encrypted_answers must start with EC01 magic bytes".to_string()));
     }
 
-    // Verify the ephemeral public key is a valid compressed secp256k1 point
-    let ephemeral_pubkey_bytes = &encrypted_bytes[4..37];
-    libsecp256k1::PublicKey::parse_slice(ephemeral_pubkey_bytes, None)
-        .map_err(|e| format!("Invalid ephemeral public key in EC01 blob: {:?}", e))?;
+    // Verify the ephemeral public key is a valid compressed secp256k1 point. parse_compressed
+    // requires the 0x02/0x03 prefix, so an uncompressed or hybrid (0x04/0x06/0x07) tag is
+    // rejected outright instead of relying on format detection from the slice length.
+    let ephemeral_pubkey_bytes: &[u8; 33] = header[4..37].try_into()
+        .map_err(|_| "EC01 ephemeral pubkey slice has the wrong length")?;
+    libsecp256k1::PublicKey::parse_compressed(ephemeral_pubkey_bytes)
+        .map_err(|e| FormsError::InvalidInput(format!("Invalid ephemeral public key in EC01 blob: {:?}", e)))?;
 
-    if encrypted_bytes.len() > MAX_BLOB_SIZE {
-        return Err(format!(
-            "encrypted_answers too large: {} bytes (max: {} bytes)",
-            encrypted_bytes.len(), MAX_BLOB_SIZE
-        ).into());
+    if decoded_len > max_blob_size {
+        return Err(blob_too_large(decoded_len, max_blob_size));
     }
 
-    Ok(encrypted_bytes)
+    Ok(decoded_len)
+}
+
+/// Validate per-question `encrypted_answers`: a JSON object mapping each question ID to a
+/// hex EC01 blob of that answer. Each field is checked with [`validate_ec01_hex`]; the
+/// whole string is held to the same hex length limit as a single blob, matching db-api.
+///
+/// Returns the decoded ciphertext size per question ID.
+pub fn validate_per_question_hex(
+    json: &str,
+    max_blob_size: usize,
+) -> Result<BTreeMap<String, usize>, FormsError> {
+    check_encoded_size(json.len(), max_blob_size)?;
+
+    let fields: BTreeMap<String, String> = serde_json::from_str(json)
+        .map_err(|_| FormsError::InvalidInput(PER_QUESTION_SHAPE_ERROR.to_string()))?;
+    if fields.is_empty() {
+        return Err(FormsError::InvalidInput("encrypted_answers has no fields".to_string()));
+    }
+    fields
+        .into_iter()
+        .map(|(id, hex_blob)| {
+            let size = validate_ec01_hex(&hex_blob, max_blob_size)
+                .map_err(|e| FormsError::InvalidInput(format!("{} (question {})", e, id)))?;
+            Ok((id, size))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ==================== is_utc_timestamp ====================
+
+    #[test]
+    fn utc_timestamp_accepts_db_api_format_only() {
+        assert!(is_utc_timestamp("2026-01-01T00:00:00.000001Z"));
+        assert!(is_utc_timestamp("2026-01-01T00:00:00Z"));
+        assert!(!is_utc_timestamp("2026-01-01T00:00:00+00:00"));
+        assert!(!is_utc_timestamp("2026-01-01T00:00:00Z&limit=1000"));
+        assert!(!is_utc_timestamp("2026-13-01T00:00:00Z"));
+        assert!(!is_utc_timestamp(""));
+    }
+
+    // ==================== is_form_creator ====================
+
+    #[test]
+    fn form_creator_matches_exactly() {
+        assert!(is_form_creator("alice.testnet", "alice.testnet"));
+        assert!(!is_form_creator("alice.testnet", "bob.testnet"));
+        assert!(!is_form_creator("alice.testne", "alice.testnet"));
+        assert!(!is_form_creator("ALICE.testnet", "alice.testnet"));
+        assert!(!is_form_creator("", "alice.testnet"));
+    }
+
+    #[test]
+    fn form_owner_matches_any_owner_exactly() {
+        let owners = vec!["alice.testnet".to_string(), "bob.testnet".to_string()];
+        assert!(is_form_owner("alice.testnet", &owners));
+        assert!(is_form_owner("bob.testnet", &owners));
+        assert!(!is_form_owner("bob.testne", &owners));
+        assert!(!is_form_owner("carol.testnet", &owners));
+        assert!(!is_form_owner("alice.testnet", &[]));
+    }
+
     // ==================== is_implicit_account ====================
 
     #[test]
@@ -183,75 +425,6 @@ mod tests {
         assert!(!is_implicit_account(&mixed));
     }
 
-    // ==================== sanitize_error ====================
-
-    #[test]
-    fn sanitize_auth_required() {
-        let msg = "Authentication required - signer_account_id not available";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_not_authorized() {
-        let msg = "Not authorized to read responses";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_implicit_accounts() {
-        let msg = "Implicit accounts (64-char hex) are not allowed.";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_invalid_json() {
-        let msg = "Invalid input JSON: expected value at line 1 column 1";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_too_short() {
-        let msg = "encrypted_answers too short: 10 bytes, need at least 65";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_too_large() {
-        let msg = "encrypted_answers too large: 999999 bytes (max: 204800 bytes)";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_db_api_already_submitted() {
-        let msg = "db-api error: You have already submitted this form. Each account can only submit once.";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_db_api_form_not_found() {
-        let msg = "db-api returned 404: Form not found";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_hex_too_long() {
-        let msg = "encrypted_answers hex too long: 500000 chars (max: 409600)";
-        assert_eq!(sanitize_error(msg), msg);
-    }
-
-    #[test]
-    fn sanitize_crypto_error_stripped() {
-        let msg = "secp256k1 scalar multiplication failed: InvalidSecretKey";
-        let sanitized = sanitize_error(msg);
-        assert_eq!(sanitized, "Request failed. Please try again or contact the form administrator.");
-    }
-
-    #[test]
-    fn sanitize_empty_string() {
-        let sanitized = sanitize_error("");
-        assert_eq!(sanitized, "Request failed. Please try again or contact the form administrator.");
-    }
-
     // ==================== validate_ec01_hex ====================
 
     /// Build a minimal valid EC01 hex blob using a real secp256k1 public key.
@@ -275,38 +448,63 @@ mod tests {
     #[test]
     fn ec01_valid_minimal() {
         let hex_blob = make_valid_ec01_hex(0);
-        let result = validate_ec01_hex(&hex_blob);
+        let result = validate_ec01_hex(&hex_blob, DEFAULT_MAX_BLOB_SIZE);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), MIN_EC01_SIZE);
+        assert_eq!(result.unwrap(), MIN_EC01_SIZE);
     }
 
     #[test]
     fn ec01_valid_with_payload() {
         let hex_blob = make_valid_ec01_hex(100);
-        let result = validate_ec01_hex(&hex_blob);
+        let result = validate_ec01_hex(&hex_blob, DEFAULT_MAX_BLOB_SIZE);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), MIN_EC01_SIZE + 100);
+        assert_eq!(result.unwrap(), MIN_EC01_SIZE + 100);
     }
 
     #[test]
     fn ec01_hex_too_long() {
-        // MAX_HEX_LEN + 2 chars (1 extra byte)
-        let hex_blob = "a".repeat(MAX_HEX_LEN + 2);
-        let err = validate_ec01_hex(&hex_blob).unwrap_err();
+        // Hex limit + 2 chars (1 extra byte)
+        let hex_blob = "a".repeat(DEFAULT_MAX_BLOB_SIZE * 2 + 2);
+        let err = validate_ec01_hex(&hex_blob, DEFAULT_MAX_BLOB_SIZE).unwrap_err();
         assert!(err.to_string().contains("too long"));
     }
 
+    #[test]
+    fn size_errors_state_limit_and_actual_size() {
+        // Same wording as db-api's check (which names `encrypted_blob`)
+        let err = check_encoded_size(21, 10).unwrap_err();
+        assert_eq!(
+            err.public_message(),
+            "encrypted_answers too long: 21 characters (max: 20 characters for 10 bytes, 2 hex characters per byte)"
+        );
+        assert!(check_encoded_size(20, 10).is_ok());
+        assert_eq!(
+            blob_too_large(11, 10).public_message(),
+            "encrypted_answers too large: 11 bytes decoded (max: 10 bytes)"
+        );
+    }
+
     #[test]
     fn ec01_invalid_hex_chars() {
-        let err = validate_ec01_hex("ZZZZ").unwrap_err();
+        let err = validate_ec01_hex("ZZZZ", DEFAULT_MAX_BLOB_SIZE).unwrap_err();
         assert!(err.to_string().contains("Invalid hex"));
     }
 
+    #[test]
+    fn empty_encrypted_answers_rejected() {
+        for empty in ["", "   ", "\n\t"] {
+            let err = check_not_empty(empty).unwrap_err();
+            assert_eq!(err.to_string(), "encrypted_answers must not be empty");
+            assert_eq!(err.code(), "invalid_input");
+        }
+        assert!(check_not_empty("4543").is_ok());
+    }
+
     #[test]
     fn ec01_too_short() {
         // Valid hex but too few bytes once decoded
         let short = hex::encode(b"EC01");  // only 4 bytes
-        let err = validate_ec01_hex(&short).unwrap_err();
+        let err = validate_ec01_hex(&short, DEFAULT_MAX_BLOB_SIZE).unwrap_err();
         assert!(err.to_string().contains("too short"));
     }
 
@@ -315,7 +513,7 @@ mod tests {
         // Right length but wrong magic bytes
         let mut bytes = vec![0u8; MIN_EC01_SIZE];
         bytes[0..4].copy_from_slice(b"XXXX");
-        let err = validate_ec01_hex(&hex::encode(&bytes)).unwrap_err();
+        let err = validate_ec01_hex(&hex::encode(&bytes), DEFAULT_MAX_BLOB_SIZE).unwrap_err();
         assert!(err.to_string().contains("EC01 magic bytes"));
     }
 
@@ -325,18 +523,229 @@ mod tests {
         let mut bytes = vec![0u8; MIN_EC01_SIZE];
         bytes[0..4].copy_from_slice(b"EC01");
         // bytes[4..37] are all zeros — invalid compressed point
-        let err = validate_ec01_hex(&hex::encode(&bytes)).unwrap_err();
+        let err = validate_ec01_hex(&hex::encode(&bytes), DEFAULT_MAX_BLOB_SIZE).unwrap_err();
+        assert!(err.to_string().contains("Invalid ephemeral public key"));
+    }
+
+    #[test]
+    fn ec01_rejects_uncompressed_pubkey() {
+        // A 65-byte uncompressed key (0x04 || x || y) where the 33-byte compressed key belongs
+        let secret = libsecp256k1::SecretKey::parse_slice(&[1u8; 32]).unwrap();
+        let uncompressed = libsecp256k1::PublicKey::from_secret_key(&secret).serialize();
+        let mut bytes = b"EC01".to_vec();
+        bytes.extend_from_slice(&uncompressed);
+        bytes.extend_from_slice(&[0u8; 28]);
+        let err = validate_ec01_hex(&hex::encode(&bytes), DEFAULT_MAX_BLOB_SIZE).unwrap_err();
         assert!(err.to_string().contains("Invalid ephemeral public key"));
     }
 
     #[test]
     fn ec01_oversized_binary() {
-        // Since MAX_HEX_LEN = MAX_BLOB_SIZE * 2, any blob exceeding MAX_BLOB_SIZE
-        // also exceeds MAX_HEX_LEN and hits the hex-length check first.
-        // The binary size check is defense-in-depth for independent constant changes.
-        let excess = MAX_BLOB_SIZE - MIN_EC01_SIZE + 1;
+        // The hex limit is always max_blob_size * 2, so any blob exceeding max_blob_size
+        // also exceeds the hex limit and hits the hex-length check first.
+        // The binary size check is defense-in-depth.
+        let excess = DEFAULT_MAX_BLOB_SIZE - MIN_EC01_SIZE + 1;
         let hex_blob = make_valid_ec01_hex(excess);
-        let err = validate_ec01_hex(&hex_blob).unwrap_err();
+        let err = validate_ec01_hex(&hex_blob, DEFAULT_MAX_BLOB_SIZE).unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn ec01_respects_configured_limit() {
+        let hex_blob = make_valid_ec01_hex(100);
+        assert!(validate_ec01_hex(&hex_blob, MIN_EC01_SIZE + 100).is_ok());
+        let err = validate_ec01_hex(&hex_blob, MIN_EC01_SIZE + 99).unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn per_question_valid() {
+        let json = format!(r#"{{"q1":"{}","q2":"{}"}}"#, make_valid_ec01_hex(0), make_valid_ec01_hex(4));
+        let fields = validate_per_question_hex(&json, DEFAULT_MAX_BLOB_SIZE).unwrap();
+        assert_eq!(fields.keys().collect::<Vec<_>>(), ["q1", "q2"]);
+        assert_eq!(fields["q2"], MIN_EC01_SIZE + 4);
+    }
+
+    #[test]
+    fn per_question_rejects_bad_field() {
+        let json = format!(r#"{{"q1":"{}","q2":"00"}}"#, make_valid_ec01_hex(0));
+        let err = validate_per_question_hex(&json, DEFAULT_MAX_BLOB_SIZE).unwrap_err();
+        assert!(err.to_string().starts_with("encrypted_answers too short"));
+        assert!(err.to_string().ends_with("(question q2)"));
+    }
+
+    #[test]
+    fn per_question_rejects_non_object_and_empty() {
+        for json in ["{", "{\"q1\": 1}", "{}"] {
+            let err = validate_per_question_hex(json, DEFAULT_MAX_BLOB_SIZE).unwrap_err();
+            assert!(err.to_string().starts_with("encrypted_answers"), "{}", json);
+        }
+    }
+
+    #[test]
+    fn per_question_total_size_limited() {
+        let json = format!(r#"{{"q1":"{0}","q2":"{0}"}}"#, make_valid_ec01_hex(100));
+        let err = validate_per_question_hex(&json, MIN_EC01_SIZE + 100).unwrap_err();
         assert!(err.to_string().contains("too long"));
     }
+
+    // ==================== decoded_size ====================
+
+    #[test]
+    fn decoded_size_whole_and_per_question() {
+        assert_eq!(decoded_size(&make_valid_ec01_hex(10)).unwrap(), MIN_EC01_SIZE + 10);
+        let json = format!(r#"{{"q1":"{}","q2":"{}"}}"#, make_valid_ec01_hex(0), make_valid_ec01_hex(5));
+        assert_eq!(decoded_size(&json).unwrap(), 2 * MIN_EC01_SIZE + 5);
+    }
+
+    #[test]
+    fn decoded_size_rejects_bad_hex() {
+        let err = decoded_size("zz").unwrap_err();
+        assert_eq!(err.public_message(), err.to_string());
+        assert!(decoded_size(r#"{"q1":"0"}"#).is_err());
+    }
+
+    // ==================== parse_input ====================
+
+    fn parse_err(json: &str) -> String {
+        parse_input(json.as_bytes()).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parse_input_accepts_valid_actions() {
+        assert!(matches!(
+            parse_input(br#"{"action":"SubmitForm","encrypted_answers":"ab"}"#).unwrap(),
+            Input::SubmitForm(_)
+        ));
+        assert!(matches!(
+            parse_input(br#"{"action":"ReadResponses","response_pubkey":"02"}"#).unwrap(),
+            Input::ReadResponses(_)
+        ));
+        assert!(matches!(
+            parse_input(br#"{"action":"GetMasterPublicKey"}"#).unwrap(),
+            Input::GetMasterPublicKey(_)
+        ));
+        assert!(matches!(
+            parse_input(br#"{"action":"Diagnostics"}"#).unwrap(),
+            Input::Diagnostics(_)
+        ));
+    }
+
+    #[test]
+    fn parse_input_names_typoed_field() {
+        let err = parse_err(r#"{"action":"SubmitForm","encrypted_answer":"ab"}"#);
+        assert!(err.starts_with("Invalid input JSON for action SubmitForm"), "{}", err);
+        assert!(err.contains("unknown field `encrypted_answer`"), "{}", err);
+        assert!(err.contains("expected `encrypted_answers`"), "{}", err);
+        assert_eq!(parse_input(b"{").unwrap_err().code(), "invalid_input");
+    }
+
+    #[test]
+    fn parse_input_lists_expected_fields() {
+        let err = parse_err(r#"{"action":"ReadResponses","response_pubkey":"02","page":2}"#);
+        assert!(err.contains("unknown field `page`"), "{}", err);
+        assert!(err.contains("`offset`") && err.contains("`limit`"), "{}", err);
+
+        let err = parse_err(r#"{"action":"GetFormPublicKey","form_id":"x"}"#);
+        assert!(err.contains("unknown field `form_id`"), "{}", err);
+    }
+
+    #[test]
+    fn parse_input_reports_missing_field_and_unknown_action() {
+        let err = parse_err(r#"{"action":"SubmitForm"}"#);
+        assert!(err.contains("missing field `encrypted_answers`"), "{}", err);
+
+        let err = parse_err(r#"{"action":"DeleteEverything"}"#);
+        assert!(err.contains("unknown variant `DeleteEverything`"), "{}", err);
+
+        // Non-identifier actions are not echoed back
+        let err = parse_err(r#"{"action":"<script>"}"#);
+        assert!(err.starts_with("Invalid input JSON: "), "{}", err);
+    }
+
+    // ==================== check_answer_keys ====================
+
+    fn sample_questions() -> serde_json::Value {
+        serde_json::json!([{ "id": "q1" }, { "id": "q2" }])
+    }
+
+    #[test]
+    fn answer_keys_subset_accepted() {
+        let answers = serde_json::json!({ "q1": "A" });
+        assert!(check_answer_keys(&answers, &sample_questions()).is_ok());
+        assert!(check_answer_keys(&serde_json::json!({}), &sample_questions()).is_ok());
+    }
+
+    #[test]
+    fn answer_keys_unknown_rejected_without_echoing_keys() {
+        let answers = serde_json::json!({ "q1": "A", "smuggled": "x", "other": 1 });
+        let err = check_answer_keys(&answers, &sample_questions()).unwrap_err();
+        assert!(matches!(err, FormsError::InvalidInput(_)));
+        let err = err.public_message();
+        assert!(err.contains("2 key(s)"));
+        assert!(!err.contains("smuggled"));
+    }
+
+    #[test]
+    fn answer_keys_require_object() {
+        let err = check_answer_keys(&serde_json::json!(["q1"]), &sample_questions()).unwrap_err();
+        assert!(err.to_string().contains("JSON object"));
+    }
+
+    // ==================== check_required_answers ====================
+
+    fn required_questions() -> serde_json::Value {
+        serde_json::json!([
+            { "id": "q1", "optional": false },
+            { "id": "q2", "optional": true },
+            { "id": "q3", "type": "multi_select", "optional": false },
+            { "id": "q3b", "optional": false, "show_if": { "question_id": "q3", "value": "Other" } },
+            { "id": "q4", "optional": false, "show_if": { "question_id": "q1", "values": ["Yes", "Maybe"] } }
+        ])
+    }
+
+    #[test]
+    fn required_answers_present_or_optional_accepted() {
+        let answers = serde_json::json!({ "q1": "No", "q3": ["A"] });
+        assert!(check_required_answers(&answers, &required_questions()).is_ok());
+        assert!(has_required_questions(&required_questions()));
+        assert!(!has_required_questions(&serde_json::json!([{ "id": "q1", "optional": true }])));
+    }
+
+    #[test]
+    fn required_answers_missing_are_listed() {
+        let answers = serde_json::json!({ "q1": "", "q3": [], "q2": "x" });
+        let err = check_required_answers(&answers, &required_questions()).unwrap_err();
+        assert!(matches!(err, FormsError::InvalidInput(_)));
+        assert_eq!(err.public_message(), "Missing answers for required question(s): q1, q3");
+    }
+
+    #[test]
+    fn required_answers_follow_show_if() {
+        let answers = serde_json::json!({ "q1": "Maybe", "q3": ["A", "Other"] });
+        let err = check_required_answers(&answers, &required_questions()).unwrap_err();
+        assert_eq!(err.public_message(), "Missing answers for required question(s): q3b, q4");
+
+        let answers = serde_json::json!({ "q1": "Maybe", "q3": ["Other"], "q3b": "text", "q4": "y" });
+        assert!(check_required_answers(&answers, &required_questions()).is_ok());
+    }
+
+    // ==================== receipt_matches ====================
+
+    #[test]
+    fn receipt_matches_stored_blob_hash() {
+        let blob = "45433031ab";
+        let hash = crate::db::blob_hash(blob);
+        assert!(receipt_matches(blob, &hash).unwrap());
+        assert!(receipt_matches(blob, &hash.to_ascii_uppercase()).unwrap());
+        assert!(!receipt_matches("45433031ac", &hash).unwrap());
+    }
+
+    #[test]
+    fn receipt_must_be_a_sha256_digest() {
+        for claimed in ["", "abc", &"g".repeat(64), &"a".repeat(63), &"a".repeat(66)] {
+            let err = receipt_matches("45433031ab", claimed).unwrap_err();
+            assert!(matches!(err, FormsError::InvalidInput(_)), "{}", err);
+        }
+    }
 }
@@ -3,13 +3,15 @@
  *
  * Verifies that encryptEC01 and decryptEC01 are consistent, and that the key
  * derivation (deriveFormPublicKey) produces deterministic results matching the
- * Rust golden test vector.
+ * Rust golden test vector. Form encryption takes the registered form key; tests
+ * derive one here to stand in for it.
  */
 
 import { describe, it, expect } from 'vitest';
 import { secp256k1 } from '@noble/curves/secp256k1.js';
 import { bytesToHex, hexToBytes, concatBytes } from '@noble/hashes/utils.js';
-import { encryptFormAnswers, encryptEC01, generateSessionKeypair, decryptEC01, deriveFormPublicKey } from './crypto';
+import { sha256 } from '@noble/hashes/sha2.js';
+import { encryptFormAnswers, encryptFormAnswersPerQuestion, encryptEC01, generateSessionKeypair, decryptEC01, deriveFormPublicKey, hashToTweak, parseFormPublicKey } from './crypto';
 
 // ==================== Constants (must match crypto.ts and Rust) ====================
 
@@ -25,6 +27,9 @@ const TEST_FORM_ID = 'daf14a0c-20f7-4199-a07b-c6456d53ef2d';
 // The compressed public key for private key = 1 (the generator point G)
 const TEST_MASTER_PUBKEY = '0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798';
 
+// What db-api would serve as the test form's registered public key
+const TEST_FORM_PUBKEY = bytesToHex(deriveFormPublicKey(TEST_MASTER_PUBKEY, TEST_FORM_ID));
+
 // ==================== Tests ====================
 
 describe('EC01 encrypt/decrypt round-trip', () => {
@@ -116,10 +121,9 @@ describe('EC01 encrypt/decrypt round-trip', () => {
 });
 
 describe('encryptFormAnswers (full flow)', () => {
-  it('encrypts form answers with derived form key', () => {
-    // This tests the full client-side flow: derive form key, encrypt answers
+  it('encrypts form answers to the registered form key', () => {
     const answers = { q1: 'Yes', q2: ['A', 'B'] };
-    const encryptedHex = encryptFormAnswers(TEST_MASTER_PUBKEY, TEST_FORM_ID, answers);
+    const encryptedHex = encryptFormAnswers(TEST_FORM_PUBKEY, answers);
 
     // Verify EC01 format
     expect(encryptedHex.substring(0, 8)).toBe('45433031'); // "EC01" in hex
@@ -129,6 +133,24 @@ describe('encryptFormAnswers (full flow)', () => {
     const bytes = hexToBytes(encryptedHex);
     expect(bytes.length).toBeGreaterThan(4 + 33 + 12 + 16);
   });
+
+  it('rejects a malformed or off-curve form key', () => {
+    expect(() => encryptFormAnswers('not-a-key', {})).toThrow('compressed secp256k1');
+    expect(() => parseFormPublicKey('02' + 'ff'.repeat(32))).toThrow();
+  });
+});
+
+describe('encryptFormAnswersPerQuestion', () => {
+  it('encrypts each answer as its own EC01 blob', () => {
+    const answers = { q1: 'Yes', q2: ['A', 'B'] };
+    const fields = JSON.parse(encryptFormAnswersPerQuestion(TEST_FORM_PUBKEY, answers));
+
+    expect(Object.keys(fields)).toEqual(['q1', 'q2']);
+    for (const blob of Object.values(fields) as string[]) {
+      expect(blob.substring(0, 8)).toBe('45433031');
+      expect(hexToBytes(blob).length).toBeGreaterThan(4 + 33 + 12 + 16);
+    }
+  });
 });
 
 describe('Key derivation', () => {
@@ -171,13 +193,45 @@ describe('Key derivation', () => {
     // Pin form pubkey — must match Rust golden_derive_form_pubkey test
     expect(formPubKeyHex).toBe('02257731f1d53b68b0c8e8602250746131b1b037556343b4f666c9ac753e5cc4ea');
   });
+
+  it('golden vector: tenant-isolated form pubkey matches Rust derivation', () => {
+    // Pin — must match Rust golden_derive_form_pubkey_with_tenant
+    const formPubKeyHex = bytesToHex(deriveFormPublicKey(TEST_MASTER_PUBKEY, TEST_FORM_ID, 'acme'));
+    expect(formPubKeyHex).toBe('03c8d3bc21043073291acb6672dd4476fcf001c66bfb32bb1766460051f16bb4dd');
+    expect(bytesToHex(deriveFormPublicKey(TEST_MASTER_PUBKEY, TEST_FORM_ID, ''))).toBe(
+      '02257731f1d53b68b0c8e8602250746131b1b037556343b4f666c9ac753e5cc4ea',
+    );
+  });
+
+  it('golden vector: salted form pubkey matches Rust derivation', () => {
+    // Pin — must match Rust golden_derive_form_pubkey_with_salt
+    const salt = '00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff';
+    const formPubKeyHex = bytesToHex(deriveFormPublicKey(TEST_MASTER_PUBKEY, TEST_FORM_ID, '', salt));
+    expect(formPubKeyHex).toBe('03cf456917c7b881866a25be81469438dd8bfc2933b547c475bb8bf9dc307a8f98');
+    expect(bytesToHex(deriveFormPublicKey(TEST_MASTER_PUBKEY, TEST_FORM_ID, '', null))).toBe(
+      '02257731f1d53b68b0c8e8602250746131b1b037556343b4f666c9ac753e5cc4ea',
+    );
+  });
+
+  it('zero tweak is rehashed with a counter suffix (matches Rust hash_to_tweak)', () => {
+    const curveOrder = hexToBytes('fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141');
+    // Digests that reduce to zero (n, then 0) for the first two attempts
+    const crafted = (data: Uint8Array) => {
+      const text = new TextDecoder().decode(data);
+      if (text === 'form') return curveOrder;
+      if (text === 'form:1') return new Uint8Array(32);
+      return sha256(data);
+    };
+    const input = encoder.encode('form');
+    expect(hashToTweak(input, crafted)).toBe(BigInt('0x' + bytesToHex(sha256(encoder.encode('form:2')))));
+  });
 });
 
 describe('Full submit→read→decrypt integration flow', () => {
   it('simulates the complete encrypted form lifecycle', () => {
-    // Step 1: Client encrypts answers with derived form public key (SubmitForm)
+    // Step 1: Client encrypts answers to the registered form public key (SubmitForm)
     const answers = { q1: 'Option A', q2: ['Choice 1', 'Choice 2'], q3: 'Open text response' };
-    const encryptedSubmission = encryptFormAnswers(TEST_MASTER_PUBKEY, TEST_FORM_ID, answers);
+    const encryptedSubmission = encryptFormAnswers(TEST_FORM_PUBKEY, answers);
 
     // Verify EC01 format
     expect(encryptedSubmission.substring(0, 8)).toBe('45433031');
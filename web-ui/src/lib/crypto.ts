@@ -33,21 +33,25 @@ const Point = secp256k1.Point;
 /**
  * Derive the form-specific public key from the master public key.
  *
- * Matches Rust: form_pubkey = master_pubkey + SHA256("near-forms:v1:" + form_id) * G
+ * Matches Rust: form_pubkey = master_pubkey + SHA256("near-forms:v1:" + [tenant + ":"] + form_id + [":" + salt]) * G
+ * The app does not call this: a form's `derivation_salt` is secret to db-api and the WASI
+ * module, so the browser encrypts to the registered key (`GET /v1/forms/:id/public-key`).
+ * Kept as the reference implementation the golden vectors pin to the Rust side.
  */
-export function deriveFormPublicKey(masterPubKeyHex: string, formId: string): Uint8Array {
-  // Compute tweak: SHA256(prefix + form_id)
+export function deriveFormPublicKey(
+  masterPubKeyHex: string,
+  formId: string,
+  tenant: string = '',
+  salt?: string | null,
+): Uint8Array {
+  // Compute tweak: SHA256(prefix + [tenant + ":"] + form_id + [":" + salt])
   const tweakInput = concatBytes(
     encoder.encode(DERIVATION_PREFIX),
+    encoder.encode(tenant ? `${tenant}:` : ''),
     encoder.encode(formId),
+    encoder.encode(salt ? `:${salt}` : ''),
   );
-  const tweakHash = sha256(tweakInput);
-
-  // Reduce tweak modulo curve order (matches Rust SecretKey::parse_slice behavior)
-  const tweakScalar = bytesToBigInt(tweakHash) % CURVE_ORDER;
-  if (tweakScalar === 0n) {
-    throw new Error('Tweak is zero (astronomically unlikely)');
-  }
+  const tweakScalar = hashToTweak(tweakInput);
 
   // tweak * G
   const tweakPoint = Point.BASE.multiply(tweakScalar);
@@ -59,6 +63,19 @@ export function deriveFormPublicKey(masterPubKeyHex: string, formId: string): Ui
   return formPoint.toBytes(true); // compressed, 33 bytes
 }
 
+/**
+ * Hash to a nonzero scalar: hash(input) mod n, rehashing input + ":" + counter (1, 2, ...)
+ * in the ~2^-256 case that reduces to zero. Matches Rust `hash_to_tweak`; `hash` is a
+ * parameter only so tests can reach the rehash path.
+ */
+export function hashToTweak(input: Uint8Array, hash: (data: Uint8Array) => Uint8Array = sha256): bigint {
+  let scalar = bytesToBigInt(hash(input)) % CURVE_ORDER;
+  for (let counter = 1; scalar === 0n; counter++) {
+    scalar = bytesToBigInt(hash(concatBytes(input, encoder.encode(`:${counter}`)))) % CURVE_ORDER;
+  }
+  return scalar;
+}
+
 /**
  * Encrypt plaintext using EC01 format (ECDH + ChaCha20-Poly1305).
  *
@@ -88,24 +105,57 @@ export function encryptEC01(formPubKey: Uint8Array, plaintext: Uint8Array): stri
   return bytesToHex(output);
 }
 
+/**
+ * Parse a compressed form public key, throwing unless it is a point on secp256k1.
+ *
+ * @param formPubKeyHex - The form's registered key from `GET /v1/forms/:id/public-key`
+ */
+export function parseFormPublicKey(formPubKeyHex: string): Uint8Array {
+  if (!COMPRESSED_PUBKEY_REGEX.test(formPubKeyHex)) {
+    throw new Error('Form public key must be a 66-char hex compressed secp256k1 public key (starts with 02 or 03)');
+  }
+  return Point.fromHex(formPubKeyHex).toBytes(true);
+}
+
 /**
  * Encrypt form answers for submission.
  *
- * @param masterPubKeyHex - Hex-encoded compressed master public key (66 chars)
- * @param formId - Form UUID
+ * @param formPubKeyHex - The form's registered public key (compressed hex, 66 chars)
  * @param answers - Answer map {question_id: answer_value}
  * @returns Hex-encoded EC01 blob
  */
 export function encryptFormAnswers(
-  masterPubKeyHex: string,
-  formId: string,
+  formPubKeyHex: string,
   answers: Record<string, unknown>,
 ): string {
-  const formPubKey = deriveFormPublicKey(masterPubKeyHex, formId);
+  const formPubKey = parseFormPublicKey(formPubKeyHex);
   const plaintext = encoder.encode(JSON.stringify(answers));
   return encryptEC01(formPubKey, plaintext);
 }
 
+/**
+ * Encrypt form answers one question at a time, so the creator side can decrypt a
+ * single answer without the rest.
+ *
+ * Each value is JSON-encoded and EC01-encrypted on its own; the result is a JSON
+ * object of question ID to hex blob, accepted anywhere a single blob is.
+ *
+ * @param formPubKeyHex - The form's registered public key (compressed hex, 66 chars)
+ * @param answers - Answer map {question_id: answer_value}
+ * @returns JSON string {question_id: hex EC01 blob}
+ */
+export function encryptFormAnswersPerQuestion(
+  formPubKeyHex: string,
+  answers: Record<string, unknown>,
+): string {
+  const formPubKey = parseFormPublicKey(formPubKeyHex);
+  const fields: Record<string, string> = {};
+  for (const [questionId, value] of Object.entries(answers)) {
+    fields[questionId] = encryptEC01(formPubKey, encoder.encode(JSON.stringify(value)));
+  }
+  return JSON.stringify(fields);
+}
+
 /** Convert Uint8Array to BigInt (big-endian) */
 function bytesToBigInt(bytes: Uint8Array): bigint {
   let result = 0n;
@@ -116,6 +116,10 @@ export interface ResponseRecord {
   submitter_id: string;
   answers: Record<string, unknown>;
   submitted_at: string;
+  /** Master key version that decrypted this response ("current" or "previous") */
+  key_version?: string;
+  /** Answers only parsed after a lenient recovery (LENIENT_ANSWERS_JSON in the WASI module) */
+  recovered?: boolean;
 }
 
 export type SortField = 'submitter_id' | 'submitted_at';
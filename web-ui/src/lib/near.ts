@@ -42,6 +42,8 @@ export interface TransactionResult {
 export interface OutLayerResult {
   success?: boolean;
   error?: string;
+  /** Stable identifier for typed failures, e.g. 'upstream_unavailable' */
+  code?: string;
   encrypted_payload?: string;
   [key: string]: unknown;
 }
@@ -9,6 +9,8 @@ export interface FormQuestion {
   show_if: { question_id: string; value?: string; values?: string[] } | null;
   exclusive_options?: string[];
   rank_count?: number;
+  /** IDs this question had in earlier form versions; ReadResponses files those answers under `id` */
+  renamed_from?: string[];
 }
 
 export interface FormData {
@@ -17,3 +19,9 @@ export interface FormData {
   questions: FormQuestion[];
   creator_id: string;
 }
+
+/** `GET /v1/forms/:id/public-key`: the key submissions are encrypted to, registered by the WASI module */
+export interface FormPublicKey {
+  form_id: string;
+  public_key: string;
+}
@@ -2,9 +2,9 @@ import { useRouter } from 'next/router';
 import { useEffect, useState } from 'react';
 import Head from 'next/head';
 import Link from 'next/link';
-import type { FormQuestion, FormData } from '@/lib/types';
+import type { FormQuestion, FormData, FormPublicKey } from '@/lib/types';
 import { useFetchWithTimeout, getFormApiUrl, useWallet } from '@/lib/hooks';
-import { deriveFormPublicKey, COMPRESSED_PUBKEY_REGEX } from '@/lib/crypto';
+import { parseFormPublicKey } from '@/lib/crypto';
 import { isVisible, resetHiddenAnswers, applyExclusiveMultiSelect, sanitizeUserError } from '@/lib/form-helpers';
 import { WALLET_ERR_CANCELLED, WALLET_ERR_WINDOW_CLOSED } from '@/lib/near';
 import type { AnswerValue, AnswerMap } from '@/lib/form-helpers';
@@ -287,8 +287,15 @@ export default function SubmitFormPage() {
     formUrl,
     { skip: !formId }
   );
+  // The key answers are encrypted to, as registered with db-api by the WASI module. Clients
+  // cannot derive it: the form's derivation salt is never served publicly.
+  const { data: keyData, loading: keyLoading, error: keyError } = useFetchWithTimeout<FormPublicKey>(
+    formUrl ? `${formUrl}/public-key` : null,
+    { skip: !formId }
+  );
 
   const [form, setForm] = useState<FormData | null>(null);
+  const [formPubKey, setFormPubKey] = useState<string | null>(null);
   const [answers, setAnswers] = useState<AnswerMap>({});
   const [loading, setLoading] = useState(true);
   const [submitting, setSubmitting] = useState(false);
@@ -304,7 +311,7 @@ export default function SubmitFormPage() {
     setFormDisabled(false);
     setMessage(null);
 
-    if (formLoading) return;
+    if (formLoading || keyLoading) return;
 
     if (formError) {
       setMessage({ type: 'error', text: formError });
@@ -315,22 +322,19 @@ export default function SubmitFormPage() {
     if (formData) {
       setForm(formData);
 
-      // Validate master public key early so users see misconfiguration immediately
-      const masterPubKey = process.env.NEXT_PUBLIC_MASTER_PUBLIC_KEY;
-      if (!masterPubKey) {
-        setMessage({ type: 'error', text: 'NEXT_PUBLIC_MASTER_PUBLIC_KEY not configured. Form submissions are disabled.' });
-        setFormDisabled(true);
-      } else if (!COMPRESSED_PUBKEY_REGEX.test(masterPubKey)) {
-        setMessage({ type: 'error', text: 'NEXT_PUBLIC_MASTER_PUBLIC_KEY is malformed. Expected a 66-char hex compressed secp256k1 public key (starts with 02 or 03).' });
+      // Validate the form key early so users see a missing registration immediately.
+      // Uses module-level import (synchronous) to avoid race window where form
+      // could be submitted before async validation completes.
+      setFormPubKey(null);
+      if (keyError || !keyData || keyData.form_id !== formData.id) {
+        setMessage({ type: 'error', text: "This form's encryption key is not registered yet. Form submissions are disabled." });
         setFormDisabled(true);
       } else {
-        // Validate the key is actually on the secp256k1 curve (not just valid hex).
-        // Uses module-level import (synchronous) to avoid race window where form
-        // could be submitted before async validation completes.
         try {
-          deriveFormPublicKey(masterPubKey, formData.id);
+          parseFormPublicKey(keyData.public_key);
+          setFormPubKey(keyData.public_key);
         } catch {
-          setMessage({ type: 'error', text: 'NEXT_PUBLIC_MASTER_PUBLIC_KEY is not a valid secp256k1 public key.' });
+          setMessage({ type: 'error', text: "This form's registered encryption key is not a valid secp256k1 public key." });
           setFormDisabled(true);
         }
       }
@@ -348,7 +352,7 @@ export default function SubmitFormPage() {
     }
 
     setLoading(false);
-  }, [formData, formLoading, formError]);
+  }, [formData, formLoading, formError, keyData, keyLoading, keyError]);
 
   const handleAnswerChange = (questionId: string, value: AnswerValue) => {
     const newAnswers = { ...answers, [questionId]: value };
@@ -416,14 +420,10 @@ export default function SubmitFormPage() {
       );
 
       // Encrypt answers client-side so plaintext never appears on-chain
-      const masterPubKey = process.env.NEXT_PUBLIC_MASTER_PUBLIC_KEY;
-      if (!masterPubKey) {
-        throw new Error('NEXT_PUBLIC_MASTER_PUBLIC_KEY not configured');
-      }
-      if (!COMPRESSED_PUBKEY_REGEX.test(masterPubKey)) {
-        throw new Error('NEXT_PUBLIC_MASTER_PUBLIC_KEY must be a 66-char hex compressed secp256k1 public key (starts with 02 or 03)');
+      if (!formPubKey) {
+        throw new Error('Form encryption key not registered');
       }
-      const encryptedAnswers = encryptFormAnswers(masterPubKey, form.id, visibleAnswers);
+      const encryptedAnswers = encryptFormAnswers(formPubKey, visibleAnswers);
 
       // Send encrypted blob instead of plaintext answers
       const result = await callOutLayer('SubmitForm', { encrypted_answers: encryptedAnswers });
@@ -21,6 +21,13 @@ interface ReadResponsesPayload {
   has_more: boolean;
   /** Authoritative offset for next page (optional for rolling deploy compatibility) */
   next_offset?: number;
+  /** Per-phase WASI timing in milliseconds (diagnostics only) */
+  timings_ms?: {
+    fetch_form: number;
+    fetch_submissions: number;
+    derive_key: number;
+    decrypt: number;
+  };
 }
 
 const PAGE_SIZE = 50;
@@ -35,6 +42,8 @@ export default function ResponsesPage() {
   const [totalCount, setTotalCount] = useState(0);
   const [hasMore, setHasMore] = useState(false);
   const [currentOffset, setCurrentOffset] = useState(0);
+  // True once a page has loaded successfully: an empty result then means "no submissions yet"
+  const [loaded, setLoaded] = useState(false);
   const [sortField, setSortField] = useState<SortField>('submitted_at');
   const [sortDirection, setSortDirection] = useState<SortDirection>('desc');
   const loadGenRef = useRef(0);
@@ -46,6 +55,7 @@ export default function ResponsesPage() {
       setResponses([]);
       setHasMore(false);
       setCurrentOffset(0);
+      setLoaded(false);
       setSkippedCount(0);
       setSkippedSubmissions([]);
       setTotalCount(0);
@@ -90,6 +100,7 @@ export default function ResponsesPage() {
       setSkippedSubmissions([]);
       setTotalCount(0);
       setHasMore(false);
+      setLoaded(false);
     }
     setLoadingResponses(true);
     // Generate ephemeral keypair — private key never leaves browser memory.
@@ -112,7 +123,12 @@ export default function ResponsesPage() {
       if (gen !== loadGenRef.current) return;
 
       if (!encryptedResult?.encrypted_payload) {
-        setMessage({ type: 'error', text: encryptedResult?.error || 'Unexpected response: no encrypted payload' });
+        // An empty form is a successful empty page (see `loaded`); code 'upstream_unavailable'
+        // means db-api failed, so say so rather than implying there is nothing to show
+        const text = encryptedResult?.code === 'upstream_unavailable'
+          ? `Could not load responses: ${encryptedResult.error}`
+          : encryptedResult?.error || 'Unexpected response: no encrypted payload';
+        setMessage({ type: 'error', text });
         return;
       }
 
@@ -162,6 +178,7 @@ export default function ResponsesPage() {
         setSkippedSubmissions(payload.skipped_submissions || []);
       }
       setTotalCount(payload.total_count || 0);
+      setLoaded(true);
       setHasMore(payload.has_more || false);
       if (payload.next_offset != null) {
         setCurrentOffset(payload.next_offset);
@@ -326,7 +343,9 @@ export default function ResponsesPage() {
 
             {responses.length === 0 ? (
               <div className="px-6 py-12 text-center text-gray-500">
-                No responses yet. Click &ldquo;Load Responses&rdquo; above to fetch and decrypt submissions.
+                {loaded
+                  ? 'No submissions yet. Share the form link to start collecting responses.'
+                  : <>No responses yet. Click &ldquo;Load Responses&rdquo; above to fetch and decrypt submissions.</>}
               </div>
             ) : (
               <>